@@ -0,0 +1,221 @@
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+
+use clap::Parser;
+use log::{error, info, LevelFilter};
+
+use dognut::department::common::constant::{PORT, UDP_PORT};
+use dognut::department::control::keymap::Keymap;
+use dognut::department::net::auth::Access;
+use dognut::department::net::config::NetConfig;
+use dognut::department::net::discovery::{self, Server};
+use dognut::department::net::frame_queue::FrameQueue;
+use dognut::department::net::transport::Stream;
+use dognut::department::net::{client, session, tls};
+use dognut::department::preview::cell_encoder::CellMode;
+use dognut::department::preview::graphics::{self, GraphicsProtocol};
+use dognut::department::preview::palette::{self, ColorDepth, Dither};
+use dognut::department::tui::terminal_dimension;
+use dognut::department::tui::viewer::ViewerApp;
+use dognut::pb::avpacket::Codec;
+
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+// what `client::decode_frame` understands.
+const CODECS: [Codec; 2] = [Codec::Raw, Codec::Jpeg];
+
+/// show the stream of a dognut server in the terminal
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct ViewArgs {
+    /// server to connect to as host or host:port, found on the lan when not given.
+    #[arg(long)]
+    server: Option<String>,
+
+    /// how terminal cells encode pixels, finer modes render at a higher resolution.
+    #[arg(long, value_enum, default_value_t=CellMode::HalfBlock)]
+    cell: CellMode,
+
+    /// terminal color depth, detected from COLORTERM/TERM when not given.
+    #[arg(long, value_enum)]
+    color: Option<ColorDepth>,
+
+    /// dithering used when the color depth is lower than truecolor.
+    #[arg(long, value_enum, default_value_t=Dither::None)]
+    dither: Dither,
+
+    /// image protocol of the terminal, detected by querying the terminal when not given.
+    #[arg(long, value_enum)]
+    graphics: Option<GraphicsProtocol>,
+
+    /// key bindings file, the keys are sent to the server as the actions they are bound to.
+    #[arg(long)]
+    keymap: Option<String>,
+
+    /// first udp port servers answer discovery on, as given to their --udp-port.
+    #[arg(long, default_value_t=UDP_PORT)]
+    udp_port: u32,
+
+    /// token asked by the server, the view token to watch or the control token to send input too.
+    #[arg(long, default_value_t=String::new())]
+    token: String,
+
+    /// pem certificate to trust, like the self signed one of the server. the stream is read over tls with it.
+    #[arg(long)]
+    ca: Option<String>,
+
+    /// list the servers on the lan and exit.
+    #[arg(long, default_value_t=false)]
+    list: bool,
+}
+
+fn resolve(server: &str) -> Option<SocketAddr> {
+    let mut addrs = match server.to_socket_addrs() {
+        Ok(addrs) => addrs,
+        Err(_) => (server, PORT as u16).to_socket_addrs().ok()?,
+    };
+    addrs.next()
+}
+
+// the name the certificate of `server` is checked against.
+fn host_name(server: &str) -> String {
+    if let Ok(ip) = server.parse::<IpAddr>() {
+        return ip.to_string();
+    }
+    match server.parse::<SocketAddr>() {
+        Ok(addr) => addr.ip().to_string(),
+        Err(_) => server.rsplit_once(':').filter(|(_, port)| port.parse::<u16>().is_ok()).map_or(server, |(host, _)| host).to_string(),
+    }
+}
+
+// a server this viewer can watch.
+fn compatible(server: &Server) -> bool {
+    let announcement = &server.announcement;
+    announcement.version == session::PROTOCOL_VERSION
+        && announcement.codecs.iter().any(|c| c.enum_value().map_or(false, |c| CODECS.contains(&c)))
+}
+
+fn print_servers(servers: &[Server]) {
+    if servers.is_empty() {
+        println!("no server found");
+    }
+    for server in servers {
+        let a = &server.announcement;
+        let codecs: Vec<String> = a.codecs.iter().map(|c| format!("{:?}", c)).collect();
+        let mut notes = String::new();
+        if a.tls {
+            notes.push_str("  tls");
+        }
+        if a.token_required {
+            notes.push_str("  token");
+        }
+        if !compatible(server) {
+            notes.push_str("  (incompatible)");
+        }
+        println!("{}  {}  {}  {}x{} at {} fps  {}  {} watching{}", server.addr, a.name, a.model, a.width, a.height,
+                 a.fps, codecs.join(","), a.clients, notes);
+    }
+}
+
+fn main() {
+    // the terminal shows the stream, logs go to stderr.
+    let env = env_logger::Env::default();
+    env_logger::Builder::from_env(env).target(env_logger::Target::Stderr)
+        .filter_level(LevelFilter::Info).format_timestamp_millis().init();
+
+    let args = ViewArgs::parse();
+
+    let keymap = match &args.keymap {
+        Some(path) => match Keymap::load(path) {
+            Ok(keymap) => keymap,
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        },
+        None => Keymap::default(),
+    };
+
+    let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    let net_config = NetConfig { udp_port: args.udp_port, ..NetConfig::default() };
+    let discover = || {
+        info!("looking for a server on the lan");
+        rt.block_on(discovery::discover(&net_config, DISCOVERY_TIMEOUT)).unwrap_or_else(|e| {
+            error!("discovery failed: {}", e);
+            Vec::new()
+        })
+    };
+    if args.list {
+        print_servers(&discover());
+        return;
+    }
+    let addr = match &args.server {
+        Some(server) => resolve(server),
+        None => {
+            let servers = discover();
+            let server = servers.into_iter().find(compatible);
+            if let Some(server) = &server {
+                info!("found {} showing {}", server.announcement.name, server.announcement.model);
+            }
+            server.map(|s| s.addr)
+        }
+    };
+    let addr = match addr {
+        Some(addr) => addr,
+        None => {
+            error!("no server found, pass one with --server");
+            return;
+        }
+    };
+    let host = args.server.as_deref().map_or_else(|| addr.ip().to_string(), host_name);
+    let stream = rt.block_on(async {
+        let stream = tokio::net::TcpStream::connect(addr).await?;
+        match &args.ca {
+            Some(ca) => tls::connect(stream, &host, ca).await,
+            None => Ok(Box::new(stream) as Box<dyn Stream>),
+        }
+    });
+    let mut stream = match stream {
+        Ok(stream) => stream,
+        Err(e) => {
+            error!("could not connect to {}: {}", addr, e);
+            return;
+        }
+    };
+    let (header, access) = match rt.block_on(session::connect(&mut stream, &CODECS, terminal_dimension(), &args.token)) {
+        Ok(accepted) => accepted,
+        Err(e) => {
+            error!("{} refused the session: {}", addr, e);
+            return;
+        }
+    };
+    info!("connected to {}, {:?} stream of {}x{} at {} fps", addr, header.codec, header.width, header.height, header.fps);
+    if access == Access::View {
+        info!("view only, the server ignores the input of this viewer");
+    }
+    let size = (header.width, header.height);
+
+    // room for one frame, a newer one replaces a frame the terminal didn't draw yet.
+    let frames = Arc::new(FrameQueue::new(1));
+    let (packet_tx, packet_rx) = tokio::sync::mpsc::unbounded_channel();
+    let requests = packet_tx.clone();
+    let received = frames.clone();
+    std::thread::Builder::new().name("dognut_view_net".into()).spawn(move || {
+        let (read, write) = tokio::io::split(stream);
+        let result = rt.block_on(async {
+            tokio::spawn(client::send(write, packet_rx));
+            client::receive(read, size, received.clone(), requests).await
+        });
+        if let Err(e) = result {
+            error!("stream from {} ended: {}", addr, e);
+        }
+        received.close();
+    }).unwrap();
+
+    let color = args.color.unwrap_or_else(palette::detect);
+    let protocol = args.graphics.unwrap_or_else(graphics::detect);
+    if let Err(e) = ViewerApp::new(frames, packet_tx, keymap, args.cell, color, args.dither, protocol).run() {
+        error!("viewer error: {}", e);
+    }
+}
@@ -1,6 +1,7 @@
 use log::{error, LevelFilter};
+use dognut::department::net::config::NetConfig;
 use dognut::department::net::router;
-use dognut::department::view::window;
+use dognut::department::view::{headless, window};
 use dognut::department::types::multi_sender::MultiSender;
 use dognut::department::types::msg;
 #[cfg(feature = "rtc")]
@@ -8,10 +9,16 @@ use dognut::department::video::encode::RgbaEncoder;
 #[cfg(feature = "image_encoder")]
 use dognut::department::video::ImgEncoder;
 use dognut::department::common::{self_type};
+use dognut::department::control::input_log::InputReplay;
+use dognut::department::control::keymap::KEYMAP;
 use dognut::department::common::constant::{HEIGHT, WIDTH};
 use dognut::department::pipeline::rasterizer::RasterRunner;
 use dognut::department::pipeline::shader::LambertianShader;
 use dognut::department::preview::vector::Vector3;
+#[cfg(feature = "image_encoder")]
+use dognut::department::preview::graphics;
+#[cfg(feature = "image_encoder")]
+use dognut::department::tui::terminal_dimension;
 use dognut::department::tui::tui_split::TuiSplitApp;
 use dognut::department::tui::tui_with_window::TuiWinApp;
 
@@ -24,28 +31,58 @@ fn main() {
         filter_level(LevelFilter::Info).format_timestamp_millis().init();
 
     let arg = &ARG;
+    if arg.print_keymap {
+        print!("{}", KEYMAP.to_config());
+        return;
+    }
+    let net_config = match NetConfig::from_args(arg) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("bad network config: {}", e);
+            return;
+        }
+    };
+    if arg.print_net_config {
+        print!("{}", net_config.to_config());
+        return;
+    }
 
     log::info!(target:"wgpu_core", "hello");
 
+    if let (true, Some(path)) = (arg.render_a_picture, &arg.replay) {
+        let replay = match InputReplay::load(path) {
+            Ok(replay) => replay,
+            Err(e) => {
+                error!("could not load input log: {}", e);
+                return;
+            }
+        };
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        if let Err(e) = rt.block_on(headless::replay(replay, &arg.replay_frames)) {
+            error!("headless replay failed: {}", e);
+        }
+        return;
+    }
+
     let (net_sender, net_receiver) = crossbeam_channel::unbounded::<msg::TransferMsg>();
     let (win_sender, win_receiver) = crossbeam_channel::unbounded::<msg::TransferMsg>();
     let (enc_sender, enc_receiver) = crossbeam_channel::unbounded::<msg::TransferMsg>();
 
     let ms = MultiSender::new(net_sender, enc_sender, win_sender);
 
-    router::Router::new(net_receiver, ms.clone()).run();
+    router::Router::new(net_receiver, ms.clone(), KEYMAP.clone()).run();
     #[cfg(feature = "rtc")]
     RgbaEncoder::run(enc_receiver, ms.clone(), (WIDTH, HEIGHT));
 
     #[cfg(feature = "image_encoder")]
-    ImgEncoder::run(enc_receiver, ms.clone(), ( if arg.term { 256} else {WIDTH}, if arg.term {79} else {HEIGHT}));
+    ImgEncoder::run(enc_receiver, ms.clone(), if arg.term && arg.split { graphics::protocol().render_dimension(arg.cell, terminal_dimension()) } else { (WIDTH, HEIGHT) });
 
     if arg.term {
         let tui_ms = ms.clone();
         if arg.split {
             let inner_rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
             inner_rt.block_on( async {
-                let result = TuiSplitApp::new(tui_ms).run().await;
+                let result = TuiSplitApp::new(tui_ms, KEYMAP.clone()).run().await;
                 if let Err(e) = result {
                     error!("tui split thread error: {}", e);
                 }
@@ -60,17 +97,16 @@ fn main() {
                                          Vector3::from_xyz(0., 0., -1.),
                                          Vector3::from_xyz(0., -1., 0.));
                 let shader = LambertianShader::new(Vector3::from_xyz(0., 1., 0.),
-                                                   0.8, 1., &camera, arg.term);
+                                                   0.8, 1., &camera);
                 let raster = RasterRunner::new(raster_ms, camera,
                                                Box::new(shader), arg.term);
                 let inner_rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
 
                 inner_rt.block_on(async {
                     let res = dognut::department::model::object_loader::ObjectLoader::load_triangle_resources(&arg.obj_path);
-                    let _dimension = (256, 79);
                     let camera = self_type::camera_instance(WIDTH, HEIGHT);
                     let state = dognut::wgpu::wgpu_helper::State::new(winit::dpi::LogicalSize { width: WIDTH, height: HEIGHT }, camera).await;
-                    let result = TuiWinApp::new(raster, res, tui_ms).run(Some(state));
+                    let result = TuiWinApp::new(raster, res, tui_ms, KEYMAP.clone()).run(Some(state));
                     if let Err(e) = result {
                         error!("tui return an error, {}", e.to_string());
                     };
@@ -82,6 +118,6 @@ fn main() {
         }
     } else {
         let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
-        rt.block_on(window::run(win_receiver, ms, arg.split)).expect("fail on block");
+        rt.block_on(window::run(win_receiver, ms, arg.split, KEYMAP.clone())).expect("fail on block");
     }
 }
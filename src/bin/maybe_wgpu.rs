@@ -12,6 +12,7 @@ use winit_input_helper::WinitInputHelper;
 use dognut::department::common::constant;
 use dognut::department::common::constant::{HEIGHT, WIDTH};
 use dognut::department::common::self_type;
+use dognut::department::control::keymap::KEYMAP;
 use dognut::department::model::object_loader::ObjectLoader;
 use dognut::department::model::triangle_resources::TriangleResources;
 
@@ -33,6 +34,10 @@ use dognut::wgpu::wgpu_helper::State;
 fn main() -> Result<(), Error>{
     env_logger::init();
     let arg = &ARG;
+    if arg.print_keymap {
+        print!("{}", KEYMAP.to_config());
+        return Ok(());
+    }
 
     let (net_sender, net_receiver) = crossbeam_channel::unbounded::<TransferMsg>();
     let (win_sender, win_receiver) = crossbeam_channel::unbounded::<TransferMsg>();
@@ -45,7 +50,7 @@ fn main() -> Result<(), Error>{
                              Vector3::from_xyz(0., -1., 0.));
 
     let shader = LambertianShader::new(Vector3::from_xyz(0., 1., 0.),
-                                       0.8, 1.,&camera, arg.term);
+                                       0.8, 1.,&camera);
 
 
     let raster = RasterRunner::new(ms.clone(), camera,
@@ -64,7 +69,7 @@ fn main() -> Result<(), Error>{
             #[cfg(feature = "rtc")]
             let handle = RgbaEncoder::run(enc_receiver, ms, (constant::WIDTH, constant::HEIGHT));
             let state = State::new(winit::dpi::LogicalSize { width: dimension.0 as u32, height: dimension.1 as u32 }, camera).await;
-            let result = TuiApp::new(raster).run(res, Some(state));
+            let result = TuiApp::new(raster, KEYMAP.clone()).run(res, Some(state));
             if let Err(e) = result {
                 error!("tui return an error, {}", e.to_string());
             };
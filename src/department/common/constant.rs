@@ -8,5 +8,16 @@ pub const WIDTH: u32 = 640;
 pub const WHOLE_WIDTH: u32 = 1280;
 pub const HEIGHT: u32 = 480;
 
+// updates per second of the render loops, and so frames per second of the stream.
+pub const FPS: u32 = 30;
+
+// fallback terminal size when the real one can't be queried.
+pub const TUI_WIDTH: u32 = 256;
+pub const TUI_HEIGHT: u32 = 79;
+
+// assumed pixel size of a terminal cell for sixel and kitty output.
+pub const GRAPHICS_CELL_WIDTH: u32 = 8;
+pub const GRAPHICS_CELL_HEIGHT: u32 = 16;
+
 pub const IS_NEED_FLIPV: bool = true;
 pub const IS_LEFT_HAND: bool = true;
@@ -1,3 +1,4 @@
+pub mod base64;
 pub mod constant;
 pub mod logger;
 pub mod self_type;
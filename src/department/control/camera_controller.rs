@@ -2,7 +2,8 @@ use std::f32::consts::FRAC_PI_2;
 use std::time::Duration;
 
 
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{KeyEvent, KeyEventKind, KeyModifiers};
+use log::info;
 use winit::dpi::PhysicalPosition;
 use winit::event::*;
 
@@ -10,9 +11,14 @@ use crate::department::view::camera_trait;
 
 
 use super::ModelController;
+use super::input_log::{InputEvent, InputLog};
+use super::keymap::{Action, Keymap};
 
 const SAFE_FRAC_PI_2: f32 = FRAC_PI_2 - 0.0001;
 
+/// scroll amount of one wheel line, about 100 pixels.
+pub const SCROLL_LINE: f32 = 100.0;
+
 #[derive(Debug)]
 pub struct CameraController {
     amount_left: f32,
@@ -29,6 +35,8 @@ pub struct CameraController {
     pub ctrl_pressed: bool,
     pub model_ctrl: ModelController,
     tui:bool,
+    log: Option<InputLog>,
+    keymap: Keymap,
 }
 
 impl CameraController {
@@ -47,104 +55,185 @@ impl CameraController {
             sensitivity,
             ctrl_pressed: false,
             model_ctrl: ModelController::new(speed, tui),
-            tui
+            tui,
+            log: None,
+            keymap: Keymap::default(),
         }
     }
 
+    /// the keys the controller maps to actions, the default keymap until one is set.
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+    }
+
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
     pub fn process_tui_keyboard(&mut self, key: &KeyEvent) -> bool {
-        if key.modifiers == KeyModifiers::CONTROL{
-            if key.kind == KeyEventKind::Press {
-                self.model_ctrl.process_keyboard_tui(key);
-            }
-            return true;
-        }
-        let amount = if key.kind == KeyEventKind::Press {1.0} else {0.0};
-        match key.code {
-            KeyCode::Backspace => {}
-            KeyCode::Left | KeyCode::Char('a') => {
-                self.amount_left += amount;
-            }
-            KeyCode::Right | KeyCode::Char('d') => {
-                self.amount_right += amount;
-            }
-            KeyCode::Up | KeyCode::Char('w') => {
-                self.amount_forward += amount;
-            }
-            KeyCode::Down | KeyCode::Char('s') => {
-                self.amount_backward += amount;
-            }
-            KeyCode::Char('q') | KeyCode::Esc => {
-                return false;
-            }
-            KeyCode::Char('x') => {
-                self.amount_up += amount;
-            }
-            KeyCode::Char('z') => {
-                self.amount_down += amount;
-            }
-            _ => {}
+        match self.keymap.tui_action(&key.code) {
+            Some(action) => self.process_action(action, key.kind == KeyEventKind::Press, key.modifiers.contains(KeyModifiers::CONTROL)),
+            None => true,
         }
-
-        return true;
     }
 
     pub fn process_keyboard(&mut self, key: VirtualKeyCode, state: ElementState) -> bool{
-        if self.ctrl_pressed {
-            return self.model_ctrl.process_keyboard(key, state);
+        match self.keymap.winit_action(key) {
+            Some(action) => self.process_action(action, state == ElementState::Pressed, self.ctrl_pressed),
+            None => true,
         }
+    }
 
-        let amount = if state == ElementState::Pressed { 1.0 } else { 0.0 };
-        match key {
-            VirtualKeyCode::W | VirtualKeyCode::Up => {
-                self.amount_forward = amount;
-                true
-            }
-            VirtualKeyCode::S | VirtualKeyCode::Down => {
-                self.amount_backward = amount;
-                true
-            }
-            VirtualKeyCode::A | VirtualKeyCode::Left => {
-                self.amount_left = amount;
-                true
-            }
-            VirtualKeyCode::D | VirtualKeyCode::Right => {
-                self.amount_right = amount;
-                true
-            }
-            VirtualKeyCode::Space => {
-                self.amount_up = amount;
-                true
-            }
-            VirtualKeyCode::LShift => {
-                self.amount_down = amount;
-                true
-            }
-
-            VirtualKeyCode::Q => {
-                false
-            }
-            _ => true,
+    /// apply an action coming from any input, `model` sends movement to the model instead of the camera.
+    /// returns false when the app should quit.
+    pub fn process_action(&mut self, action: Action, pressed: bool, model: bool) -> bool {
+        if action == Action::Quit {
+            return !pressed;
         }
+        self.input(InputEvent::Action { action, pressed, model });
+        true
     }
 
     pub fn process_mouse(&mut self, mouse_dx: f64, mouse_dy: f64) {
-        self.rotate_horizontal = mouse_dx as f32;
-        self.rotate_vertical = mouse_dy as f32;
+        self.input(InputEvent::Look { dx: mouse_dx as f32, dy: mouse_dy as f32 });
+    }
+
+    /// analog movement like a gamepad stick, both in -1..1.
+    pub fn process_movement(&mut self, forward: f32, right: f32) {
+        self.input(InputEvent::Movement { forward, right });
+    }
+
+    /// turn the model by a mouse drag, in mouse pixels.
+    pub fn process_model_rotation(&mut self, dx: f32, dy: f32) {
+        self.input(InputEvent::Rotate { dx, dy });
+    }
+
+    /// make `instance` the one the model controls move.
+    pub fn process_select(&mut self, instance: usize) {
+        self.input(InputEvent::Select(instance));
+    }
+
+    /// zoom by `lines` scroll wheel lines.
+    pub fn process_zoom(&mut self, lines: f32) {
+        self.input(InputEvent::Scroll(-lines * SCROLL_LINE));
     }
 
     pub fn process_scroll(&mut self, delta: &MouseScrollDelta) {
-        self.scroll = -match delta {
-            // I'm assuming a line is about 100 pixels
-            MouseScrollDelta::LineDelta(_, scroll) => scroll * 100.0,
+        let scroll = -match delta {
+            MouseScrollDelta::LineDelta(_, scroll) => scroll * SCROLL_LINE,
             MouseScrollDelta::PixelDelta(PhysicalPosition {
                                              y: scroll,
                                              ..
                                          }) => *scroll as f32,
         };
+        self.input(InputEvent::Scroll(scroll));
+    }
+
+    /// input of a remote viewer, see `net::remote_input`.
+    pub fn process_remote(&mut self, event: InputEvent) {
+        self.input(event);
+    }
+
+    /// record to or replay from `log`, see `input_log`.
+    pub fn set_input_log(&mut self, log: Option<InputLog>) {
+        self.log = log;
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        matches!(self.log, Some(InputLog::Replay(_)))
+    }
+
+    // live input is recorded, or dropped while a replay drives the controller.
+    fn input(&mut self, event: InputEvent) {
+        match self.log {
+            Some(InputLog::Record(ref mut recorder)) => recorder.push(event),
+            Some(InputLog::Replay(_)) => return,
+            None => {}
+        }
+        self.apply(event);
+    }
+
+    fn apply(&mut self, event: InputEvent) {
+        match event {
+            InputEvent::Action { action, pressed, model } => {
+                if (model && action.is_movement()) || action.is_model_action() {
+                    self.model_ctrl.process_action(action, pressed);
+                    return;
+                }
+
+                let amount = if pressed { 1.0 } else { 0.0 };
+                let slot = match action {
+                    Action::Forward => &mut self.amount_forward,
+                    Action::Backward => &mut self.amount_backward,
+                    Action::Left => &mut self.amount_left,
+                    Action::Right => &mut self.amount_right,
+                    Action::Up => &mut self.amount_up,
+                    Action::Down => &mut self.amount_down,
+                    _ => return,
+                };
+                if self.tui {
+                    *slot += amount;
+                } else {
+                    *slot = amount;
+                }
+            }
+            InputEvent::Look { dx, dy } => {
+                // several terminal drag events can arrive between two updates.
+                if self.tui {
+                    self.rotate_horizontal += dx;
+                    self.rotate_vertical += dy;
+                } else {
+                    self.rotate_horizontal = dx;
+                    self.rotate_vertical = dy;
+                }
+            }
+            InputEvent::Movement { forward, right } => {
+                self.amount_forward = forward.max(0.);
+                self.amount_backward = (-forward).max(0.);
+                self.amount_right = right.max(0.);
+                self.amount_left = (-right).max(0.);
+            }
+            InputEvent::Rotate { dx, dy } => self.model_ctrl.process_rotation(dx, dy),
+            InputEvent::Select(instance) => self.model_ctrl.select(instance),
+            InputEvent::Scroll(scroll) => {
+                if self.tui {
+                    self.scroll += scroll;
+                } else {
+                    self.scroll = scroll;
+                }
+            }
+        }
+    }
+
+    // the time step of this update, the recorded one during a replay.
+    fn begin_frame(&mut self, dt: Duration) -> Duration {
+        match self.log {
+            Some(InputLog::Record(ref mut recorder)) => {
+                recorder.end_frame(dt);
+                dt
+            }
+            Some(InputLog::Replay(ref mut replay)) => match replay.next_frame() {
+                Some(frame) => {
+                    for event in frame.events {
+                        self.apply(event);
+                    }
+                    frame.dt
+                }
+                None => {
+                    info!("input replay finished, back to live input");
+                    self.log = None;
+                    dt
+                }
+            },
+            None => dt,
+        }
     }
 
-    pub fn update_camera<T: camera_trait::CameraTrait>(&mut self, camera: &mut T, dt: Duration) {
-        let dt = dt.as_secs_f32();
+    /// move the camera by the input since the last update, returns the time step that was used,
+    /// which the model update has to use too.
+    pub fn update_camera<T: camera_trait::CameraTrait>(&mut self, camera: &mut T, dt: Duration) -> Duration {
+        let step = self.begin_frame(dt);
+        let dt = step.as_secs_f32();
 
         // Move forward/backward and left/right
         let forward_dt = (self.amount_forward - self.amount_backward) * self.speed * dt;
@@ -192,5 +281,6 @@ impl CameraController {
         // camera.position += right * (self.amount_right - self.amount_left) * self.speed * dt;
         //
 
+        step
     }
 }
@@ -0,0 +1,212 @@
+use gilrs::{Axis, Button, EventType, GamepadId, Gilrs};
+use log::{debug, info, warn};
+
+use super::camera_controller::CameraController;
+use super::keymap::Action;
+
+/// stick values closer to the center than this are treated as zero.
+pub const STICK_DEADZONE: f32 = 0.15;
+
+/// how far a fully tilted right stick turns the camera per frame, in mouse pixels.
+const LOOK_SPEED: f32 = 12.;
+
+/// how far a fully pressed trigger zooms per frame, in scroll wheel lines.
+const ZOOM_SPEED: f32 = 0.1;
+
+/// what the gamepad asks the camera controller to do.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PadCommand {
+    /// a button acting like a key, `model` moves the model instead of the camera.
+    Action { action: Action, pressed: bool, model: bool },
+    /// left stick, both in -1..1.
+    Move { forward: f32, right: f32 },
+    /// right stick, both in -1..1.
+    Look { x: f32, y: f32 },
+    /// triggers, positive zooms in.
+    Zoom(f32),
+}
+
+/// turns gamepad buttons and axes into controller commands, kept apart from gilrs so it can be
+/// fed with synthetic input.
+#[derive(Debug, Default)]
+pub struct PadMapper {
+    left_stick: (f32, f32),
+    right_stick: (f32, f32),
+    left_trigger: f32,
+    right_trigger: f32,
+    // last movement sent, the keyboard owns the amounts while the stick rests.
+    moving: (f32, f32),
+}
+
+fn deadzone(v: f32) -> f32 {
+    if v.abs() < STICK_DEADZONE { 0. } else { v }
+}
+
+impl PadMapper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// forget every held axis and return the releases of the held buttons, used when the
+    /// gamepad goes away. the next `frame` still stops a movement that was sent before.
+    pub fn reset(&mut self) -> Vec<PadCommand> {
+        *self = Self { moving: self.moving, ..Self::default() };
+        [Button::DPadUp, Button::DPadDown, Button::DPadLeft, Button::DPadRight, Button::LeftTrigger, Button::RightTrigger]
+            .into_iter()
+            .filter_map(|b| self.button(b, false))
+            .collect()
+    }
+
+    pub fn button(&mut self, button: Button, pressed: bool) -> Option<PadCommand> {
+        let (action, model) = match button {
+            Button::DPadUp => (Action::Forward, true),
+            Button::DPadDown => (Action::Backward, true),
+            Button::DPadLeft => (Action::Left, true),
+            Button::DPadRight => (Action::Right, true),
+            Button::LeftTrigger => (Action::Down, false),
+            Button::RightTrigger => (Action::Up, false),
+            Button::North => (Action::TogglePresentation, false),
+            Button::West => (Action::ResetModel, false),
+            Button::East => (Action::NextInstance, false),
+            _ => return None,
+        };
+        Some(PadCommand::Action { action, pressed, model })
+    }
+
+    /// analog button values, only the lower triggers are used.
+    pub fn button_value(&mut self, button: Button, value: f32) {
+        match button {
+            Button::LeftTrigger2 => self.left_trigger = value,
+            Button::RightTrigger2 => self.right_trigger = value,
+            _ => {}
+        }
+    }
+
+    pub fn axis(&mut self, axis: Axis, value: f32) {
+        match axis {
+            Axis::LeftStickX => self.left_stick.0 = value,
+            Axis::LeftStickY => self.left_stick.1 = value,
+            Axis::RightStickX => self.right_stick.0 = value,
+            Axis::RightStickY => self.right_stick.1 = value,
+            // some drivers report the lower triggers as axes.
+            Axis::LeftZ => self.left_trigger = value.max(0.),
+            Axis::RightZ => self.right_trigger = value.max(0.),
+            _ => {}
+        }
+    }
+
+    /// commands for the held sticks and triggers, call it once per update.
+    /// movement is only sent when it changed, look and zoom whenever they are non zero.
+    pub fn frame(&mut self) -> Vec<PadCommand> {
+        let mut commands = Vec::new();
+        let moving = (deadzone(self.left_stick.1), deadzone(self.left_stick.0));
+        if moving != self.moving {
+            self.moving = moving;
+            commands.push(PadCommand::Move { forward: moving.0, right: moving.1 });
+        }
+        let look = (deadzone(self.right_stick.0), deadzone(self.right_stick.1));
+        if look != (0., 0.) {
+            commands.push(PadCommand::Look { x: look.0, y: look.1 });
+        }
+        let zoom = deadzone(self.right_trigger) - deadzone(self.left_trigger);
+        if zoom != 0. {
+            commands.push(PadCommand::Zoom(zoom));
+        }
+        commands
+    }
+
+    pub fn event(&mut self, event: &EventType) -> Option<PadCommand> {
+        match *event {
+            EventType::ButtonPressed(button, _) => self.button(button, true),
+            EventType::ButtonReleased(button, _) => self.button(button, false),
+            EventType::ButtonChanged(button, value, _) => {
+                self.button_value(button, value);
+                None
+            }
+            EventType::AxisChanged(axis, value, _) => {
+                self.axis(axis, value);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// send a command down the same path keyboard and mouse input take.
+pub fn apply(controller: &mut CameraController, command: PadCommand) {
+    match command {
+        PadCommand::Action { action, pressed, model } => {
+            controller.process_action(action, pressed, model);
+        }
+        PadCommand::Move { forward, right } => controller.process_movement(forward, right),
+        // stick y points up, mouse y points down.
+        PadCommand::Look { x, y } => controller.process_mouse((x * LOOK_SPEED) as f64, (-y * LOOK_SPEED) as f64),
+        PadCommand::Zoom(amount) => controller.process_zoom(amount * ZOOM_SPEED),
+    }
+}
+
+/// follows the first connected gamepad and feeds it into a camera controller.
+pub struct GamepadInput {
+    gilrs: Option<Gilrs>,
+    active: Option<GamepadId>,
+    mapper: PadMapper,
+}
+
+impl GamepadInput {
+    pub fn new() -> Self {
+        let gilrs = match Gilrs::new() {
+            Ok(g) => Some(g),
+            Err(e) => {
+                warn!("gamepad support disabled: {}", e);
+                None
+            }
+        };
+        let active = gilrs.as_ref().and_then(|g| g.gamepads().next().map(|(id, _)| id));
+        if let (Some(g), Some(id)) = (&gilrs, active) {
+            info!("using gamepad {}", g.gamepad(id).name());
+        }
+        Self { gilrs, active, mapper: PadMapper::new() }
+    }
+
+    /// pump gilrs events and apply them, call it once per update.
+    pub fn update(&mut self, controller: &mut CameraController) {
+        let gilrs = match self.gilrs.as_mut() {
+            Some(g) => g,
+            None => return,
+        };
+
+        while let Some(gilrs::Event { id, event, .. }) = gilrs.next_event() {
+            match event {
+                EventType::Connected => {
+                    debug!("gamepad {} connected: {}", id, gilrs.gamepad(id).name());
+                    if self.active.is_none() {
+                        info!("using gamepad {}", gilrs.gamepad(id).name());
+                        self.active = Some(id);
+                    }
+                }
+                EventType::Disconnected => {
+                    debug!("gamepad {} disconnected", id);
+                    if self.active == Some(id) {
+                        // stop whatever the sticks were doing and fall back to another pad.
+                        for command in self.mapper.reset() {
+                            apply(controller, command);
+                        }
+                        self.active = gilrs.gamepads().map(|(other, _)| other).find(|other| *other != id);
+                    }
+                }
+                _ if self.active == Some(id) => {
+                    if let Some(command) = self.mapper.event(&event) {
+                        apply(controller, command);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if self.active.is_some() || self.mapper.moving != (0., 0.) {
+            for command in self.mapper.frame() {
+                apply(controller, command);
+            }
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::time::Duration;
+
+use log::{error, info, warn};
+
+use super::keymap::Action;
+use crate::util::ARG;
+
+const HEADER: &str = "# dognut input log v1";
+// the terminal controller sums the input between updates, the window one keeps the last value, so
+// a log only replays the same with the controller it was recorded from.
+const TERMINAL_MODE: &str = "terminal";
+const WINDOW_MODE: &str = "window";
+
+/// one change to the controller input, as it reached `CameraController`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputEvent {
+    Action { action: Action, pressed: bool, model: bool },
+    /// camera look, in mouse pixels.
+    Look { dx: f32, dy: f32 },
+    /// camera zoom, in the units of the scroll amount.
+    Scroll(f32),
+    /// analog movement, both in -1..1.
+    Movement { forward: f32, right: f32 },
+    /// model rotation, in mouse pixels.
+    Rotate { dx: f32, dy: f32 },
+    /// an instance picked with the mouse.
+    Select(usize),
+}
+
+/// the input that arrived before an update, and the time step of that update.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub index: u64,
+    pub dt: Duration,
+    pub events: Vec<InputEvent>,
+}
+
+impl Frame {
+    /// the frame in the log file format. floats are printed in their shortest exact form so a
+    /// replay reads back the same bits.
+    pub fn to_lines(&self) -> String {
+        let mut out = format!("frame {} {}\n", self.index, self.dt.as_nanos());
+        for event in &self.events {
+            let line = match event {
+                InputEvent::Action { action, pressed, model } =>
+                    format!("action {} {} {}", action.name(), *pressed as u8, *model as u8),
+                InputEvent::Look { dx, dy } => format!("look {} {}", dx, dy),
+                InputEvent::Scroll(amount) => format!("scroll {}", amount),
+                InputEvent::Movement { forward, right } => format!("move {} {}", forward, right),
+                InputEvent::Rotate { dx, dy } => format!("rotate {} {}", dx, dy),
+                InputEvent::Select(instance) => format!("select {}", instance),
+            };
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// the header line of a log recorded from the terminal controller when `tui`, else from the window one.
+pub fn header(tui: bool) -> String {
+    format!("{} {}", HEADER, if tui { TERMINAL_MODE } else { WINDOW_MODE })
+}
+
+/// whether the log was recorded from the terminal controller, logs without a mode are from the window.
+pub fn parse_mode(text: &str) -> Result<bool, String> {
+    let first = text.lines().next().unwrap_or("").trim();
+    match first.strip_prefix(HEADER).map(str::trim) {
+        Some(TERMINAL_MODE) => Ok(true),
+        Some(WINDOW_MODE) | Some("") | None => Ok(false),
+        Some(other) => Err(format!("line 1: unknown controller mode `{}`", other)),
+    }
+}
+
+/// parse a whole log, as written by `InputRecorder`.
+pub fn parse(text: &str) -> Result<Vec<Frame>, String> {
+    let mut frames: Vec<Frame> = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let err = |what: &str| format!("line {}: {}", n + 1, what);
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let float = |i: usize| words.get(i).and_then(|w| w.parse::<f32>().ok()).ok_or_else(|| err("expected a number"));
+        let flag = |i: usize| match words.get(i) {
+            Some(&"0") => Ok(false),
+            Some(&"1") => Ok(true),
+            _ => Err(err("expected 0 or 1")),
+        };
+
+        if words[0] == "frame" {
+            let index = words.get(1).and_then(|w| w.parse().ok()).ok_or_else(|| err("expected a frame index"))?;
+            let nanos: u64 = words.get(2).and_then(|w| w.parse().ok()).ok_or_else(|| err("expected a time step"))?;
+            frames.push(Frame { index, dt: Duration::from_nanos(nanos), events: Vec::new() });
+            continue;
+        }
+
+        let event = match words[0] {
+            "action" => {
+                let action = words.get(1).and_then(|w| Action::from_name(w)).ok_or_else(|| err("unknown action"))?;
+                InputEvent::Action { action, pressed: flag(2)?, model: flag(3)? }
+            }
+            "look" => InputEvent::Look { dx: float(1)?, dy: float(2)? },
+            "scroll" => InputEvent::Scroll(float(1)?),
+            "move" => InputEvent::Movement { forward: float(1)?, right: float(2)? },
+            "rotate" => InputEvent::Rotate { dx: float(1)?, dy: float(2)? },
+            "select" => InputEvent::Select(words.get(1).and_then(|w| w.parse().ok()).ok_or_else(|| err("expected an instance"))?),
+            other => return Err(err(&format!("unknown event `{}`", other))),
+        };
+        frames.last_mut().ok_or_else(|| err("event before the first frame"))?.events.push(event);
+    }
+    Ok(frames)
+}
+
+/// writes every update with the input that led to it.
+pub struct InputRecorder {
+    out: BufWriter<File>,
+    frame: u64,
+    pending: Vec<InputEvent>,
+}
+
+impl InputRecorder {
+    /// a log of the input to the terminal controller when `tui`, else to the window one.
+    pub fn create(path: &str, tui: bool) -> std::io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "{}", header(tui))?;
+        Ok(Self { out, frame: 0, pending: Vec::new() })
+    }
+
+    pub fn push(&mut self, event: InputEvent) {
+        self.pending.push(event);
+    }
+
+    /// close the frame that is about to be updated with `dt`.
+    pub fn end_frame(&mut self, dt: Duration) {
+        let frame = Frame { index: self.frame, dt, events: std::mem::take(&mut self.pending) };
+        self.frame += 1;
+        // flushed every frame, the window event loop never returns to drop the writer.
+        let result = self.out.write_all(frame.to_lines().as_bytes()).and_then(|_| self.out.flush());
+        if let Err(e) = result {
+            error!("could not write input log: {}", e);
+        }
+    }
+}
+
+/// feeds recorded frames back, one per update.
+pub struct InputReplay {
+    frames: VecDeque<Frame>,
+    tui: bool,
+}
+
+impl InputReplay {
+    pub fn new(frames: Vec<Frame>, tui: bool) -> Self {
+        Self { frames: frames.into(), tui }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        Ok(InputReplay::new(parse(text)?, parse_mode(text)?))
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        InputReplay::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// whether the log was recorded from the terminal controller.
+    pub fn tui(&self) -> bool {
+        self.tui
+    }
+
+    pub fn next_frame(&mut self) -> Option<Frame> {
+        self.frames.pop_front()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+pub enum InputLog {
+    Record(InputRecorder),
+    Replay(InputReplay),
+}
+
+impl std::fmt::Debug for InputLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputLog::Record(recorder) => write!(f, "Record(frame {})", recorder.frame),
+            InputLog::Replay(replay) => write!(f, "Replay({} frames left)", replay.frames.len()),
+        }
+    }
+}
+
+/// the log asked for with `--record` or `--replay`, replay wins when both are given. `tui` tells
+/// which controller the app feeds.
+pub fn from_args(tui: bool) -> Option<InputLog> {
+    if let Some(path) = &ARG.replay {
+        return match InputReplay::load(path) {
+            Ok(replay) => {
+                info!("replaying input from {}", path);
+                if replay.tui() != tui {
+                    warn!("{} was recorded from the {} controller, the replay may differ", path,
+                          if replay.tui() { TERMINAL_MODE } else { WINDOW_MODE });
+                }
+                Some(InputLog::Replay(replay))
+            }
+            Err(e) => {
+                error!("could not load input log: {}", e);
+                None
+            }
+        };
+    }
+    if let Some(path) = &ARG.record {
+        return match InputRecorder::create(path, tui) {
+            Ok(recorder) => {
+                info!("recording input to {}", path);
+                Some(InputLog::Record(recorder))
+            }
+            Err(e) => {
+                error!("could not create input log {}: {}", path, e);
+                None
+            }
+        };
+    }
+    None
+}
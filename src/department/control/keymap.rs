@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use crossterm::event::KeyCode;
+use lazy_static::lazy_static;
+use log::error;
+use winit::event::VirtualKeyCode;
+
+use crate::util::ARG;
+
+/// what a key does, independent of where the key came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Forward,
+    Backward,
+    Left,
+    Right,
+    Up,
+    Down,
+    TogglePresentation,
+    ResetModel,
+    NextInstance,
+    PreviousInstance,
+    ToggleHud,
+    ToggleBrowser,
+    /// ask the server for a keyframe, for a remote viewer whose picture broke up.
+    RequestKeyframe,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: [Action; 14] = [
+        Action::Forward, Action::Backward, Action::Left, Action::Right, Action::Up, Action::Down,
+        Action::TogglePresentation, Action::ResetModel, Action::NextInstance, Action::PreviousInstance,
+        Action::ToggleHud, Action::ToggleBrowser, Action::RequestKeyframe, Action::Quit,
+    ];
+
+    /// name used in the keymap file.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Forward => "forward",
+            Action::Backward => "backward",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::TogglePresentation => "toggle_presentation",
+            Action::ResetModel => "reset_model",
+            Action::NextInstance => "next_instance",
+            Action::PreviousInstance => "previous_instance",
+            Action::ToggleHud => "toggle_hud",
+            Action::ToggleBrowser => "toggle_browser",
+            Action::RequestKeyframe => "request_keyframe",
+            Action::Quit => "quit",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Forward => "move forward",
+            Action::Backward => "move backward",
+            Action::Left => "move left",
+            Action::Right => "move right",
+            Action::Up => "move up",
+            Action::Down => "move down",
+            Action::TogglePresentation => "spin the model",
+            Action::ResetModel => "reset model rotation",
+            Action::NextInstance => "select the next instance",
+            Action::PreviousInstance => "select the previous instance",
+            Action::ToggleHud => "toggle the overlay",
+            Action::ToggleBrowser => "browse models",
+            Action::RequestKeyframe => "ask the server for a keyframe, in a viewer",
+            Action::Quit => "quit",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.iter().find(|a| a.name() == name).cloned()
+    }
+
+    /// actions that always go to the model controller.
+    pub fn is_model_action(&self) -> bool {
+        matches!(self, Action::TogglePresentation | Action::ResetModel | Action::NextInstance | Action::PreviousInstance)
+    }
+
+    /// movement actions move the model instead of the camera while ctrl is held.
+    pub fn is_movement(&self) -> bool {
+        matches!(self, Action::Forward | Action::Backward | Action::Left | Action::Right | Action::Up | Action::Down)
+    }
+}
+
+const DEFAULT_BINDINGS: &[(Action, &[&str])] = &[
+    (Action::Forward, &["w", "up"]),
+    (Action::Backward, &["s", "down"]),
+    (Action::Left, &["a", "left"]),
+    (Action::Right, &["d", "right"]),
+    // terminals don't report a lone shift press, so x/z work everywhere.
+    (Action::Up, &["x", "space"]),
+    (Action::Down, &["z", "lshift"]),
+    (Action::TogglePresentation, &["p"]),
+    (Action::ResetModel, &["r"]),
+    (Action::NextInstance, &["n", "tab"]),
+    (Action::PreviousInstance, &["b"]),
+    (Action::ToggleHud, &["h"]),
+    (Action::ToggleBrowser, &["m"]),
+    (Action::RequestKeyframe, &["k"]),
+    (Action::Quit, &["q", "esc"]),
+];
+
+/// name of a terminal key in the keymap file.
+pub fn tui_key_name(code: &KeyCode) -> Option<String> {
+    let name = match code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_lowercase().to_string(),
+        KeyCode::F(n) => format!("f{}", n),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Home => "home".to_string(),
+        KeyCode::End => "end".to_string(),
+        KeyCode::PageUp => "pageup".to_string(),
+        KeyCode::PageDown => "pagedown".to_string(),
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// name of a window key in the keymap file.
+pub fn winit_key_name(key: VirtualKeyCode) -> String {
+    match key {
+        VirtualKeyCode::Key0 => "0".to_string(),
+        VirtualKeyCode::Key1 => "1".to_string(),
+        VirtualKeyCode::Key2 => "2".to_string(),
+        VirtualKeyCode::Key3 => "3".to_string(),
+        VirtualKeyCode::Key4 => "4".to_string(),
+        VirtualKeyCode::Key5 => "5".to_string(),
+        VirtualKeyCode::Key6 => "6".to_string(),
+        VirtualKeyCode::Key7 => "7".to_string(),
+        VirtualKeyCode::Key8 => "8".to_string(),
+        VirtualKeyCode::Key9 => "9".to_string(),
+        VirtualKeyCode::Return => "enter".to_string(),
+        VirtualKeyCode::Escape => "esc".to_string(),
+        VirtualKeyCode::Back => "backspace".to_string(),
+        VirtualKeyCode::LShift => "lshift".to_string(),
+        VirtualKeyCode::RShift => "rshift".to_string(),
+        VirtualKeyCode::PageUp => "pageup".to_string(),
+        VirtualKeyCode::PageDown => "pagedown".to_string(),
+        // letters, arrows, space, tab, f1..f24 and the rest are named after the variant.
+        other => format!("{:?}", other).to_lowercase(),
+    }
+}
+
+/// key bindings, loaded from the file given by `--keymap` on top of the defaults.
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: HashMap<String, Action>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut bindings = HashMap::new();
+        for (action, keys) in DEFAULT_BINDINGS {
+            for key in keys.iter() {
+                bindings.insert(key.to_string(), *action);
+            }
+        }
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// parse a keymap file. every line looks like `forward = w, up`, `#` starts a comment.
+    /// actions that are not in the file keep their default keys.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut keymap = Keymap::default();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (name, keys) = line.split_once('=')
+                .ok_or_else(|| format!("line {}: expected `action = key, key`", n + 1))?;
+            let action = Action::from_name(name.trim())
+                .ok_or_else(|| format!("line {}: unknown action `{}`", n + 1, name.trim()))?;
+
+            keymap.bindings.retain(|_, a| *a != action);
+            for key in keys.split(',').map(|k| k.trim().to_lowercase()).filter(|k| !k.is_empty()) {
+                keymap.bindings.insert(key, action);
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        Keymap::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn action(&self, key_name: &str) -> Option<Action> {
+        self.bindings.get(key_name).cloned()
+    }
+
+    pub fn tui_action(&self, code: &KeyCode) -> Option<Action> {
+        tui_key_name(code).and_then(|k| self.action(&k))
+    }
+
+    pub fn winit_action(&self, key: VirtualKeyCode) -> Option<Action> {
+        self.action(&winit_key_name(key))
+    }
+
+    /// keys bound to `action`, sorted.
+    pub fn keys(&self, action: Action) -> Vec<&str> {
+        let mut keys: Vec<&str> = self.bindings.iter().filter(|(_, a)| **a == action).map(|(k, _)| k.as_str()).collect();
+        keys.sort();
+        keys
+    }
+
+    /// every binding as a json object of key names to action names, for the browser viewer.
+    pub fn to_json(&self) -> String {
+        let mut out = json::JsonValue::new_object();
+        for (key, action) in self.bindings.iter() {
+            out[key.as_str()] = action.name().into();
+        }
+        out.dump()
+    }
+
+    /// the keymap in the file format, as printed by `--print-keymap`.
+    pub fn to_config(&self) -> String {
+        let mut out = String::from("# hold ctrl to move the model instead of the camera\n");
+        for action in Action::ALL {
+            out.push_str(&format!("# {}\n{} = {}\n", action.description(), action.name(), self.keys(action).join(", ")));
+        }
+        out
+    }
+}
+
+lazy_static! {
+    pub static ref KEYMAP: Keymap = match &ARG.keymap {
+        Some(path) => Keymap::load(path).unwrap_or_else(|e| {
+            error!("could not load keymap, using the default one: {}", e);
+            Keymap::default()
+        }),
+        None => Keymap::default(),
+    };
+}
@@ -2,18 +2,25 @@ use std::f32::consts::PI;
 
 use cgmath::{InnerSpace, Rotation3};
 
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
-use winit::event::{ElementState, VirtualKeyCode};
-
-
+use crate::department::model::scene;
 use crate::wgpu::instance::Instance;
 
+use self::keymap::Action;
+
 pub mod camera_controller;
+pub mod gamepad;
+pub mod input_log;
+pub mod keymap;
+pub mod terminal_mouse;
+
+// radians the model turns per dragged mouse pixel.
+const ROTATE_SPEED: f32 = 0.01;
 
 #[derive(Debug)]
 pub struct ModelController {
-    position: cgmath::Vector3<f32>,
-    rotation: cgmath::Quaternion<f32>,
+    instances: Vec<Instance>,
+    // the instance that moves, turns and spins.
+    selected: usize,
     amount_left: f32,
     amount_right: f32,
     amount_forward: f32,
@@ -31,99 +38,91 @@ pub struct ModelController {
 
 impl ModelController {
     pub fn new(speed: f32, tui: bool) -> Self {
-        let p = cgmath::Vector3{x: 0.0, y: 0., z:1.};
-        let q = cgmath::Quaternion::from_axis_angle(p.normalize(), cgmath::Deg(0.0));
-        Self {position: p,
+        Self {instances: scene::grid(1),
+            selected: 0,
             theta: 0.,
-            rotation: q,
             presentation_mode: false,
             amount_left: 0., amount_right: 0., amount_forward: 0., amount_backward: 0., amount_up: 0.,
             amount_down: 0., rotate_horizontal: 0., rotate_vertical: 0., scroll: 0., speed , tui}
     }
 
-    pub fn process_keyboard_tui(&mut self, key: &KeyEvent) -> bool {
-        let amount = if key.kind == KeyEventKind::Press {1.0} else {0.0};
-        match key.code {
-            KeyCode::Backspace => {}
-            KeyCode::Left | KeyCode::Char('a') => {
-                self.amount_left += amount;
-            }
-            KeyCode::Right | KeyCode::Char('d') => {
-                self.amount_right += amount;
-            }
-            KeyCode::Up | KeyCode::Char('w') => {
-                self.amount_forward += amount;
-            }
-            KeyCode::Down | KeyCode::Char('s') => {
-                self.amount_backward += amount;
-            }
-            KeyCode::Char('x') => {
-                self.amount_up += amount;
-            }
-            KeyCode::Char('z') => {
-                self.amount_down += amount;
-            }
-            KeyCode::Char('p') => {
-                self.presentation_mode = !self.presentation_mode;
-            }
-            KeyCode::Char('r') => {
-                let p = cgmath::Vector3{x:0.0, y:0.0, z:1.0};
-                self.rotation = cgmath::Quaternion::from_axis_angle(p.normalize(), cgmath::Deg(0.0));
-            }
-            KeyCode::Modifier(_) => {}
-            _ => {}
-        }
+    /// replace the scene, the first instance gets selected.
+    pub fn set_instances(&mut self, instances: Vec<Instance>) {
+        assert!(!instances.is_empty(), "a scene needs at least one instance");
+        self.instances = instances;
+        self.selected = 0;
+    }
 
-        return true;
+    pub fn instances(&self) -> &[Instance] {
+        &self.instances
+    }
 
+    pub fn selected(&self) -> usize {
+        self.selected
     }
 
-    pub fn process_keyboard(&mut self, key: VirtualKeyCode, state:ElementState) -> bool {
-        let amount = if state == ElementState::Pressed { 1.0 } else { 0.0 };
-        match key {
-            VirtualKeyCode::W | VirtualKeyCode::Up => {
-                self.amount_forward = amount;
-                true
-            }
-            VirtualKeyCode::S | VirtualKeyCode::Down => {
-                self.amount_backward = amount;
-                true
-            }
-            VirtualKeyCode::A | VirtualKeyCode::Left => {
-                self.amount_left = amount;
-                true
-            }
-            VirtualKeyCode::D | VirtualKeyCode::Right => {
-                self.amount_right = amount;
-                true
-            }
-            VirtualKeyCode::P => {
-                self.presentation_mode = !self.presentation_mode;
-                true
-            }
-            VirtualKeyCode::R => {
-                let p = cgmath::Vector3{x:0.0, y:0.0, z:1.0};
-                self.rotation = cgmath::Quaternion::from_axis_angle(p.normalize(), cgmath::Deg(0.0));
-                true
-            }
-            VirtualKeyCode::Space => {
-                self.amount_up = amount;
-                true
-            }
-            VirtualKeyCode::LShift => {
-                self.amount_down = amount;
-                true
-            }
-            _ => true,
+    pub fn select(&mut self, index: usize) {
+        if index < self.instances.len() {
+            self.selected = index;
+        }
+    }
+
+    pub fn process_action(&mut self, action: Action, pressed: bool) {
+        let amount = if pressed { 1.0 } else { 0.0 };
+        let slot = match action {
+            Action::Forward => &mut self.amount_forward,
+            Action::Backward => &mut self.amount_backward,
+            Action::Left => &mut self.amount_left,
+            Action::Right => &mut self.amount_right,
+            Action::Up => &mut self.amount_up,
+            Action::Down => &mut self.amount_down,
+            Action::TogglePresentation => {
+                if pressed {
+                    self.presentation_mode = !self.presentation_mode;
+                }
+                return;
+            }
+            Action::ResetModel => {
+                if pressed {
+                    self.instances[self.selected].rotation = cgmath::Quaternion::from_angle_y(cgmath::Deg(0.0));
+                }
+                return;
+            }
+            Action::NextInstance => {
+                if pressed {
+                    self.selected = (self.selected + 1) % self.instances.len();
+                }
+                return;
+            }
+            Action::PreviousInstance => {
+                if pressed {
+                    self.selected = (self.selected + self.instances.len() - 1) % self.instances.len();
+                }
+                return;
+            }
+            _ => return,
+        };
+        // terminals only send presses, so the amounts are summed up and reset every update.
+        if self.tui {
+            *slot += amount;
+        } else {
+            *slot = amount;
         }
     }
 
+    /// turn the model by a mouse drag, in mouse pixels.
+    pub fn process_rotation(&mut self, dx: f32, dy: f32) {
+        self.rotate_horizontal += dx;
+        self.rotate_vertical += dy;
+    }
+
     pub fn update_model(&mut self, dt: std::time::Duration) -> Vec<crate::wgpu::instance::InstanceRaw>{
         let dt = dt.as_secs_f32();
+        let instance = &mut self.instances[self.selected];
 
-        self.position.z +=  (self.amount_forward - self.amount_backward) * self.speed * dt;
-        self.position.x +=  (self.amount_right - self.amount_left) * self.speed * dt;
-        self.position.y += (self.amount_up - self.amount_down) * self.speed * dt;
+        instance.position.z +=  (self.amount_forward - self.amount_backward) * self.speed * dt;
+        instance.position.x +=  (self.amount_right - self.amount_left) * self.speed * dt;
+        instance.position.y += (self.amount_up - self.amount_down) * self.speed * dt;
 
 
         if self.tui {
@@ -135,15 +134,21 @@ impl ModelController {
             self.amount_backward = 0.0;
         }
 
+        if self.rotate_horizontal != 0. || self.rotate_vertical != 0. {
+            let turn = cgmath::Quaternion::from_angle_y(cgmath::Rad(self.rotate_horizontal * ROTATE_SPEED))
+                * cgmath::Quaternion::from_angle_x(cgmath::Rad(self.rotate_vertical * ROTATE_SPEED));
+            instance.rotation = (turn * instance.rotation).normalize();
+            self.rotate_horizontal = 0.;
+            self.rotate_vertical = 0.;
+        }
+
         if self.presentation_mode {
             self.theta += PI * dt * 0.2;
             let rad = cgmath::Rad(self.theta);
-            self.rotation = cgmath::Quaternion::from_angle_y(rad);// * cgmath::Quaternion::from_angle_x(rad);
+            instance.rotation = cgmath::Quaternion::from_angle_y(rad);// * cgmath::Quaternion::from_angle_x(rad);
         }
 
-        let instances = vec![Instance{position: self.position.clone(), rotation:self.rotation.clone()}];
-
-        let data = instances.iter().map(Instance::to_raw).collect::<Vec<_>>();
+        let data = self.instances.iter().map(Instance::to_raw).collect::<Vec<_>>();
 
         return data;
     }
@@ -0,0 +1,89 @@
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+use super::camera_controller::CameraController;
+
+/// mouse pixels one dragged cell column is worth, rows are about twice as tall.
+const CELL_DRAG: f64 = 8.;
+
+/// where the camera input of a terminal mouse goes.
+pub trait MouseSink {
+    /// look around, in mouse pixels.
+    fn look(&mut self, dx: f32, dy: f32);
+    /// turn the model, in mouse pixels.
+    fn rotate(&mut self, dx: f32, dy: f32);
+    /// zoom by wheel lines.
+    fn zoom(&mut self, lines: f32);
+}
+
+impl MouseSink for CameraController {
+    fn look(&mut self, dx: f32, dy: f32) {
+        self.process_mouse(dx as f64, dy as f64);
+    }
+
+    fn rotate(&mut self, dx: f32, dy: f32) {
+        self.process_model_rotation(dx, dy);
+    }
+
+    fn zoom(&mut self, lines: f32) {
+        self.process_zoom(lines);
+    }
+}
+
+/// what a mouse event means for the app, beyond moving the camera.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MouseAction {
+    None,
+    /// a press and release of the left button on the same cell, in cells.
+    Click { column: u16, row: u16 },
+}
+
+/// turns terminal mouse events into the camera input the window gets from winit.
+/// dragging with the left button looks around, with the right button or ctrl it turns the model,
+/// the wheel zooms.
+#[derive(Debug, Default)]
+pub struct TerminalMouse {
+    // cell of the last press or drag, None while no button is held.
+    last: Option<(u16, u16)>,
+    dragged: bool,
+}
+
+impl TerminalMouse {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle<S: MouseSink>(&mut self, event: &MouseEvent, sink: &mut S) -> MouseAction {
+        let at = (event.column, event.row);
+        match event.kind {
+            MouseEventKind::Down(_) => {
+                self.last = Some(at);
+                self.dragged = false;
+            }
+            MouseEventKind::Drag(button) => {
+                if let Some(last) = self.last {
+                    let dx = (at.0 as f64 - last.0 as f64) * CELL_DRAG;
+                    let dy = (at.1 as f64 - last.1 as f64) * CELL_DRAG * 2.;
+                    if button == MouseButton::Right || event.modifiers.contains(KeyModifiers::CONTROL) {
+                        sink.rotate(dx as f32, dy as f32);
+                    } else {
+                        sink.look(dx as f32, dy as f32);
+                    }
+                    self.dragged |= at != last;
+                }
+                self.last = Some(at);
+            }
+            MouseEventKind::Up(button) => {
+                let clicked = button == MouseButton::Left && !self.dragged && self.last.is_some();
+                self.last = None;
+                self.dragged = false;
+                if clicked {
+                    return MouseAction::Click { column: at.0, row: at.1 };
+                }
+            }
+            MouseEventKind::ScrollUp => sink.zoom(1.0),
+            MouseEventKind::ScrollDown => sink.zoom(-1.0),
+            _ => {}
+        }
+        MouseAction::None
+    }
+}
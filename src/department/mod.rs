@@ -1,11 +1,12 @@
-use gilrs::GamepadId;
-use gilrs::Gilrs;
+use log::info;
 use pixels::Pixels;
 use winit::window::WindowId;
 use winit_input_helper::WinitInputHelper;
 
 use crate::department::common::self_type;
 use crate::department::control::camera_controller::CameraController;
+use crate::department::control::gamepad::GamepadInput;
+use crate::department::net::remote_input::{self, RemoteControl};
 
 pub mod preview;
 pub mod view;
@@ -13,6 +14,7 @@ pub mod net;
 pub mod control;
 pub mod tui;
 pub mod pipeline;
+pub mod pick;
 pub mod model;
 pub mod common;
 pub mod types;
@@ -24,8 +26,8 @@ pub struct Game {
     state: self_type::StateImp,
     controls: CameraController,
     input: WinitInputHelper,
-    gilrs: Gilrs,
-    gamepad: Option<GamepadId>,
+    gamepad: GamepadInput,
+    remote: RemoteControl,
     paused: bool,
     id: WindowId,
 }
@@ -37,63 +39,30 @@ impl Game {
             state,
             controls: CameraController::new(2.0, 0.2, false),
             input: WinitInputHelper::new(),
-            gilrs: Gilrs::new().unwrap(), // XXX: Don't unwrap.
-            gamepad: None,
+            gamepad: GamepadInput::new(),
+            remote: RemoteControl::new(remote_input::receiver()),
             paused: false,
             id,
         }
     }
 
+    /// report what is under `pixel` of the rendered frame and select its instance.
+    fn pick(&mut self, pixel: (u32, u32)) {
+        let ndc = pick::ndc(pixel, self.state.size());
+        let hit = self.state.pick(ndc, self.state.size(), &self.state.camera_controller.model_ctrl);
+        match (hit, self.state.picker()) {
+            (Some(hit), Some(scene)) => {
+                info!("picked {}", scene.describe(&hit));
+                let instance = hit.instance;
+                self.state.camera_controller.process_select(instance);
+            }
+            _ => info!("picked nothing"),
+        }
+    }
+
+    /// pump gamepad and remote viewer events into the camera controller, before the state update.
     fn update_controls(&mut self) {
-        todo!();
-        // // Pump the gilrs event loop and find an active gamepad
-        // while let Some(gilrs::Event { id, event, .. }) = self.gilrs.next_event() {
-        //     let pad = self.gilrs.gamepad(id);
-        //     if self.gamepad.is_none() {
-        //         debug!("Gamepad with id {} is connected: {}", id, pad.name());
-        //         self.gamepad = Some(id);
-        //     } else if event == gilrs::ev::EventType::Disconnected {
-        //         debug!("Gamepad with id {} is disconnected: {}", id, pad.name());
-        //         self.gamepad = None;
-        //     }
-        // }
-        //
-        // self.controls = {
-        //     // Keyboard controls
-        //     let mut left = self.input.key_held(VirtualKeyCode::Left);
-        //     let mut right = self.input.key_held(VirtualKeyCode::Right);
-        //     let mut fire = self.input.key_pressed(VirtualKeyCode::Space);
-        //     let mut pause = self.input.key_pressed(VirtualKeyCode::Pause)
-        //         | self.input.key_pressed(VirtualKeyCode::P);
-        //
-        //     // GamePad controls
-        //     if let Some(id) = self.gamepad {
-        //         let gamepad = self.gilrs.gamepad(id);
-        //
-        //         left |= gamepad.is_pressed(Button::DPadLeft);
-        //         right |= gamepad.is_pressed(Button::DPadRight);
-        //         fire |= gamepad.button_data(Button::South).map_or(false, |button| {
-        //             button.is_pressed() && button.counter() == self.gilrs.counter()
-        //         });
-        //         pause |= gamepad.button_data(Button::Start).map_or(false, |button| {
-        //             button.is_pressed() && button.counter() == self.gilrs.counter()
-        //         });
-        //     }
-        //     self.gilrs.inc();
-        //
-        //     if pause {
-        //         self.paused = !self.paused;
-        //     }
-        //
-        //     let direction = if left {
-        //         Direction::Left
-        //     } else if right {
-        //         Direction::Right
-        //     } else {
-        //         Direction::Still
-        //     };
-        //
-        //     Controls { direction, fire }
-        //};
+        self.gamepad.update(&mut self.state.camera_controller);
+        self.remote.update(&mut self.state.camera_controller);
     }
 }
@@ -3,5 +3,6 @@ pub mod object_buffer;
 pub mod object_loader;
 pub mod render_object;
 pub mod triangle_resources;
+pub mod scene;
 
 
@@ -136,14 +136,19 @@ impl ObjectLoader {
 
     // only load one resources for now
     pub fn load_triangle_resources(path: &str) -> TriangleResources {
+        Self::try_load_triangle_resources(path).expect("Failed to OBJ load file")
+    }
+
+    pub fn try_load_triangle_resources(path: &str) -> anyhow::Result<TriangleResources> {
         let model_path = Path::new(path);
         let (mut models, materials) =
             tobj::load_obj(
                 path,
                 &tobj::LoadOptions::default(),
-            )
-                .expect("Failed to OBJ load file");
-        assert!(models.len() > 0);
+            )?;
+        if models.is_empty() {
+            anyhow::bail!("{} has no model", path);
+        }
 
         let mut mat = materials.unwrap_or_default();
 
@@ -153,7 +158,7 @@ impl ObjectLoader {
 
         println!("we've got {} triangles in total.", model.mesh.indices.len() / 3);
 
-        if let Some(i) = model.mesh.material_id {
+        if let Some(i) = model.mesh.material_id.filter(|i| *i < mat.len()) {
             if model_path.is_relative() {
                 let texture_path = model_path.parent().unwrap().join(Path::new(&mat[i].diffuse_texture));
                 let texture = image::open(texture_path);
@@ -168,6 +173,6 @@ impl ObjectLoader {
             }
         }
 
-        triangle_resources
+        Ok(triangle_resources)
     }
 }
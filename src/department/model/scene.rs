@@ -0,0 +1,67 @@
+use cgmath::{Deg, Quaternion, Rotation3, Vector3};
+use log::error;
+
+use crate::util::ARG;
+use crate::wgpu::instance::Instance;
+
+/// distance between two neighbours of a spawned grid.
+pub const SPACE_BETWEEN: f32 = 3.0;
+
+/// where a lone model is placed, in front of the camera.
+pub const ORIGIN: Vector3<f32> = Vector3 { x: 0., y: 0., z: 1. };
+
+fn place(x: f32, y: f32, z: f32, yaw: f32) -> Instance {
+    Instance { position: Vector3 { x, y, z }, rotation: Quaternion::from_angle_y(Deg(yaw)) }
+}
+
+/// `count` instances in a square grid, centered left to right and going away from the camera.
+/// a single instance sits at `ORIGIN`.
+pub fn grid(count: usize) -> Vec<Instance> {
+    let cols = (count as f32).sqrt().ceil().max(1.) as usize;
+    (0..count).map(|i| {
+        let (row, col) = (i / cols, i % cols);
+        let x = (col as f32 - (cols - 1) as f32 / 2.) * SPACE_BETWEEN;
+        place(ORIGIN.x + x, ORIGIN.y, ORIGIN.z - row as f32 * SPACE_BETWEEN, 0.)
+    }).collect()
+}
+
+/// parse a scene file, one instance per line as `x y z` or `x y z yaw`, yaw in degrees.
+/// `#` starts a comment.
+pub fn parse(text: &str) -> Result<Vec<Instance>, String> {
+    let mut instances = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let values = line.split_whitespace()
+            .map(|w| w.parse::<f32>())
+            .collect::<Result<Vec<f32>, _>>()
+            .map_err(|e| format!("line {}: {}", n + 1, e))?;
+        match values[..] {
+            [x, y, z] => instances.push(place(x, y, z, 0.)),
+            [x, y, z, yaw] => instances.push(place(x, y, z, yaw)),
+            _ => return Err(format!("line {}: expected `x y z` or `x y z yaw`", n + 1)),
+        }
+    }
+    if instances.is_empty() {
+        return Err("no instances".to_string());
+    }
+    Ok(instances)
+}
+
+pub fn load(path: &str) -> Result<Vec<Instance>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    parse(&text).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// the instances from `--scene`, or a grid of `--instances`.
+pub fn from_args() -> Vec<Instance> {
+    if let Some(path) = &ARG.scene {
+        match load(path) {
+            Ok(instances) => return instances,
+            Err(e) => error!("could not load scene, spawning a grid instead: {}", e),
+        }
+    }
+    grid(ARG.instances.max(1))
+}
@@ -0,0 +1,40 @@
+/// what a client may do once its token is checked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// it gets the stream, its input is dropped.
+    View,
+    /// it gets the stream and may send input, as far as the control policy lets it.
+    Control,
+}
+
+/// the shared secrets of the server, empty ones are not asked for.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tokens {
+    /// needed to watch, the control token works as well.
+    pub view: String,
+    /// needed to send input. without it every viewer may.
+    pub control: String,
+}
+
+// compares every byte, so the time taken doesn't tell how much of a guess was right.
+fn same(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+impl Tokens {
+    /// what a client presenting `token` may do, None when it is turned away.
+    pub fn access(&self, token: &str) -> Option<Access> {
+        if !self.control.is_empty() && same(token, &self.control) {
+            return Some(Access::Control);
+        }
+        if !self.view.is_empty() && !same(token, &self.view) {
+            return None;
+        }
+        Some(if self.control.is_empty() { Access::Control } else { Access::View })
+    }
+
+    /// whether watching needs a token.
+    pub fn required(&self) -> bool {
+        !self.view.is_empty()
+    }
+}
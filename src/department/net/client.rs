@@ -0,0 +1,115 @@
+use std::io;
+use std::sync::Arc;
+
+use log::{info, warn};
+use protobuf::Message;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+use crate::department::net::frame_queue::FrameQueue;
+use crate::department::net::session;
+use crate::pb::avpacket::VideoPacket;
+use crate::pb::netpacket::{NetPacket, PacketKind};
+
+/// packets bigger than this are treated as a broken stream, an uncompressed 4k frame fits.
+pub const MAX_PACKET_SIZE: usize = 64 * 1024 * 1024;
+
+/// one decoded frame of the stream.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VideoFrame {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// write one packet as the router frames it, a big endian length and the serialized packet.
+pub async fn write_packet<W: AsyncWrite + Unpin>(out: &mut W, packet: &NetPacket) -> io::Result<()> {
+    let serialized = packet.write_to_bytes().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    out.write_u32(serialized.len() as u32).await?;
+    out.write_all(&serialized).await
+}
+
+/// read one packet written by `write_packet`.
+pub async fn read_packet<R: AsyncRead + Unpin>(input: &mut R) -> io::Result<NetPacket> {
+    read_packet_limited(input, MAX_PACKET_SIZE).await
+}
+
+/// `read_packet`, refusing packets bigger than `max` bytes before anything is allocated for them.
+pub async fn read_packet_limited<R: AsyncRead + Unpin>(input: &mut R, max: usize) -> io::Result<NetPacket> {
+    let len = input.read_u32().await? as usize;
+    if len > max {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("packet of {} bytes is too big", len)));
+    }
+    let mut buf = vec![0; len];
+    input.read_exact(&mut buf).await?;
+    NetPacket::parse_from_bytes(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// the picture in a video packet. the payload is a jpeg from the image encoder, or raw rgba of
+/// `size` pixels, either bare or wrapped in a `VideoPacket`.
+pub fn decode_frame(packet: &NetPacket, size: (u32, u32)) -> Result<VideoFrame, String> {
+    if packet.kind.enum_value() != Ok(PacketKind::VideoPacket) {
+        return Err(format!("unexpected packet kind {:?}", packet.kind));
+    }
+    let data = if Some(packet.data.len()) == rgba_len(size) {
+        packet.data.clone()
+    } else {
+        VideoPacket::parse_from_bytes(&packet.data).map(|video| video.data).unwrap_or_else(|_| packet.data.clone())
+    };
+    decode_image(data, size)
+}
+
+/// the bytes of a raw rgba frame of `size` pixels, None when that doesn't fit in memory.
+pub fn rgba_len(size: (u32, u32)) -> Option<usize> {
+    (size.0 as usize).checked_mul(size.1 as usize)?.checked_mul(4)
+}
+
+fn decode_image(data: Vec<u8>, size: (u32, u32)) -> Result<VideoFrame, String> {
+    if data.starts_with(&[0xff, 0xd8]) {
+        let image = image::load_from_memory_with_format(&data, image::ImageFormat::Jpeg)
+            .map_err(|e| format!("bad jpeg: {}", e))?
+            .to_rgba8();
+        return Ok(VideoFrame { width: image.width(), height: image.height(), rgba: image.into_raw() });
+    }
+    if Some(data.len()) == rgba_len(size) {
+        return Ok(VideoFrame { width: size.0, height: size.1, rgba: data });
+    }
+    Err(format!("payload of {} bytes is neither jpeg nor {}x{} rgba", data.len(), size.0, size.1))
+}
+
+/// decode every packet of `stream` and pass the frames to `frames`. with room for one frame, a
+/// frame the terminal hasn't taken yet is replaced by the newer one, so a slow terminal always
+/// gets the newest. after a frame that can't be decoded a keyframe is asked for on `requests`.
+pub async fn receive<R: AsyncRead + Unpin>(mut stream: R, size: (u32, u32), frames: Arc<FrameQueue<VideoFrame>>,
+                                           requests: UnboundedSender<NetPacket>) -> io::Result<()> {
+    // one request until a frame decodes again.
+    let mut requested = false;
+    loop {
+        let packet = read_packet(&mut stream).await?;
+        let frame = match decode_frame(&packet, size) {
+            Ok(frame) => frame,
+            Err(e) => {
+                warn!("skip frame: {}", e);
+                if !requested {
+                    let _ = requests.send(session::keyframe_request_packet("decode error"));
+                    requested = true;
+                }
+                continue;
+            }
+        };
+        requested = false;
+        if frames.is_closed() {
+            info!("viewer closed, stop receiving");
+            return Ok(());
+        }
+        frames.push(frame);
+    }
+}
+
+/// write the packets of the viewer to the server until the viewer closes.
+pub async fn send<W: AsyncWrite + Unpin>(mut out: W, mut packets: UnboundedReceiver<NetPacket>) -> io::Result<()> {
+    while let Some(packet) = packets.recv().await {
+        write_packet(&mut out, &packet).await?;
+    }
+    Ok(())
+}
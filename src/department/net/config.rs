@@ -0,0 +1,173 @@
+use lazy_static::lazy_static;
+use log::error;
+
+use crate::department::common::constant;
+use crate::department::net::auth::Tokens;
+use crate::util::{Args, ARG};
+
+/// where the server listens and how much it gives each client, from the file given by
+/// `--net-config` with the command line on top.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetConfig {
+    /// address the stream and discovery sockets bind to.
+    pub bind: String,
+    /// first tcp port tried for the stream, 0 lets the system pick one.
+    pub port: u32,
+    /// first udp port tried for discovery queries.
+    pub udp_port: u32,
+    /// how many ports after the first one are tried when it is taken.
+    pub port_range: u32,
+    /// clients watching at once, 0 for no limit.
+    pub max_clients: usize,
+    /// bytes per second sent to one client, 0 for no limit.
+    pub client_bandwidth: u64,
+    /// secrets asked of viewers in the handshake.
+    pub tokens: Tokens,
+    /// pem certificate chain and private key, the stream port speaks tls when both are given.
+    pub tls_cert: Option<String>,
+    pub tls_key: Option<String>,
+}
+
+impl Default for NetConfig {
+    fn default() -> Self {
+        Self {
+            bind: constant::HOST.to_string(),
+            port: constant::PORT,
+            udp_port: constant::UDP_PORT,
+            port_range: constant::PORT_RANGE,
+            max_clients: 0,
+            client_bandwidth: 0,
+            tokens: Tokens::default(),
+            tls_cert: None,
+            tls_key: None,
+        }
+    }
+}
+
+fn number<T: std::str::FromStr>(n: usize, key: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("line {}: `{}` is not a number for {}", n + 1, value, key))
+}
+
+fn path(value: &str) -> Option<String> {
+    Some(value.to_string()).filter(|v| !v.is_empty())
+}
+
+fn tried_ports(first: u32, range: u32) -> impl Iterator<Item = u32> {
+    // a system picked port never collides, one try is enough.
+    (0..if first == 0 { 1 } else { range }).map(move |i| first + i)
+}
+
+impl NetConfig {
+    /// parse a config file. every line looks like `port = 9527`, `#` starts a comment. the
+    /// bandwidth is in kilobytes per second, keys that are not in the file keep their defaults.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut config = NetConfig::default();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line.split_once('=')
+                .ok_or_else(|| format!("line {}: expected `key = value`", n + 1))?;
+            let (key, value) = (key.trim(), value.trim());
+            match key {
+                "bind" => config.bind = value.to_string(),
+                "port" => config.port = number(n, key, value)?,
+                "udp_port" => config.udp_port = number(n, key, value)?,
+                "port_range" => config.port_range = number(n, key, value)?,
+                "max_clients" => config.max_clients = number(n, key, value)?,
+                "client_bandwidth" => config.client_bandwidth = number::<u64>(n, key, value)? * 1024,
+                "view_token" => config.tokens.view = value.to_string(),
+                "control_token" => config.tokens.control = value.to_string(),
+                "tls_cert" => config.tls_cert = path(value),
+                "tls_key" => config.tls_key = path(value),
+                _ => return Err(format!("line {}: unknown key `{}`", n + 1, key)),
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        NetConfig::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// the config file of `args`, or the defaults, with the options given on the command line.
+    pub fn from_args(args: &Args) -> Result<Self, String> {
+        let mut config = match &args.net_config {
+            Some(path) => NetConfig::load(path)?,
+            None => NetConfig::default(),
+        };
+        if let Some(bind) = &args.bind {
+            config.bind = bind.clone();
+        }
+        config.port = args.port.unwrap_or(config.port);
+        config.udp_port = args.udp_port.unwrap_or(config.udp_port);
+        config.port_range = args.port_range.unwrap_or(config.port_range);
+        config.max_clients = args.max_clients.unwrap_or(config.max_clients);
+        config.client_bandwidth = args.client_bandwidth.map_or(config.client_bandwidth, |kb| kb * 1024);
+        if let Some(token) = &args.view_token {
+            config.tokens.view = token.clone();
+        }
+        if let Some(token) = &args.control_token {
+            config.tokens.control = token.clone();
+        }
+        if let Some(cert) = &args.tls_cert {
+            config.tls_cert = path(cert);
+        }
+        if let Some(key) = &args.tls_key {
+            config.tls_key = path(key);
+        }
+        if config.port_range == 0 {
+            return Err("port_range must be at least 1".to_string());
+        }
+        if config.tls_cert.is_some() != config.tls_key.is_some() {
+            return Err("tls needs both tls_cert and tls_key".to_string());
+        }
+        Ok(config)
+    }
+
+    /// the ports tried for the stream, in order.
+    pub fn ports(&self) -> impl Iterator<Item = u32> {
+        tried_ports(self.port, self.port_range)
+    }
+
+    /// the ports tried for discovery queries, in order.
+    pub fn udp_ports(&self) -> impl Iterator<Item = u32> {
+        tried_ports(self.udp_port, self.port_range)
+    }
+
+    /// where servers broadcast their announcements, just past the ports they answer queries on.
+    pub fn announce_port(&self) -> u32 {
+        let first = if self.udp_port == 0 { constant::UDP_PORT } else { self.udp_port };
+        first + self.port_range
+    }
+
+    /// whether `clients` watching leave no room for another one.
+    pub fn is_full(&self, clients: usize) -> bool {
+        self.max_clients != 0 && clients >= self.max_clients
+    }
+
+    /// the config in the file format, as printed by `--print-net-config`.
+    pub fn to_config(&self) -> String {
+        format!("# address the server binds to, 127.0.0.1 keeps it on this machine\nbind = {}\n\
+                 # first tcp port of the stream, 0 lets the system pick one\nport = {}\n\
+                 # first udp port answering discovery queries\nudp_port = {}\n\
+                 # ports tried after the first one when it is taken\nport_range = {}\n\
+                 # clients watching at once, 0 for no limit\nmax_clients = {}\n\
+                 # kilobytes per second sent to one client, 0 for no limit\nclient_bandwidth = {}\n\
+                 # token needed to watch, empty lets anybody watch\nview_token = {}\n\
+                 # token needed to send input, empty lets every viewer\ncontrol_token = {}\n\
+                 # pem files, with both the stream is served over tls\ntls_cert = {}\ntls_key = {}\n",
+                self.bind, self.port, self.udp_port, self.port_range, self.max_clients, self.client_bandwidth / 1024,
+                self.tokens.view, self.tokens.control, self.tls_cert.as_deref().unwrap_or_default(),
+                self.tls_key.as_deref().unwrap_or_default())
+    }
+}
+
+lazy_static! {
+    pub static ref NET_CONFIG: NetConfig = NetConfig::from_args(&ARG).unwrap_or_else(|e| {
+        error!("could not load the network config, using the default one: {}", e);
+        NetConfig::default()
+    });
+}
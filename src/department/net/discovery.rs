@@ -0,0 +1,102 @@
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use log::debug;
+use protobuf::Message;
+use tokio::net::UdpSocket;
+
+use crate::department::net::config::NetConfig;
+use crate::department::net::session::{self, PROTOCOL_VERSION};
+use crate::pb::netpacket::{Announcement, DiscoveryQuery, NetPacket, PacketKind};
+
+/// how often a server announces itself unasked.
+pub const ANNOUNCE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// a server found on the lan.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Server {
+    /// where its stream is, the sender of the announcement at the announced port.
+    pub addr: SocketAddr,
+    pub announcement: Announcement,
+}
+
+/// the name a server announces when it isn't given one.
+pub fn host_name() -> String {
+    std::fs::read_to_string("/etc/hostname").ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "dognut".to_string())
+}
+
+/// one datagram, a packet serialized without a length prefix.
+pub fn to_datagram(packet: &NetPacket) -> Vec<u8> {
+    packet.write_to_bytes().unwrap()
+}
+
+pub fn query_datagram() -> Vec<u8> {
+    let mut query = DiscoveryQuery::new();
+    query.version = PROTOCOL_VERSION;
+    to_datagram(&session::packet(PacketKind::DiscoveryQuery, &query))
+}
+
+pub fn announcement_datagram(announcement: &Announcement) -> Vec<u8> {
+    to_datagram(&session::packet(PacketKind::Announcement, announcement))
+}
+
+/// whether a datagram is a query a server should answer. queries of other protocol versions are
+/// answered too, the client sees the version in the announcement.
+pub fn is_query(datagram: &[u8]) -> bool {
+    NetPacket::parse_from_bytes(datagram).map_or(false, |p| p.kind.enum_value() == Ok(PacketKind::DiscoveryQuery))
+}
+
+/// the announcement in a datagram, or why it isn't one.
+pub fn parse_announcement(datagram: &[u8]) -> Result<Announcement, String> {
+    let packet = NetPacket::parse_from_bytes(datagram).map_err(|e| format!("bad datagram: {}", e))?;
+    if packet.kind.enum_value() != Ok(PacketKind::Announcement) {
+        return Err(format!("expected an announcement, got {:?}", packet.kind));
+    }
+    Announcement::parse_from_bytes(&packet.data).map_err(|e| format!("bad announcement: {}", e))
+}
+
+/// list the servers on the lan. a query goes to every udp port of `config` a server may answer
+/// on, and for `timeout` the answers and the periodic announcements are collected, newest per server.
+pub async fn discover(config: &NetConfig, timeout: Duration) -> io::Result<Vec<Server>> {
+    // another viewer on this host may hold the announce port, answers to the query still arrive.
+    let sock = match UdpSocket::bind(format!("0.0.0.0:{}", config.announce_port())).await {
+        Ok(sock) => sock,
+        Err(_) => UdpSocket::bind("0.0.0.0:0").await?,
+    };
+    sock.set_broadcast(true)?;
+    let query = query_datagram();
+    for port in config.udp_ports() {
+        sock.send_to(&query, ("255.255.255.255", port as u16)).await?;
+    }
+
+    let mut servers: Vec<Server> = Vec::new();
+    let mut buf = [0; 2048];
+    let collect = async {
+        loop {
+            let (len, from) = match sock.recv_from(&mut buf).await {
+                Ok(received) => received,
+                Err(e) => return e,
+            };
+            let announcement = match parse_announcement(&buf[..len]) {
+                Ok(announcement) => announcement,
+                Err(e) => {
+                    debug!("ignore datagram from {}: {}", from, e);
+                    continue;
+                }
+            };
+            let addr = SocketAddr::new(from.ip(), announcement.port as u16);
+            servers.retain(|s| s.addr != addr);
+            servers.push(Server { addr, announcement });
+        }
+    };
+    if let Ok(e) = tokio::time::timeout(timeout, collect).await {
+        return Err(e);
+    }
+    Ok(servers)
+}
@@ -0,0 +1,91 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+
+/// the packets waiting for one client. it holds at most `capacity`, the oldest one makes room
+/// for a new one, so a slow client skips frames instead of stalling the others.
+pub struct FrameQueue<T> {
+    state: Mutex<State<T>>,
+    ready: Notify,
+    capacity: usize,
+}
+
+struct State<T> {
+    items: VecDeque<T>,
+    closed: bool,
+    dropped: u64,
+}
+
+impl<T> FrameQueue<T> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "a frame queue needs room for one frame");
+        Self { state: Mutex::new(State { items: VecDeque::new(), closed: false, dropped: 0 }), ready: Notify::new(), capacity }
+    }
+
+    /// queue `item`, returns false when the oldest waiting item was dropped for it.
+    pub fn push(&self, item: T) -> bool {
+        let kept = {
+            let mut state = self.state.lock().unwrap();
+            if state.closed {
+                return true;
+            }
+            let kept = state.items.len() < self.capacity;
+            if !kept {
+                state.items.pop_front();
+                state.dropped += 1;
+            }
+            state.items.push_back(item);
+            kept
+        };
+        self.ready.notify_one();
+        kept
+    }
+
+    /// the next item, None once the queue is closed.
+    pub async fn pop(&self) -> Option<T> {
+        loop {
+            {
+                let mut state = self.state.lock().unwrap();
+                if state.closed {
+                    return None;
+                }
+                if let Some(item) = state.items.pop_front() {
+                    return Some(item);
+                }
+            }
+            self.ready.notified().await;
+        }
+    }
+
+    /// the next item if one is waiting, for readers that can't wait.
+    pub fn try_pop(&self) -> Option<T> {
+        self.state.lock().unwrap().items.pop_front()
+    }
+
+    /// stop the client, waiting items are thrown away.
+    pub fn close(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.closed = true;
+        state.items.clear();
+        drop(state);
+        self.ready.notify_one();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.state.lock().unwrap().closed
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// how many items were dropped for newer ones.
+    pub fn dropped(&self) -> u64 {
+        self.state.lock().unwrap().dropped
+    }
+}
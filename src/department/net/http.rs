@@ -0,0 +1,115 @@
+use std::io;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+// request heads bigger than this are not from a browser we want to talk to.
+const MAX_HEAD_SIZE: usize = 8 * 1024;
+
+/// the head of an http request, the stream only carries gets without a body.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    // names are lowercase.
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    pub fn parse(head: &str) -> Result<Self, String> {
+        let mut lines = head.split("\r\n");
+        let request_line = lines.next().unwrap_or_default();
+        let mut parts = request_line.split_whitespace();
+        let (method, path) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(method), Some(path), Some(version)) if version.starts_with("HTTP/") => (method, path),
+            _ => return Err(format!("bad request line `{}`", request_line)),
+        };
+        let mut headers = Vec::new();
+        for line in lines.filter(|l| !l.is_empty()) {
+            let (name, value) = line.split_once(':').ok_or_else(|| format!("bad header `{}`", line))?;
+            headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
+        Ok(Self { method: method.to_string(), path: path.to_string(), headers })
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_lowercase();
+        self.headers.iter().find(|(n, _)| *n == name).map(|(_, v)| v.as_str())
+    }
+
+    /// the path without the query.
+    pub fn route(&self) -> &str {
+        self.path.split('?').next().unwrap()
+    }
+
+    /// the decoded value of `name` in the query.
+    pub fn query(&self, name: &str) -> Option<String> {
+        let query = self.path.split_once('?')?.1;
+        query.split('&').filter_map(|pair| pair.split_once('=')).find(|(n, _)| *n == name).map(|(_, v)| percent_decode(v))
+    }
+
+    /// the token of an `Authorization: Bearer <token>` header.
+    pub fn bearer(&self) -> Option<&str> {
+        let (scheme, token) = self.header("authorization")?.split_once(' ')?;
+        Some(token.trim()).filter(|_| scheme.eq_ignore_ascii_case("bearer"))
+    }
+}
+
+// undo the %xx escapes and plus signs of a query value, bad escapes are kept as they are.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).filter(|h| h.iter().all(u8::is_ascii_hexdigit))
+            .map(|h| u8::from_str_radix(std::str::from_utf8(h).unwrap(), 16).unwrap());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (b'+', _) => {
+                out.push(b' ');
+                i += 1;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// whether the first byte of a connection starts an http request rather than a length prefix. packets
+/// are at most `client::MAX_PACKET_SIZE`, 64mb, so the big endian length of a raw client starts with
+/// a byte of at most 4 and never with a letter.
+pub fn looks_like_http(first: u8) -> bool {
+    first.is_ascii_uppercase()
+}
+
+/// whether a comma separated header value like `keep-alive, Upgrade` has `token`.
+pub fn has_token(value: &str, token: &str) -> bool {
+    value.split(',').any(|t| t.trim().eq_ignore_ascii_case(token))
+}
+
+/// read a request head. it is read byte by byte, whatever follows the head stays in the stream.
+pub async fn read_request<R: AsyncRead + Unpin>(input: &mut R) -> io::Result<Request> {
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= MAX_HEAD_SIZE {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "request head is too big"));
+        }
+        head.push(input.read_u8().await?);
+    }
+    let head = String::from_utf8(head).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Request::parse(head.trim_end()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// write a whole response, the connection closes after it.
+pub async fn write_response<W: AsyncWrite + Unpin>(out: &mut W, status: &str, content_type: &str, body: &[u8]) -> io::Result<()> {
+    let head = format!("HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\n\
+                        Connection: close\r\n\r\n", status, content_type, body.len());
+    out.write_all(head.as_bytes()).await?;
+    out.write_all(body).await?;
+    out.flush().await
+}
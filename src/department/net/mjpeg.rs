@@ -0,0 +1,39 @@
+use std::io;
+
+use protobuf::Message;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::pb::avpacket::VideoPacket;
+use crate::pb::netpacket::{NetPacket, PacketKind};
+
+/// separates the jpegs of an mjpeg stream.
+pub const BOUNDARY: &str = "dognutframe";
+
+/// the jpeg a video packet carries, bare or wrapped in a `VideoPacket`. h264 and raw frames have none.
+pub fn jpeg_of(packet: &NetPacket) -> Option<Vec<u8>> {
+    if packet.kind.enum_value() != Ok(PacketKind::VideoPacket) {
+        return None;
+    }
+    let is_jpeg = |data: &[u8]| data.starts_with(&[0xff, 0xd8]);
+    if is_jpeg(&packet.data) {
+        return Some(packet.data.clone());
+    }
+    VideoPacket::parse_from_bytes(&packet.data).ok().map(|video| video.data).filter(|data| is_jpeg(data))
+}
+
+/// start a `multipart/x-mixed-replace` response, every part after it replaces the last one.
+pub async fn write_stream_head<W: AsyncWrite + Unpin>(out: &mut W) -> io::Result<()> {
+    let head = format!("HTTP/1.1 200 OK\r\nContent-Type: multipart/x-mixed-replace; boundary={}\r\n\
+                        Cache-Control: no-cache\r\nConnection: close\r\n\r\n", BOUNDARY);
+    out.write_all(head.as_bytes()).await?;
+    out.flush().await
+}
+
+/// write one jpeg of the stream.
+pub async fn write_part<W: AsyncWrite + Unpin>(out: &mut W, jpeg: &[u8]) -> io::Result<()> {
+    let head = format!("--{}\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n", BOUNDARY, jpeg.len());
+    out.write_all(head.as_bytes()).await?;
+    out.write_all(jpeg).await?;
+    out.write_all(b"\r\n").await?;
+    out.flush().await
+}
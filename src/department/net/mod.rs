@@ -1,2 +1,16 @@
+pub mod auth;
+pub mod client;
+pub mod config;
+pub mod discovery;
+pub mod frame_queue;
+pub mod http;
+pub mod mjpeg;
+pub mod pacer;
+pub mod remote_input;
 pub mod router;
+pub mod session;
+pub mod tls;
+pub mod transport;
+pub mod web;
+pub mod websocket;
 pub mod debug_service;
@@ -0,0 +1,25 @@
+use std::time::{Duration, Instant};
+
+/// spaces out the writes to one client so it gets at most `rate` bytes per second. while the
+/// writer waits its frame queue fills up and drops the oldest frames, like for a slow client.
+pub struct Pacer {
+    rate: u64,
+    next: Option<Instant>,
+}
+
+impl Pacer {
+    /// a pacer for `rate` bytes per second, 0 never waits.
+    pub fn new(rate: u64) -> Self {
+        Self { rate, next: None }
+    }
+
+    /// how long to wait at `now` before sending `bytes` more.
+    pub fn delay(&mut self, bytes: usize, now: Instant) -> Duration {
+        if self.rate == 0 {
+            return Duration::ZERO;
+        }
+        let start = self.next.map_or(now, |next| next.max(now));
+        self.next = Some(start + Duration::from_secs_f64(bytes as f64 / self.rate as f64));
+        start - now
+    }
+}
@@ -0,0 +1,174 @@
+use clap::ValueEnum;
+use crossbeam_channel::{Receiver, Sender};
+use lazy_static::lazy_static;
+use protobuf::Message;
+
+use crate::department::control::camera_controller::{CameraController, SCROLL_LINE};
+use crate::department::control::input_log::InputEvent;
+use crate::department::control::keymap::Action;
+use crate::department::control::terminal_mouse::MouseSink;
+use crate::department::types::msg::TransferMsg;
+use crate::pb::netpacket::{KeyEvent, MouseEvent, NetPacket, PacketKind, ScrollEvent};
+
+// events waiting for the renderer, more than a few frames worth means it fell behind.
+const QUEUE_SIZE: usize = 256;
+
+lazy_static! {
+    // input of remote viewers on its way from the router to the renderer.
+    static ref REMOTE_INPUT: (Sender<TransferMsg>, Receiver<TransferMsg>) = crossbeam_channel::bounded(QUEUE_SIZE);
+}
+
+/// where the router sends `TransferMsg::RemoteInput`.
+pub fn sender() -> Sender<TransferMsg> {
+    REMOTE_INPUT.0.clone()
+}
+
+/// where the renderer takes remote input from.
+pub fn receiver() -> Receiver<TransferMsg> {
+    REMOTE_INPUT.1.clone()
+}
+
+/// which of the connected viewers may drive the camera and model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ControlPolicy {
+    /// viewers only watch.
+    ViewOnly,
+    /// the viewer connected longest controls, the others watch.
+    Single,
+    /// every viewer controls.
+    Shared,
+}
+
+/// keeps track of the connected viewers to decide whose input counts.
+#[derive(Debug)]
+pub struct InputGate {
+    policy: ControlPolicy,
+    // in the order they connected.
+    clients: Vec<u64>,
+}
+
+impl InputGate {
+    pub fn new(policy: ControlPolicy) -> Self {
+        Self { policy, clients: Vec::new() }
+    }
+
+    pub fn join(&mut self, client: u64) {
+        self.clients.push(client);
+    }
+
+    pub fn leave(&mut self, client: u64) {
+        self.clients.retain(|c| *c != client);
+    }
+
+    pub fn may_control(&self, client: u64) -> bool {
+        match self.policy {
+            ControlPolicy::ViewOnly => false,
+            ControlPolicy::Single => self.clients.first() == Some(&client),
+            ControlPolicy::Shared => self.clients.contains(&client),
+        }
+    }
+}
+
+fn packet(kind: PacketKind, event: &impl Message) -> NetPacket {
+    let mut packet = NetPacket::new();
+    packet.kind = protobuf::EnumOrUnknown::from(kind);
+    packet.data = event.write_to_bytes().unwrap();
+    packet
+}
+
+pub fn key_packet(action: Action, pressed: bool, model: bool) -> NetPacket {
+    let mut event = KeyEvent::new();
+    event.action = action.name().to_string();
+    event.pressed = pressed;
+    event.model = model;
+    packet(PacketKind::KeyInput, &event)
+}
+
+pub fn mouse_packet(dx: f32, dy: f32, rotate: bool) -> NetPacket {
+    let mut event = MouseEvent::new();
+    event.dx = dx;
+    event.dy = dy;
+    event.rotate = rotate;
+    packet(PacketKind::MouseInput, &event)
+}
+
+pub fn scroll_packet(lines: f32) -> NetPacket {
+    let mut event = ScrollEvent::new();
+    event.lines = lines;
+    packet(PacketKind::ScrollInput, &event)
+}
+
+/// the controller input in a packet of a viewer. None for keys that only mean something to the
+/// viewer itself, like quitting.
+pub fn to_input_event(packet: &NetPacket) -> Result<Option<InputEvent>, String> {
+    let bad = |e: protobuf::Error| format!("bad {:?} packet: {}", packet.kind, e);
+    match packet.kind.enum_value() {
+        Ok(PacketKind::KeyInput) => {
+            let key = KeyEvent::parse_from_bytes(&packet.data).map_err(bad)?;
+            let action = Action::from_name(&key.action).ok_or_else(|| format!("unknown action `{}`", key.action))?;
+            Ok(match action {
+                Action::Quit | Action::ToggleHud | Action::ToggleBrowser | Action::RequestKeyframe => None,
+                _ => Some(InputEvent::Action { action, pressed: key.pressed, model: key.model }),
+            })
+        }
+        Ok(PacketKind::MouseInput) => {
+            let mouse = MouseEvent::parse_from_bytes(&packet.data).map_err(bad)?;
+            Ok(Some(if mouse.rotate {
+                InputEvent::Rotate { dx: mouse.dx, dy: mouse.dy }
+            } else {
+                InputEvent::Look { dx: mouse.dx, dy: mouse.dy }
+            }))
+        }
+        Ok(PacketKind::ScrollInput) => {
+            let scroll = ScrollEvent::parse_from_bytes(&packet.data).map_err(bad)?;
+            Ok(Some(InputEvent::Scroll(-scroll.lines * SCROLL_LINE)))
+        }
+        other => Err(format!("viewers don't send {:?} packets", other)),
+    }
+}
+
+/// applies the input of remote viewers to a controller, once before every update.
+pub struct RemoteControl {
+    input: Receiver<TransferMsg>,
+    // viewers are terminals which only send presses, so a press holds for one update.
+    releases: Vec<InputEvent>,
+}
+
+impl RemoteControl {
+    pub fn new(input: Receiver<TransferMsg>) -> Self {
+        Self { input, releases: Vec::new() }
+    }
+
+    pub fn update(&mut self, controller: &mut CameraController) {
+        for release in self.releases.drain(..) {
+            controller.process_remote(release);
+        }
+        while let Ok(msg) = self.input.try_recv() {
+            if let TransferMsg::RemoteInput(event) = msg {
+                if let InputEvent::Action { action, pressed: true, model } = event {
+                    self.releases.push(InputEvent::Action { action, pressed: false, model });
+                }
+                controller.process_remote(event);
+            }
+        }
+    }
+}
+
+/// sends the mouse input of a viewer to the server.
+pub struct RemoteMouse<'a> {
+    pub out: &'a tokio::sync::mpsc::UnboundedSender<NetPacket>,
+}
+
+impl MouseSink for RemoteMouse<'_> {
+    fn look(&mut self, dx: f32, dy: f32) {
+        let _ = self.out.send(mouse_packet(dx, dy, false));
+    }
+
+    fn rotate(&mut self, dx: f32, dy: f32) {
+        let _ = self.out.send(mouse_packet(dx, dy, true));
+    }
+
+    fn zoom(&mut self, lines: f32) {
+        let _ = self.out.send(scroll_packet(lines));
+    }
+}
@@ -1,43 +1,94 @@
 use std::convert::Infallible;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 //use crate::proto::debugger;
 use crossbeam_channel::{Receiver};
 use lazy_static::lazy_static;
-use log::{debug, error};
+use log::{debug, error, info, warn};
 use protobuf::Message;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, ReadHalf, WriteHalf};
 use tokio::net::{
-    tcp::OwnedWriteHalf,
     TcpListener,
+    TcpStream,
     UdpSocket
 };
-use tokio::sync::Mutex;
 use tokio::time::sleep;
 
 use crate::department::common::constant;
+use crate::department::net::auth::Access;
+use crate::department::net::config::{NetConfig, NET_CONFIG};
+use crate::department::net::discovery;
+use crate::department::net::frame_queue::FrameQueue;
+use crate::department::net::http;
+use crate::department::net::mjpeg;
+use crate::department::net::pacer::Pacer;
+use crate::department::net::remote_input::{self, InputGate};
+use crate::department::net::session;
+use crate::department::net::tls::Acceptor;
+use crate::department::net::transport::{PacketReader, PacketWriter, Rewound, Stream};
+use crate::department::net::{web, websocket};
+use crate::department::types::control;
+use crate::department::control::keymap::Keymap;
 use crate::department::types::msg;
 use crate::department::types::multi_sender::MultiSender;
 use crate::department::types::msg::{TransferMsg, DognutOption};
-use crate::pb::netpacket::{NetPacket, PacketKind};
+use crate::pb::avpacket::{Codec, VideoHeader};
+use crate::pb::netpacket::{Announcement, NetPacket, PacketKind};
+use crate::util::ARG;
+
+// packets waiting for one client, a few frames at 30 fps.
+const CLIENT_QUEUE_SIZE: usize = 8;
+
+// a connected client, its own task writes what is queued for it.
+struct Client {
+    id: u64,
+    addr: String,
+    queue: ClientQueue,
+}
 
 lazy_static! {
-    static ref CLIENT_SENDERS: Arc<Mutex<Vec<OwnedWriteHalf>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref CLIENTS: std::sync::Mutex<Vec<Client>> = std::sync::Mutex::new(Vec::new());
+    // which clients may send input, by the id given when they connect.
+    static ref INPUT_GATE: std::sync::Mutex<InputGate> = std::sync::Mutex::new(InputGate::new(ARG.remote_control));
+    // what new clients are told about the stream, the encoder reports the frame size.
+    static ref STREAM_HEADER: std::sync::Mutex<VideoHeader> = std::sync::Mutex::new(
+        session::video_header(session::server_codec(), (constant::WIDTH, constant::HEIGHT)));
+    // the last broadcast video packet, snapshots are served from it.
+    static ref LATEST_FRAME: std::sync::Mutex<Option<Arc<NetPacket>>> = std::sync::Mutex::new(None);
+}
+
+static NEXT_CLIENT_ID: AtomicU64 = AtomicU64::new(0);
+
+// the ports actually bound, announced on the lan.
+static BIND_PORT: AtomicU32 = AtomicU32::new(0);
+static DISCOVERY_PORT: AtomicU32 = AtomicU32::new(0);
+
+static CLIENT_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// number of connected stream clients.
+pub fn client_count() -> usize {
+    CLIENT_COUNT.load(Ordering::Relaxed)
 }
 
-static mut BIND_PORT: u32 = 0;
+/// whether anybody watches the stream, frames for the encoder are only worth making then.
+pub fn streaming() -> bool {
+    client_count() > 0
+}
 
 pub struct Router {
     client_clicked: bool,
     receiver: Option<Receiver<TransferMsg>>, // for encoder use
     ms: Option<MultiSender<TransferMsg>>,
+    // served to browsers, so the page maps keys the way the server does.
+    keymap: Arc<Keymap>,
 }
 
 impl Router {
-    pub fn new(receiver: Receiver<TransferMsg>, ms: MultiSender<TransferMsg>) -> Self {
-        Self { client_clicked: false, receiver: Some(receiver), ms: Some(ms)}
+    pub fn new(receiver: Receiver<TransferMsg>, ms: MultiSender<TransferMsg>, keymap: Keymap) -> Self {
+        Self { client_clicked: false, receiver: Some(receiver), ms: Some(ms), keymap: Arc::new(keymap) }
     }
 
     pub fn run(mut self) {
@@ -45,17 +96,39 @@ impl Router {
         std::thread::Builder::new().name("dognut_net_router".into()).spawn(move || {
             let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
             rt.block_on(async {
-                for i in 0..(constant::PORT_RANGE) {
-                    let host_str = format!("{}:{}", constant::HOST, constant::PORT + i);
-                    if let Ok(mut lis) = TcpListener::bind(&host_str).await {
-                        println!("Server listen on {}", host_str);
-                        unsafe {
-                            BIND_PORT = constant::PORT + i;
+                let config = &*NET_CONFIG;
+                let tls = match (&config.tls_cert, &config.tls_key) {
+                    (Some(cert), Some(key)) => match Acceptor::load(cert, key) {
+                        Ok(acceptor) => Some(acceptor),
+                        Err(e) => {
+                            error!("can't serve tls, nothing will be streamed: {}", e);
+                            return;
                         }
-                        self.ws_accept(&mut lis).await;
-                        break;
+                    },
+                    _ => None,
+                };
+                let mut lis = match bind_stream(config).await {
+                    Some(lis) => lis,
+                    None => {
+                        error!("no free tcp port on {} from {}, nothing will be streamed", config.bind, config.port);
+                        return;
                     }
+                };
+                let udp = bind_discovery(config).await;
+                let stream_port = lis.local_addr().map(|a| a.port() as u32).unwrap_or_default();
+                let discovery_port = udp.as_ref().and_then(|s| s.local_addr().ok()).map(|a| a.port() as u32);
+                BIND_PORT.store(stream_port, Ordering::Relaxed);
+                DISCOVERY_PORT.store(discovery_port.unwrap_or_default(), Ordering::Relaxed);
+                match discovery_port {
+                    Some(port) => info!("stream on tcp {}:{}, discovery on udp {}:{}", config.bind, stream_port, config.bind, port),
+                    None => info!("stream on tcp {}:{}, no free udp port for discovery", config.bind, stream_port),
                 }
+                let scheme = if tls.is_some() { "https" } else { "http" };
+                info!("browsers can watch on {}://<this host>:{}/, mjpeg players on {}", scheme, stream_port, web::MJPEG_ROUTE);
+                if config.tokens.required() {
+                    info!("viewers need a token, browsers pass it as ?token=");
+                }
+                self.ws_accept(&mut lis, udp, tls).await;
             });
         }).unwrap();
     }
@@ -67,16 +140,16 @@ impl Router {
         }
     }
 
-    pub async fn ws_accept(&mut self, l: &mut TcpListener) -> Result<(), Infallible>{
+    pub async fn ws_accept(&mut self, l: &mut TcpListener, udp: Option<UdpSocket>, tls: Option<Acceptor>) -> Result<(), Infallible>{
         tokio::spawn(listen_from_render(self.receiver.take().unwrap()));
-        tokio::spawn(listen_from_udp());
+        if let Some(udp) = udp {
+            tokio::spawn(listen_from_udp(udp));
+        }
 
         loop {
             match l.accept().await {
                 Ok((stream, addr)) => {
-                    //tokio::spawn(trans_websocket(stream, addr.to_string()));
-                    let (_client_recv, client_sender) = stream.into_split();
-                    CLIENT_SENDERS.lock().await.push(client_sender);
+                    tokio::spawn(admit(stream, addr.to_string(), tls.clone(), self.keymap.clone()));
                     if !self.client_clicked {
                         self.start_encoding_and_rendering();
                         self.client_clicked = true;
@@ -91,33 +164,292 @@ impl Router {
     }
 }
 
+// a client connection with the byte that told what it speaks put back.
+type Conn = Rewound<Box<dyn Stream>>;
+
+// a client queue, the encoder output reaches it while the client is registered.
+type ClientQueue = Arc<FrameQueue<Arc<NetPacket>>>;
+
+// how an admitted connection gets its packets. an mjpeg viewer is registered as soon as its
+// request is answered.
+enum Transport {
+    Framed,
+    WebSocket,
+    Mjpeg(u64, ClientQueue),
+}
+
+// whether an http request carries a token that lets it watch, as a bearer header or in the query.
+fn may_watch(request: &http::Request) -> bool {
+    let token = request.bearer().map(str::to_string).or_else(|| request.query("token")).unwrap_or_default();
+    NET_CONFIG.tokens.access(&token).is_some()
+}
+
+// the stream port also speaks http, browsers get the viewer page and open a websocket from it, other
+// tools can take the jpegs. returns None when the request was answered.
+async fn serve_http(stream: &mut Conn, addr: &str, keymap: &Keymap) -> Option<Transport> {
+    let request = match tokio::time::timeout(session::HELLO_TIMEOUT, http::read_request(stream)).await {
+        Ok(Ok(request)) => request,
+        Ok(Err(e)) => {
+            debug!("bad http request from {}: {}", addr, e);
+            return None;
+        }
+        Err(_) => {
+            debug!("http request from {} timed out", addr);
+            return None;
+        }
+    };
+    // the query may hold the token, only the route is logged.
+    debug!("{} {} from {}", request.method, request.route(), addr);
+    let jpeg_stream = STREAM_HEADER.lock().unwrap().codec.enum_value() == Ok(Codec::Jpeg);
+    let result = match request.route() {
+        web::WEBSOCKET_ROUTE if websocket::is_upgrade(&request) => {
+            websocket::upgrade(stream, &request).await.map(|_| Some(Transport::WebSocket))
+        }
+        web::MJPEG_ROUTE | web::SNAPSHOT_ROUTE if request.method == "GET" && !may_watch(&request) => {
+            http::write_response(stream, "403 Forbidden", "text/plain", b"a view token is needed, as ?token= or a bearer header\n").await.map(|_| None)
+        }
+        web::MJPEG_ROUTE | web::SNAPSHOT_ROUTE if request.method == "GET" && !jpeg_stream => {
+            http::write_response(stream, "503 Service Unavailable", "text/plain", b"the stream is not jpeg encoded\n").await.map(|_| None)
+        }
+        web::MJPEG_ROUTE if request.method == "GET" => mjpeg_viewer(stream, addr).await,
+        web::SNAPSHOT_ROUTE if request.method == "GET" => snapshot(stream).await.map(|_| None),
+        _ => web::serve(stream, &request, keymap).await.map(|_| None),
+    };
+    match result {
+        Ok(transport) => transport,
+        Err(e) => {
+            debug!("http to {} failed: {}", addr, e);
+            None
+        }
+    }
+}
+
+// answer with the last rendered frame. a snapshot doesn't watch the stream, so it isn't counted
+// as a client and doesn't make the encoder send a keyframe.
+async fn snapshot(stream: &mut Conn) -> std::io::Result<()> {
+    let latest = LATEST_FRAME.lock().unwrap().clone();
+    match latest.as_deref().and_then(mjpeg::jpeg_of) {
+        Some(jpeg) => http::write_response(stream, "200 OK", "image/jpeg", &jpeg).await,
+        None => http::write_response(stream, "503 Service Unavailable", "text/plain", b"no frame was rendered yet\n").await,
+    }
+}
+
+// take a place for an mjpeg viewer and start its stream.
+async fn mjpeg_viewer(stream: &mut Conn, addr: &str) -> std::io::Result<Option<Transport>> {
+    let (id, queue) = match reserve_client(addr, CLIENT_QUEUE_SIZE) {
+        Some(reserved) => reserved,
+        None => {
+            warn!("turn away mjpeg viewer {}: the server is full", addr);
+            return http::write_response(stream, "503 Service Unavailable", "text/plain", b"the server is full\n").await.map(|_| None);
+        }
+    };
+    if let Err(e) = mjpeg::write_stream_head(stream).await {
+        remove_client(id);
+        return Err(e);
+    }
+    Ok(Some(Transport::Mjpeg(id, queue)))
+}
+
+// register a client so the encoder output reaches its queue, None when --max-clients are already
+// watching. the check and the registration happen under one lock, so clients connecting at once
+// can't both take the last place. it can't send input until it joins the gate.
+fn reserve_client(addr: &str, queue_size: usize) -> Option<(u64, ClientQueue)> {
+    let mut clients = CLIENTS.lock().unwrap();
+    if NET_CONFIG.is_full(clients.len()) {
+        return None;
+    }
+    let id = NEXT_CLIENT_ID.fetch_add(1, Ordering::Relaxed);
+    let queue = Arc::new(FrameQueue::new(queue_size));
+    clients.push(Client { id, addr: addr.to_string(), queue: queue.clone() });
+    CLIENT_COUNT.fetch_add(1, Ordering::Relaxed);
+    Some((id, queue))
+}
+
+// shake hands with a new client, then stream to it and listen to its input.
+async fn admit(stream: TcpStream, addr: String, tls: Option<Acceptor>, keymap: Arc<Keymap>) {
+    let mut stream: Box<dyn Stream> = match &tls {
+        Some(tls) => match tokio::time::timeout(session::HELLO_TIMEOUT, tls.accept(stream)).await {
+            Ok(Ok(stream)) => stream,
+            Ok(Err(e)) => {
+                debug!("tls handshake with {} failed: {}", addr, e);
+                return;
+            }
+            Err(_) => {
+                debug!("tls handshake with {} timed out", addr);
+                return;
+            }
+        },
+        None => Box::new(stream),
+    };
+    // a client that never speaks gives its connection back after the hello timeout.
+    let mut first = [0; 1];
+    match tokio::time::timeout(session::HELLO_TIMEOUT, stream.read_exact(&mut first)).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(_)) => return,
+        Err(_) => {
+            debug!("{} sent nothing", addr);
+            return;
+        }
+    }
+    let mut stream = Rewound::new(first.to_vec(), stream);
+    let transport = if http::looks_like_http(first[0]) {
+        match serve_http(&mut stream, &addr, &keymap).await {
+            Some(transport) => transport,
+            None => return,
+        }
+    } else {
+        Transport::Framed
+    };
+    let browser = matches!(transport, Transport::WebSocket);
+    let (read, write) = tokio::io::split(stream);
+    let (mut client_recv, mut client_sender) = match transport {
+        Transport::Framed => (PacketReader::Framed(read), PacketWriter::Framed(write)),
+        Transport::WebSocket => {
+            let write = Arc::new(tokio::sync::Mutex::new(write));
+            (PacketReader::WebSocket(read, write.clone()), PacketWriter::WebSocket(write))
+        }
+        Transport::Mjpeg(id, queue) => {
+            // mjpeg viewers send nothing, they are gone when a write fails. every jpeg is a keyframe.
+            info!("mjpeg viewer {} joined", addr);
+            stream_to_client(PacketWriter::Mjpeg(write), queue, id, addr).await;
+            return;
+        }
+    };
+
+    // the hello is checked first, so a client without the token can't hold a place. the place is
+    // then taken under the clients lock and only a client that got it is welcomed.
+    let header = STREAM_HEADER.lock().unwrap().clone();
+    let (hello, access) = match session::read_hello(&mut client_recv, &mut client_sender, &header, &NET_CONFIG.tokens).await {
+        Ok(accepted) => accepted,
+        Err(e) => {
+            warn!("turn away client {}: {}", addr, e);
+            return;
+        }
+    };
+    let (id, queue) = match reserve_client(&addr, CLIENT_QUEUE_SIZE) {
+        Some(reserved) => reserved,
+        None => {
+            warn!("turn away client {}: the server is full", addr);
+            let _ = client_sender.write(&session::error_packet("the server is full")).await;
+            return;
+        }
+    };
+    if let Err(e) = client_sender.write(&session::welcome_packet(&header, access)).await {
+        warn!("lost client {} during the handshake: {}", addr, e);
+        remove_client(id);
+        return;
+    }
+    if browser {
+        info!("browser {} joined, {:?} access", addr, access);
+    } else {
+        info!("client {} joined with a {}x{} terminal, {:?} access", addr, hello.columns, hello.rows, access);
+    }
+
+    // an h264 stream can only be decoded from a keyframe on.
+    control::request_keyframe();
+    if access == Access::Control {
+        INPUT_GATE.lock().unwrap().join(id);
+    }
+    tokio::spawn(stream_to_client(client_sender, queue, id, addr.clone()));
+    listen_from_client(client_recv, id, addr).await;
+    remove_client(id);
+}
+
+// forget a client, both of its tasks end up here and the later one finds nothing to do.
+fn remove_client(id: u64) {
+    let mut clients = CLIENTS.lock().unwrap();
+    let index = match clients.iter().position(|c| c.id == id) {
+        Some(index) => index,
+        None => return,
+    };
+    let client = clients.remove(index);
+    client.queue.close();
+    INPUT_GATE.lock().unwrap().leave(id);
+    CLIENT_COUNT.fetch_sub(1, Ordering::Relaxed);
+    info!("client {} left, {} still watching, dropped {} frames", client.addr, clients.len(), client.queue.dropped());
+}
+
+// write the queued packets of a client within its bandwidth, a dead client is removed at the first failed write.
+async fn stream_to_client(mut out: PacketWriter<WriteHalf<Conn>>, queue: ClientQueue, id: u64, addr: String) {
+    let mut pacer = Pacer::new(NET_CONFIG.client_bandwidth);
+    while let Some(packet) = queue.pop().await {
+        let wait = pacer.delay(packet.compute_size() as usize, Instant::now());
+        if !wait.is_zero() {
+            sleep(wait).await;
+        }
+        if let Err(e) = out.write(&packet).await {
+            info!("client {} stopped receiving: {}", addr, e);
+            break;
+        }
+    }
+    remove_client(id);
+}
+
+// pass the input a client sends to the renderer, when the control policy lets it.
+async fn listen_from_client(mut recv: PacketReader<ReadHalf<Conn>, WriteHalf<Conn>>, id: u64, addr: String) {
+    let input = remote_input::sender();
+    loop {
+        let packet = match recv.read().await {
+            Ok(packet) => packet,
+            Err(e) => {
+                info!("client {} stopped sending: {}", addr, e);
+                break;
+            }
+        };
+        if packet.kind.enum_value() == Ok(PacketKind::KeyframeRequest) {
+            match session::keyframe_reason(&packet) {
+                Ok(reason) => info!("client {} asks for a keyframe: {}", addr, reason),
+                Err(e) => warn!("{} from {}", e, addr),
+            }
+            control::request_keyframe();
+            continue;
+        }
+        let event = match remote_input::to_input_event(&packet) {
+            Ok(Some(event)) => event,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!("bad input from {}: {}", addr, e);
+                continue;
+            }
+        };
+        if !INPUT_GATE.lock().unwrap().may_control(id) {
+            continue;
+        }
+        if input.try_send(TransferMsg::RemoteInput(event)).is_err() {
+            debug!("remote input queue is full, drop input from {}", addr);
+        }
+    }
+}
+
+// queue a video packet for every connected client.
+fn broadcast(data: Vec<u8>) {
+    let mut net_pkt = NetPacket::new();
+    net_pkt.data = data;
+    net_pkt.kind = protobuf::EnumOrUnknown::from(PacketKind::VideoPacket);
+    let net_pkt = Arc::new(net_pkt);
+    *LATEST_FRAME.lock().unwrap() = Some(net_pkt.clone());
+
+    for client in CLIENTS.lock().unwrap().iter() {
+        if !client.queue.push(net_pkt.clone()) {
+            debug!("client {} is behind, drop its oldest frame", client.addr);
+        }
+    }
+}
+
 async fn listen_from_render(render_recv: Receiver<msg::TransferMsg>) {
     loop {
         if let Ok(msg) = render_recv.try_recv() {
             match msg {
                 msg::TransferMsg::CompressedData(pic_frame) => {
-                    let mut net_pkt = NetPacket::new();
-                    net_pkt.data = pic_frame;
-                    net_pkt.kind = protobuf::EnumOrUnknown::from(PacketKind::VideoPacket);
-                    let serialized = net_pkt.write_to_bytes().unwrap();
-
-                    for sender in CLIENT_SENDERS.lock().await.iter_mut() {
-
-                        sender.write_u32( serialized.len() as u32).await.unwrap();
-                        sender.write(serialized.as_slice()).await.unwrap();
-                    }
+                    broadcast(pic_frame);
                 }
                 TransferMsg::RenderedData(frame) => {
-                    let mut net_pkt = NetPacket::new();
-                    net_pkt.data = frame;
-                    net_pkt.kind = protobuf::EnumOrUnknown::from(PacketKind::VideoPacket);
-                    let serialized = net_pkt.write_to_bytes().unwrap();
-
-                    for sender in CLIENT_SENDERS.lock().await.iter_mut() {
-
-                        sender.write_u32( serialized.len() as u32).await.unwrap();
-                        sender.write(serialized.as_slice()).await.unwrap();
-                    }
+                    broadcast(frame);
+                },
+                TransferMsg::Resize(w, h) => {
+                    let mut header = STREAM_HEADER.lock().unwrap();
+                    header.width = w;
+                    header.height = h;
                 },
                 _ => ()
             }
@@ -129,23 +461,73 @@ async fn listen_from_render(render_recv: Receiver<msg::TransferMsg>) {
 
 
 
-async fn listen_from_udp() {
+// what this server tells the lan about itself.
+fn announcement() -> Announcement {
+    let header = STREAM_HEADER.lock().unwrap().clone();
+    let model = ARG.scene.as_ref().unwrap_or(&ARG.obj_path);
+    let mut announcement = Announcement::new();
+    announcement.version = session::PROTOCOL_VERSION;
+    announcement.name = ARG.name.clone().unwrap_or_else(discovery::host_name);
+    announcement.port = BIND_PORT.load(Ordering::Relaxed);
+    announcement.model = std::path::Path::new(model).file_name()
+        .map_or_else(|| model.clone(), |name| name.to_string_lossy().into_owned());
+    announcement.width = header.width;
+    announcement.height = header.height;
+    announcement.fps = header.fps;
+    announcement.codecs = vec![header.codec];
+    announcement.clients = client_count() as u32;
+    announcement.discovery_port = DISCOVERY_PORT.load(Ordering::Relaxed);
+    announcement.token_required = NET_CONFIG.tokens.required();
+    announcement.tls = NET_CONFIG.tls_cert.is_some();
+    announcement
+}
+
+// the first free tcp port of the configured range.
+async fn bind_stream(config: &NetConfig) -> Option<TcpListener> {
+    for port in config.ports() {
+        match TcpListener::bind(format!("{}:{}", config.bind, port)).await {
+            Ok(lis) => return Some(lis),
+            Err(e) => debug!("can't listen on {}:{}: {}", config.bind, port, e),
+        }
+    }
+    None
+}
+
+// the first free udp port of the configured range.
+async fn bind_discovery(config: &NetConfig) -> Option<UdpSocket> {
+    for port in config.udp_ports() {
+        match UdpSocket::bind(format!("{}:{}", config.bind, port)).await {
+            Ok(sock) => return Some(sock),
+            Err(e) => debug!("can't answer discovery on {}:{}: {}", config.bind, port, e),
+        }
+    }
+    None
+}
+
+// answer discovery queries, and announce the server to the lan every few seconds.
+async fn listen_from_udp(sock: UdpSocket) {
+    if let Err(e) = sock.set_broadcast(true) {
+        warn!("can't broadcast announcements: {}", e);
+    }
+
     let mut buf = [0; 1024];
-    for i in 0..(constant::PORT_RANGE) {
-        let host_str = format!("{}:{}", constant::HOST, constant::UDP_PORT + i);
-        if let Ok(sock) = UdpSocket::bind(host_str.clone()).await {
-            println!("listen from udp at:{:?}", host_str);
-            loop {
-                if let Ok((len, addr)) = sock.recv_from(&mut buf).await {
-                    println!("recv buf len:{:?}, addr:{:?}", len, addr);
-                    let mut data = json::JsonValue::new_object();
-                    unsafe {
-                        data["port"] = BIND_PORT.into();
+    let mut announce = tokio::time::interval(discovery::ANNOUNCE_INTERVAL);
+    loop {
+        tokio::select! {
+            received = sock.recv_from(&mut buf) => match received {
+                Ok((len, addr)) if discovery::is_query(&buf[..len]) => {
+                    debug!("discovery query from {}", addr);
+                    if let Err(e) = sock.send_to(&discovery::announcement_datagram(&announcement()), addr).await {
+                        debug!("can't answer {}: {}", addr, e);
                     }
-                    sock.send_to(&data.dump().as_bytes(), addr).await;
                 }
-                else {
-                    println!("recv buf failed:");
+                Ok((_, addr)) => debug!("ignore datagram from {}", addr),
+                Err(e) => warn!("discovery receive failed: {}", e),
+            },
+            _ = announce.tick() => {
+                let target = ("255.255.255.255", NET_CONFIG.announce_port() as u16);
+                if let Err(e) = sock.send_to(&discovery::announcement_datagram(&announcement()), target).await {
+                    debug!("announcement failed: {}", e);
                 }
             }
         }
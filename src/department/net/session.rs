@@ -0,0 +1,176 @@
+use std::io;
+use std::time::Duration;
+
+use protobuf::Message;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::department::common::constant;
+use crate::department::net::auth::{Access, Tokens};
+use crate::department::net::client::{self, read_packet, write_packet};
+use crate::department::net::transport::{PacketReader, PacketWriter};
+use crate::pb::avpacket::{Codec, PixelFormat, VideoHeader};
+use crate::pb::netpacket::{Hello, KeyframeRequest, NetPacket, PacketKind, SessionError, Welcome};
+
+/// bumped whenever client and server stop understanding each other.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// how long the server waits for the hello of a new client.
+pub const HELLO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// the biggest hello the server reads, a client that isn't welcomed yet can't make it buffer a
+/// whole video packet.
+pub const MAX_HELLO_SIZE: usize = 64 * 1024;
+
+/// the codec of the video packets this build sends, the h264 encoder wins over the image encoder.
+pub fn server_codec() -> Codec {
+    if cfg!(feature = "rtc") {
+        Codec::H264
+    } else if cfg!(feature = "image_encoder") {
+        Codec::Jpeg
+    } else {
+        Codec::Raw
+    }
+}
+
+/// the header of a stream of `codec` frames of `size` pixels.
+pub fn video_header(codec: Codec, size: (u32, u32)) -> VideoHeader {
+    let mut header = VideoHeader::new();
+    header.codec = protobuf::EnumOrUnknown::from(codec);
+    header.pixel_format = protobuf::EnumOrUnknown::from(match codec {
+        Codec::H264 => PixelFormat::Yuv420p,
+        Codec::Raw | Codec::Jpeg => PixelFormat::Rgba,
+    });
+    header.width = size.0;
+    header.height = size.1;
+    header.fps = constant::FPS;
+    header
+}
+
+pub(crate) fn packet(kind: PacketKind, message: &impl Message) -> NetPacket {
+    let mut packet = NetPacket::new();
+    packet.kind = protobuf::EnumOrUnknown::from(kind);
+    packet.data = message.write_to_bytes().unwrap();
+    packet
+}
+
+pub fn hello_packet(codecs: &[Codec], terminal: (u32, u32), token: &str) -> NetPacket {
+    let mut hello = Hello::new();
+    hello.version = PROTOCOL_VERSION;
+    hello.codecs = codecs.iter().map(|c| protobuf::EnumOrUnknown::from(*c)).collect();
+    hello.columns = terminal.0;
+    hello.rows = terminal.1;
+    hello.token = token.to_string();
+    packet(PacketKind::Hello, &hello)
+}
+
+pub fn welcome_packet(header: &VideoHeader, access: Access) -> NetPacket {
+    let mut welcome = Welcome::new();
+    welcome.version = PROTOCOL_VERSION;
+    welcome.header = protobuf::MessageField::some(header.clone());
+    welcome.control = access == Access::Control;
+    packet(PacketKind::Welcome, &welcome)
+}
+
+pub fn error_packet(reason: &str) -> NetPacket {
+    let mut error = SessionError::new();
+    error.reason = reason.to_string();
+    packet(PacketKind::Error, &error)
+}
+
+/// ask the server for a keyframe, after a decode error or whenever the client wants one.
+pub fn keyframe_request_packet(reason: &str) -> NetPacket {
+    let mut request = KeyframeRequest::new();
+    request.reason = reason.to_string();
+    packet(PacketKind::KeyframeRequest, &request)
+}
+
+/// why the client asks for a keyframe.
+pub fn keyframe_reason(packet: &NetPacket) -> Result<String, String> {
+    KeyframeRequest::parse_from_bytes(&packet.data).map(|r| r.reason).map_err(|e| format!("bad keyframe request: {}", e))
+}
+
+/// the hello in the first packet of a client and what its token lets it do, or why the client
+/// can't watch `header`.
+pub fn check_hello(packet: &NetPacket, header: &VideoHeader, tokens: &Tokens) -> Result<(Hello, Access), String> {
+    if packet.kind.enum_value() != Ok(PacketKind::Hello) {
+        return Err(format!("expected a hello, got {:?}", packet.kind));
+    }
+    let hello = Hello::parse_from_bytes(&packet.data).map_err(|e| format!("bad hello: {}", e))?;
+    if hello.version != PROTOCOL_VERSION {
+        return Err(format!("protocol version {} is not supported, the server speaks {}", hello.version, PROTOCOL_VERSION));
+    }
+    if !hello.codecs.contains(&header.codec) {
+        return Err(format!("the stream is {:?}, which the client can't decode", header.codec));
+    }
+    match tokens.access(&hello.token) {
+        Some(access) => Ok((hello, access)),
+        None if hello.token.is_empty() => Err("the server needs a token".to_string()),
+        None => Err("wrong token".to_string()),
+    }
+}
+
+/// the server side of the handshake on a tcp stream, see `accept_packets`.
+pub async fn accept<S: AsyncRead + AsyncWrite + Unpin>(stream: &mut S, header: &VideoHeader, tokens: &Tokens)
+                                                     -> io::Result<(Hello, Access)> {
+    let (read, write) = tokio::io::split(stream);
+    accept_packets(&mut PacketReader::Framed(read), &mut PacketWriter::Framed(write), header, tokens).await
+}
+
+/// the server side of the handshake, the hello of the accepted client and what it may do. the
+/// client gets an error packet when it is turned away.
+pub async fn accept_packets<R, W>(reader: &mut PacketReader<R, W>, writer: &mut PacketWriter<W>, header: &VideoHeader,
+                                  tokens: &Tokens) -> io::Result<(Hello, Access)>
+    where R: AsyncRead + Unpin, W: AsyncWrite + Unpin {
+    let (hello, access) = read_hello(reader, writer, header, tokens).await?;
+    writer.write(&welcome_packet(header, access)).await?;
+    Ok((hello, access))
+}
+
+/// the first half of `accept_packets`, the hello of a client and what it may do. nothing is sent
+/// when the hello is fine, so the server can still turn the client away before it welcomes it.
+pub async fn read_hello<R, W>(reader: &mut PacketReader<R, W>, writer: &mut PacketWriter<W>, header: &VideoHeader,
+                              tokens: &Tokens) -> io::Result<(Hello, Access)>
+    where R: AsyncRead + Unpin, W: AsyncWrite + Unpin {
+    let first = match tokio::time::timeout(HELLO_TIMEOUT, reader.read_limited(MAX_HELLO_SIZE)).await {
+        Ok(packet) => packet?,
+        Err(_) => {
+            writer.write(&error_packet("no hello")).await?;
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "no hello"));
+        }
+    };
+    match check_hello(&first, header, tokens) {
+        Ok(accepted) => Ok(accepted),
+        Err(reason) => {
+            writer.write(&error_packet(&reason)).await?;
+            Err(io::Error::new(io::ErrorKind::InvalidData, reason))
+        }
+    }
+}
+
+/// the client side of the handshake, the header of the stream the server is about to send and
+/// whether the server takes input from this client.
+pub async fn connect<S: AsyncRead + AsyncWrite + Unpin>(stream: &mut S, codecs: &[Codec], terminal: (u32, u32), token: &str)
+                                                      -> io::Result<(VideoHeader, Access)> {
+    write_packet(stream, &hello_packet(codecs, terminal, token)).await?;
+    let answer = read_packet(stream).await?;
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+    match answer.kind.enum_value() {
+        Ok(PacketKind::Welcome) => {
+            let welcome = Welcome::parse_from_bytes(&answer.data).map_err(|e| invalid(format!("bad welcome: {}", e)))?;
+            if welcome.version != PROTOCOL_VERSION {
+                return Err(invalid(format!("server speaks protocol version {}, expected {}", welcome.version, PROTOCOL_VERSION)));
+            }
+            let access = if welcome.control { Access::Control } else { Access::View };
+            let header = welcome.header.unwrap_or_default();
+            if client::rgba_len((header.width, header.height)).is_none() {
+                return Err(invalid(format!("a {}x{} stream is too big to decode", header.width, header.height)));
+            }
+            Ok((header, access))
+        }
+        Ok(PacketKind::Error) => {
+            let error = SessionError::parse_from_bytes(&answer.data).map_err(|e| invalid(format!("bad error packet: {}", e)))?;
+            Err(io::Error::new(io::ErrorKind::ConnectionRefused, error.reason))
+        }
+        other => Err(invalid(format!("expected a welcome, got {:?}", other))),
+    }
+}
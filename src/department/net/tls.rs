@@ -0,0 +1,92 @@
+use std::io;
+#[cfg(feature = "tls")]
+use std::sync::Arc;
+
+use tokio::net::TcpStream;
+#[cfg(feature = "tls")]
+use tokio_rustls::rustls::{self, Certificate, PrivateKey};
+
+use crate::department::net::transport::Stream;
+
+#[cfg(not(feature = "tls"))]
+const NO_TLS: &str = "dognut was built without the tls feature";
+
+/// terminates tls on the stream port, browsers and viewers then speak the usual protocol inside.
+#[derive(Clone)]
+pub struct Acceptor {
+    #[cfg(feature = "tls")]
+    inner: tokio_rustls::TlsAcceptor,
+}
+
+#[cfg(feature = "tls")]
+fn read_pem(path: &str) -> Result<Vec<rustls_pemfile::Item>, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+    rustls_pemfile::read_all(&mut io::BufReader::new(file)).map_err(|e| format!("{}: {}", path, e))
+}
+
+#[cfg(feature = "tls")]
+fn certificates(path: &str) -> Result<Vec<Certificate>, String> {
+    let certificates: Vec<Certificate> = read_pem(path)?.into_iter().filter_map(|item| match item {
+        rustls_pemfile::Item::X509Certificate(der) => Some(Certificate(der)),
+        _ => None,
+    }).collect();
+    if certificates.is_empty() {
+        return Err(format!("{}: no certificate in the file", path));
+    }
+    Ok(certificates)
+}
+
+#[cfg(feature = "tls")]
+fn private_key(path: &str) -> Result<PrivateKey, String> {
+    read_pem(path)?.into_iter().find_map(|item| match item {
+        rustls_pemfile::Item::RSAKey(der) | rustls_pemfile::Item::PKCS8Key(der) | rustls_pemfile::Item::ECKey(der) => Some(PrivateKey(der)),
+        _ => None,
+    }).ok_or_else(|| format!("{}: no private key in the file", path))
+}
+
+impl Acceptor {
+    /// an acceptor for the pem certificate chain in `cert` and the private key in `key`.
+    #[cfg(feature = "tls")]
+    pub fn load(cert: &str, key: &str) -> Result<Self, String> {
+        let config = rustls::ServerConfig::builder().with_safe_defaults().with_no_client_auth()
+            .with_single_cert(certificates(cert)?, private_key(key)?)
+            .map_err(|e| format!("{}: {}", cert, e))?;
+        Ok(Self { inner: tokio_rustls::TlsAcceptor::from(Arc::new(config)) })
+    }
+
+    #[cfg(not(feature = "tls"))]
+    pub fn load(_cert: &str, _key: &str) -> Result<Self, String> {
+        Err(NO_TLS.to_string())
+    }
+
+    /// the tls handshake of a new connection.
+    #[cfg(feature = "tls")]
+    pub async fn accept(&self, stream: TcpStream) -> io::Result<Box<dyn Stream>> {
+        Ok(Box::new(self.inner.accept(stream).await?))
+    }
+
+    #[cfg(not(feature = "tls"))]
+    pub async fn accept(&self, _stream: TcpStream) -> io::Result<Box<dyn Stream>> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, NO_TLS))
+    }
+}
+
+/// the client side of tls to `host`, trusting only the pem certificates in `ca`, like the self
+/// signed certificate of the server.
+#[cfg(feature = "tls")]
+pub async fn connect(stream: TcpStream, host: &str, ca: &str) -> io::Result<Box<dyn Stream>> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let mut roots = rustls::RootCertStore::empty();
+    for certificate in certificates(ca).map_err(invalid)? {
+        roots.add(&certificate).map_err(|e| invalid(format!("{}: {}", ca, e)))?;
+    }
+    let config = rustls::ClientConfig::builder().with_safe_defaults().with_root_certificates(roots).with_no_client_auth();
+    let name = rustls::ServerName::try_from(host).map_err(|e| invalid(format!("{}: {}", host, e)))?;
+    let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+    Ok(Box::new(connector.connect(name, stream).await?))
+}
+
+#[cfg(not(feature = "tls"))]
+pub async fn connect(_stream: TcpStream, _host: &str, _ca: &str) -> io::Result<Box<dyn Stream>> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, NO_TLS))
+}
@@ -0,0 +1,96 @@
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::Mutex;
+
+use crate::department::net::{client, mjpeg, websocket};
+use crate::pb::netpacket::NetPacket;
+
+/// the writing half of a websocket, the reader answers pings and closes on it too.
+pub type SharedWriter<W> = Arc<Mutex<W>>;
+
+/// where the packets of a client come from, length prefixed on a tcp stream or websocket messages.
+pub enum PacketReader<R, W> {
+    Framed(R),
+    WebSocket(R, SharedWriter<W>),
+}
+
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> PacketReader<R, W> {
+    pub async fn read(&mut self) -> io::Result<NetPacket> {
+        self.read_limited(client::MAX_PACKET_SIZE).await
+    }
+
+    /// the next packet, refusing one bigger than `max` bytes.
+    pub async fn read_limited(&mut self, max: usize) -> io::Result<NetPacket> {
+        match self {
+            PacketReader::Framed(input) => client::read_packet_limited(input, max).await,
+            PacketReader::WebSocket(input, out) => websocket::read_packet_limited(input, out, max).await,
+        }
+    }
+}
+
+/// where the packets for a client go, see `PacketReader`. mjpeg viewers only get the jpegs.
+pub enum PacketWriter<W> {
+    Framed(W),
+    WebSocket(SharedWriter<W>),
+    Mjpeg(W),
+}
+
+impl<W: AsyncWrite + Unpin> PacketWriter<W> {
+    pub async fn write(&mut self, packet: &NetPacket) -> io::Result<()> {
+        match self {
+            PacketWriter::Framed(out) => client::write_packet(out, packet).await,
+            PacketWriter::WebSocket(out) => websocket::write_packet(&mut *out.lock().await, packet).await,
+            PacketWriter::Mjpeg(out) => match mjpeg::jpeg_of(packet) {
+                Some(jpeg) => mjpeg::write_part(out, &jpeg).await,
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+/// a client connection, plain tcp or tls.
+pub trait Stream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Stream for T {}
+
+/// a stream with bytes that were read to tell what it speaks put back in front of it.
+pub struct Rewound<S> {
+    prefix: Vec<u8>,
+    inner: S,
+}
+
+impl<S> Rewound<S> {
+    pub fn new(prefix: Vec<u8>, inner: S) -> Self {
+        Self { prefix, inner }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for Rewound<S> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        if !self.prefix.is_empty() {
+            let n = self.prefix.len().min(buf.remaining());
+            buf.put_slice(&self.prefix[..n]);
+            self.prefix.drain(..n);
+            return Poll::Ready(Ok(()));
+        }
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for Rewound<S> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
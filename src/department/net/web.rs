@@ -0,0 +1,31 @@
+use std::io;
+
+use tokio::io::AsyncWrite;
+
+use crate::department::control::keymap::Keymap;
+use crate::department::net::http::{self, Request};
+
+/// where the browser viewer opens its websocket.
+pub const WEBSOCKET_ROUTE: &str = "/ws";
+
+/// the render as an mjpeg stream, for vlc, ffmpeg or an `<img>` tag.
+pub const MJPEG_ROUTE: &str = "/stream.mjpg";
+
+/// the last rendered frame as one jpeg.
+pub const SNAPSHOT_ROUTE: &str = "/snapshot.jpg";
+
+/// the browser viewer, it decodes jpeg and raw frames into a canvas and sends input back.
+pub const VIEWER_PAGE: &str = include_str!("../../../res/web/viewer.html");
+
+/// answer a plain http request on the stream port, the page gets the keys of `keymap`.
+pub async fn serve<W: AsyncWrite + Unpin>(out: &mut W, request: &Request, keymap: &Keymap) -> io::Result<()> {
+    if request.method != "GET" {
+        return http::write_response(out, "405 Method Not Allowed", "text/plain", b"only GET is served\n").await;
+    }
+    match request.route() {
+        "/" | "/index.html" => http::write_response(out, "200 OK", "text/html; charset=utf-8", VIEWER_PAGE.as_bytes()).await,
+        // the page maps keys to actions the way the server does.
+        "/keymap.json" => http::write_response(out, "200 OK", "application/json", keymap.to_json().as_bytes()).await,
+        _ => http::write_response(out, "404 Not Found", "text/plain", b"not found\n").await,
+    }
+}
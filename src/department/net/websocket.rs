@@ -0,0 +1,195 @@
+use std::io;
+
+use protobuf::Message;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+use crate::department::common::base64;
+use crate::department::net::client::MAX_PACKET_SIZE;
+use crate::department::net::http::{self, Request};
+use crate::pb::netpacket::NetPacket;
+
+// appended to the key of the client before hashing, from rfc 6455.
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+pub const OP_CONTINUATION: u8 = 0x0;
+pub const OP_TEXT: u8 = 0x1;
+pub const OP_BINARY: u8 = 0x2;
+pub const OP_CLOSE: u8 = 0x8;
+pub const OP_PING: u8 = 0x9;
+pub const OP_PONG: u8 = 0xa;
+
+/// the `Sec-WebSocket-Accept` answer to the `Sec-WebSocket-Key` of a client.
+pub fn accept_key(key: &str) -> String {
+    base64::encode(&sha1(format!("{}{}", key.trim(), ACCEPT_GUID).as_bytes()))
+}
+
+/// switch an http connection to a websocket, answering the upgrade `request`.
+pub async fn upgrade<W: AsyncWrite + Unpin>(out: &mut W, request: &Request) -> io::Result<()> {
+    let key = request.header("sec-websocket-key")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "websocket upgrade without a key"))?;
+    let head = format!("HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+                        Sec-WebSocket-Accept: {}\r\n\r\n", accept_key(key));
+    out.write_all(head.as_bytes()).await?;
+    out.flush().await
+}
+
+/// write one frame. clients mask what they send with `mask`, servers send unmasked frames.
+pub async fn write_frame<W: AsyncWrite + Unpin>(out: &mut W, opcode: u8, payload: &[u8], mask: Option<[u8; 4]>) -> io::Result<()> {
+    let mut head = vec![0x80 | opcode];
+    let mask_bit = if mask.is_some() { 0x80 } else { 0 };
+    match payload.len() {
+        len if len < 126 => head.push(mask_bit | len as u8),
+        len if len <= u16::MAX as usize => {
+            head.push(mask_bit | 126);
+            head.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            head.push(mask_bit | 127);
+            head.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    match mask {
+        Some(mask) => {
+            head.extend_from_slice(&mask);
+            out.write_all(&head).await?;
+            let masked: Vec<u8> = payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]).collect();
+            out.write_all(&masked).await?;
+        }
+        None => {
+            out.write_all(&head).await?;
+            out.write_all(payload).await?;
+        }
+    }
+    out.flush().await
+}
+
+// one frame as (fin, opcode, unmasked payload).
+async fn read_frame<R: AsyncRead + Unpin>(input: &mut R, max: usize) -> io::Result<(bool, u8, Vec<u8>)> {
+    let first = input.read_u8().await?;
+    let second = input.read_u8().await?;
+    let len = match second & 0x7f {
+        126 => input.read_u16().await? as u64,
+        127 => input.read_u64().await?,
+        len => len as u64,
+    };
+    if len > max as u64 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("websocket frame of {} bytes is too big", len)));
+    }
+    let mask = if second & 0x80 != 0 {
+        let mut mask = [0; 4];
+        input.read_exact(&mut mask).await?;
+        Some(mask)
+    } else {
+        None
+    };
+    let mut payload = vec![0; len as usize];
+    input.read_exact(&mut payload).await?;
+    if let Some(mask) = mask {
+        for (i, b) in payload.iter_mut().enumerate() {
+            *b ^= mask[i % 4];
+        }
+    }
+    Ok((first & 0x80 != 0, first & 0x0f, payload))
+}
+
+/// the next text or binary message, put together from its fragments. pings are answered with a
+/// pong on `out`, a close frame is echoed there and then ends the stream like an eof. the answers
+/// are sent as the server does, unmasked. messages bigger than `max` bytes are refused.
+pub async fn read_message<R, W>(input: &mut R, out: &Mutex<W>, max: usize) -> io::Result<Vec<u8>>
+    where R: AsyncRead + Unpin, W: AsyncWrite + Unpin {
+    let mut message = Vec::new();
+    loop {
+        let (fin, opcode, payload) = read_frame(input, max).await?;
+        match opcode {
+            OP_CLOSE => {
+                // the echo carries the status code of the client, if it sent one.
+                let mut out = out.lock().await;
+                let _ = write_frame(&mut *out, OP_CLOSE, &payload[..payload.len().min(2)], None).await;
+                let _ = out.shutdown().await;
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "websocket closed"));
+            }
+            OP_PING => write_frame(&mut *out.lock().await, OP_PONG, &payload, None).await?,
+            OP_PONG => continue,
+            OP_TEXT | OP_BINARY | OP_CONTINUATION => {
+                if message.len() + payload.len() > max {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "websocket message is too big"));
+                }
+                message.extend_from_slice(&payload);
+                if fin {
+                    return Ok(message);
+                }
+            }
+            other => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown websocket opcode {}", other))),
+        }
+    }
+}
+
+/// write a packet as one binary message, the websocket frame replaces the length prefix.
+pub async fn write_packet<W: AsyncWrite + Unpin>(out: &mut W, packet: &NetPacket) -> io::Result<()> {
+    let serialized = packet.write_to_bytes().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    write_frame(out, OP_BINARY, &serialized, None).await
+}
+
+/// read a packet sent as one binary message, see `read_message` for `out`.
+pub async fn read_packet<R, W>(input: &mut R, out: &Mutex<W>) -> io::Result<NetPacket>
+    where R: AsyncRead + Unpin, W: AsyncWrite + Unpin {
+    read_packet_limited(input, out, MAX_PACKET_SIZE).await
+}
+
+/// `read_packet` of a message no bigger than `max` bytes.
+pub async fn read_packet_limited<R, W>(input: &mut R, out: &Mutex<W>, max: usize) -> io::Result<NetPacket>
+    where R: AsyncRead + Unpin, W: AsyncWrite + Unpin {
+    let message = read_message(input, out, max).await?;
+    NetPacket::parse_from_bytes(&message).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// whether `request` asks to become a websocket.
+pub fn is_upgrade(request: &Request) -> bool {
+    request.header("upgrade").map_or(false, |v| v.eq_ignore_ascii_case("websocket"))
+        && request.header("connection").map_or(false, |v| http::has_token(v, "upgrade"))
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for chunk in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (w, word) in w.iter_mut().zip(chunk.chunks(4)) {
+            *w = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5a827999),
+                20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6),
+            };
+            let t = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = t;
+        }
+        for (h, v) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(v);
+        }
+    }
+
+    let mut out = [0; 20];
+    for (i, v) in h.iter().enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&v.to_be_bytes());
+    }
+    out
+}
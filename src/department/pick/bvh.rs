@@ -0,0 +1,162 @@
+use cgmath::{Point3, Vector3};
+
+use super::{intersect_triangle, Ray};
+
+// triangles a leaf holds before it gets split.
+const LEAF_SIZE: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Point3<f32>,
+    pub max: Point3<f32>,
+}
+
+impl Aabb {
+    pub const EMPTY: Aabb = Aabb {
+        min: Point3 { x: f32::INFINITY, y: f32::INFINITY, z: f32::INFINITY },
+        max: Point3 { x: f32::NEG_INFINITY, y: f32::NEG_INFINITY, z: f32::NEG_INFINITY },
+    };
+
+    pub fn grow(&mut self, p: Point3<f32>) {
+        self.min = Point3::new(self.min.x.min(p.x), self.min.y.min(p.y), self.min.z.min(p.z));
+        self.max = Point3::new(self.max.x.max(p.x), self.max.y.max(p.y), self.max.z.max(p.z));
+    }
+
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        let mut out = *self;
+        out.grow(other.min);
+        out.grow(other.max);
+        out
+    }
+
+    fn extent(&self) -> Vector3<f32> {
+        self.max - self.min
+    }
+
+    /// distance along the ray where it enters the box, None when it misses or the box is
+    /// farther than `max_t`.
+    pub fn hit(&self, ray: &Ray, max_t: f32) -> Option<f32> {
+        let mut near = 0f32;
+        let mut far = max_t;
+        for axis in 0..3 {
+            let inv = 1. / ray.direction[axis];
+            let mut t0 = (self.min[axis] - ray.origin[axis]) * inv;
+            let mut t1 = (self.max[axis] - ray.origin[axis]) * inv;
+            if inv < 0. {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            // NaN from a zero direction against a face the origin lies on keeps the old bounds.
+            near = if t0 > near { t0 } else { near };
+            far = if t1 < far { t1 } else { far };
+            if far < near {
+                return None;
+            }
+        }
+        Some(near)
+    }
+}
+
+#[derive(Debug)]
+enum Node {
+    Leaf { bounds: Aabb, first: usize, count: usize },
+    Inner { bounds: Aabb, left: usize, right: usize },
+}
+
+impl Node {
+    fn bounds(&self) -> &Aabb {
+        match self {
+            Node::Leaf { bounds, .. } | Node::Inner { bounds, .. } => bounds,
+        }
+    }
+}
+
+/// bounding volume hierarchy over the triangles of one mesh, split at the median of the
+/// longest axis.
+#[derive(Debug)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    // triangle indices, leaves point at ranges of it.
+    order: Vec<usize>,
+}
+
+impl Bvh {
+    /// `triangles` are the corners of every triangle.
+    pub fn build(triangles: &[[Point3<f32>; 3]]) -> Self {
+        let mut bvh = Bvh { nodes: Vec::new(), order: (0..triangles.len()).collect() };
+        if !triangles.is_empty() {
+            let centers: Vec<Point3<f32>> = triangles.iter()
+                .map(|[a, b, c]| Point3::new((a.x + b.x + c.x) / 3., (a.y + b.y + c.y) / 3., (a.z + b.z + c.z) / 3.))
+                .collect();
+            bvh.split(triangles, &centers, 0, triangles.len());
+        }
+        bvh
+    }
+
+    // builds the node for order[first..first + count] and returns its index.
+    fn split(&mut self, triangles: &[[Point3<f32>; 3]], centers: &[Point3<f32>], first: usize, count: usize) -> usize {
+        let mut bounds = Aabb::EMPTY;
+        let mut center_bounds = Aabb::EMPTY;
+        for &t in &self.order[first..first + count] {
+            triangles[t].iter().for_each(|p| bounds.grow(*p));
+            center_bounds.grow(centers[t]);
+        }
+
+        let index = self.nodes.len();
+        self.nodes.push(Node::Leaf { bounds, first, count });
+        if count <= LEAF_SIZE {
+            return index;
+        }
+
+        let extent = center_bounds.extent();
+        let axis = if extent.x >= extent.y && extent.x >= extent.z { 0 } else if extent.y >= extent.z { 1 } else { 2 };
+        if extent[axis] <= 0. {
+            // every center is the same point, nothing to split.
+            return index;
+        }
+        let half = count / 2;
+        self.order[first..first + count].select_nth_unstable_by(half, |a, b| {
+            centers[*a][axis].partial_cmp(&centers[*b][axis]).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let left = self.split(triangles, centers, first, half);
+        let right = self.split(triangles, centers, first + half, count - half);
+        self.nodes[index] = Node::Inner { bounds, left, right };
+        index
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        self.nodes.first().map_or(Aabb::EMPTY, |n| *n.bounds())
+    }
+
+    /// closest triangle the ray hits, as (triangle index, distance along the ray).
+    pub fn intersect(&self, triangles: &[[Point3<f32>; 3]], ray: &Ray) -> Option<(usize, f32)> {
+        let mut best: Option<(usize, f32)> = None;
+        let mut stack = Vec::with_capacity(32);
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(i) = stack.pop() {
+            let max_t = best.map_or(f32::INFINITY, |(_, t)| t);
+            if self.nodes[i].bounds().hit(ray, max_t).is_none() {
+                continue;
+            }
+            match self.nodes[i] {
+                Node::Leaf { first, count, .. } => {
+                    for &t in &self.order[first..first + count] {
+                        let [a, b, c] = triangles[t];
+                        if let Some(d) = intersect_triangle(ray, a, b, c) {
+                            if d < max_t && best.map_or(true, |(_, bt)| d < bt) {
+                                best = Some((t, d));
+                            }
+                        }
+                    }
+                }
+                Node::Inner { left, right, .. } => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+        best
+    }
+}
@@ -0,0 +1,165 @@
+use cgmath::{EuclideanSpace, InnerSpace, Matrix4, Point3, SquareMatrix, Transform, Vector3, Vector4};
+
+use crate::department::preview::homo_transformation::HomoTransform;
+use crate::department::view::camera_trait::CameraTrait;
+
+use self::bvh::Bvh;
+
+pub mod bvh;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: Point3<f32>,
+    pub direction: Vector3<f32>,
+}
+
+impl Ray {
+    pub fn at(&self, t: f32) -> Point3<f32> {
+        self.origin + self.direction * t
+    }
+
+    /// the ray in the space `m` maps from. the direction is not normalized again, so distances
+    /// along the moved ray are the same as along this one.
+    pub fn transformed(&self, m: &Matrix4<f32>) -> Ray {
+        Ray { origin: m.transform_point(self.origin), direction: m.transform_vector(self.direction) }
+    }
+}
+
+/// normalized device coordinates of the middle of a pixel, y pointing up.
+pub fn ndc(pixel: (u32, u32), size: (u32, u32)) -> (f32, f32) {
+    (
+        (pixel.0 as f32 + 0.5) / size.0 as f32 * 2. - 1.,
+        1. - (pixel.1 as f32 + 0.5) / size.1 as f32 * 2.,
+    )
+}
+
+/// ray from `eye` through the point at `ndc` of the inverse view projection `inv_view_proj`,
+/// pointed the same way as `forward`.
+pub fn unproject(inv_view_proj: &Matrix4<f32>, ndc: (f32, f32), eye: Point3<f32>, forward: Vector3<f32>) -> Ray {
+    let p = inv_view_proj * Vector4::new(ndc.0, ndc.1, 0.5, 1.);
+    let p = Point3::from_vec(p.truncate() / p.w);
+    let mut direction = (p - eye).normalize();
+    if direction.dot(forward) < 0. {
+        direction = -direction;
+    }
+    Ray { origin: eye, direction }
+}
+
+/// ray through `ndc` of a target rendered at `size`, which may not have the aspect `camera` projects with.
+pub fn target_ray<T: CameraTrait + Clone>(camera: &T, ndc: (f32, f32), size: (u32, u32)) -> Ray {
+    let mut camera = camera.clone();
+    camera.update_projection(size.0, size.1);
+    camera.screen_ray(ndc)
+}
+
+/// column vector form of a matrix of the cpu pipeline, which multiplies row vectors.
+pub fn to_column_major(m: &HomoTransform) -> Matrix4<f32> {
+    // the rows of `m` become the columns.
+    Matrix4::from(m.to_slice())
+}
+
+/// distance along the ray to the triangle a, b, c, Möller–Trumbore. both sides count.
+pub fn intersect_triangle(ray: &Ray, a: Point3<f32>, b: Point3<f32>, c: Point3<f32>) -> Option<f32> {
+    const EPSILON: f32 = 1e-7;
+    let e1 = b - a;
+    let e2 = c - a;
+    let p = ray.direction.cross(e2);
+    let det = e1.dot(p);
+    if det.abs() < EPSILON {
+        return None;
+    }
+    let inv_det = 1. / det;
+    let s = ray.origin - a;
+    let u = s.dot(p) * inv_det;
+    if !(0. ..=1.).contains(&u) {
+        return None;
+    }
+    let q = s.cross(e1);
+    let v = ray.direction.dot(q) * inv_det;
+    if v < 0. || u + v > 1. {
+        return None;
+    }
+    let t = e2.dot(q) * inv_det;
+    if t > EPSILON { Some(t) } else { None }
+}
+
+/// what a ray hit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hit {
+    pub instance: usize,
+    pub mesh: usize,
+    /// index of the triangle in its mesh.
+    pub triangle: usize,
+    /// in world space.
+    pub point: Point3<f32>,
+    pub distance: f32,
+}
+
+pub struct PickMesh {
+    pub name: String,
+    triangles: Vec<[Point3<f32>; 3]>,
+    bvh: Bvh,
+}
+
+impl PickMesh {
+    pub fn new(name: String, positions: &[f32], indices: &[u32]) -> Self {
+        let corner = |i: u32| {
+            let i = i as usize * 3;
+            Point3::new(positions[i], positions[i + 1], positions[i + 2])
+        };
+        let triangles: Vec<[Point3<f32>; 3]> = indices.chunks_exact(3)
+            .map(|t| [corner(t[0]), corner(t[1]), corner(t[2])])
+            .collect();
+        let bvh = Bvh::build(&triangles);
+        Self { name, triangles, bvh }
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    pub fn intersect(&self, ray: &Ray) -> Option<(usize, f32)> {
+        self.bvh.intersect(&self.triangles, ray)
+    }
+}
+
+/// the meshes of the loaded model on the cpu side, to be hit by rays.
+pub struct PickScene {
+    pub meshes: Vec<PickMesh>,
+}
+
+impl PickScene {
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let (models, _) = tobj::load_obj(path, &tobj::LoadOptions { triangulate: true, single_index: true, ..Default::default() })?;
+        Ok(Self {
+            meshes: models.iter().map(|m| PickMesh::new(m.name.clone(), &m.mesh.positions, &m.mesh.indices)).collect(),
+        })
+    }
+
+    /// closest hit of a world space ray against the model placed by every matrix of `instances`.
+    pub fn pick(&self, ray: &Ray, instances: &[Matrix4<f32>]) -> Option<Hit> {
+        let mut best: Option<Hit> = None;
+        for (instance, model) in instances.iter().enumerate() {
+            let inverse = match model.invert() {
+                Some(m) => m,
+                None => continue,
+            };
+            let local = ray.transformed(&inverse);
+            for (mesh_index, mesh) in self.meshes.iter().enumerate() {
+                if let Some((triangle, distance)) = mesh.intersect(&local) {
+                    if best.map_or(true, |b| distance < b.distance) {
+                        best = Some(Hit { instance, mesh: mesh_index, triangle, point: ray.at(distance), distance });
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// one line describing a hit, for logs and the hud.
+    pub fn describe(&self, hit: &Hit) -> String {
+        let mesh = self.meshes.get(hit.mesh).map_or("?", |m| m.name.as_str());
+        format!("instance {} mesh {} ({}) triangle {} at {:.2} {:.2} {:.2}",
+                hit.instance + 1, hit.mesh, mesh, hit.triangle, hit.point.x, hit.point.y, hit.point.z)
+    }
+}
@@ -8,6 +8,7 @@ use crate::department::preview::homo_transformation::HomoTransform;
 use crate::department::preview::output_buffer::OutputBuffer;
 use crate::department::preview::vector::Vector3;
 use crate::department::view::camera::Camera;
+use crate::department::view::camera_trait::CameraTrait;
 use crate::department::types::multi_sender::MultiSender;
 use crate::department::types::msg::TransferMsg;
 
@@ -40,9 +41,37 @@ impl RasterRunner {
         self.model_mat = m;
     }
 
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    pub fn camera_mut(&mut self) -> &mut Camera {
+        &mut self.camera
+    }
+
+    pub fn shader_name(&self) -> &'static str {
+        self.shader.name()
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.camera.update_projection(width, height);
+        self.proj_mat = self.camera.perspective_projection.clone();
+    }
+
 
     pub fn render_frame(&self, triangle_res: &TriangleResources, out:&mut OutputBuffer) {
-        let mv = &self.model_mat * &self.view_mat;
+        self.render_with_model(triangle_res, out, &self.model_mat);
+    }
+
+    /// draw the model once for every model matrix, sharing the depth buffer of `out`.
+    pub fn render_instances(&self, triangle_res: &TriangleResources, out: &mut OutputBuffer, models: &[HomoTransform]) {
+        for model in models {
+            self.render_with_model(triangle_res, out, model);
+        }
+    }
+
+    fn render_with_model(&self, triangle_res: &TriangleResources, out: &mut OutputBuffer, model: &HomoTransform) {
+        let mv = model * &self.view_mat;
         let mvp = &mv * &self.proj_mat;
         let view_port = out.to_view_port_matrix();
         let image = triangle_res.image.as_ref().unwrap();
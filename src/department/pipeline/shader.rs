@@ -3,6 +3,7 @@ use crate::department::preview::matrix::Matrix;
 use crate::department::preview::vector::Vector3;
 use crate::department::view::camera::Camera;
 
+// from dark to bright, used by the ascii cell mode.
 pub static LUMINANCE_CHARS: [char; 12] = ['.', ',', '-', '~', ':', ';', '=', '!', '*', '#', '$', '@'];
 
 //#[derive(Debug)]
@@ -13,15 +14,18 @@ pub struct LambertianShader {
     ka: f32,
     ks_index: f32,
     light_intensity: f32,
-    tui: bool,
 }
 
 pub trait Shader {
     fn shade(&self, normal: &Vec<Vector3>, diffuse: &[u8; 4], bar: &Vector3) -> [u8;4];
+
+    fn name(&self) -> &'static str {
+        "custom"
+    }
 }
 
 impl LambertianShader{
-    pub fn new(light_source: Vector3, ka: f32, light_intensity: f32, cam: &Camera, tui: bool) -> Self {
+    pub fn new(light_source: Vector3, ka: f32, light_intensity: f32, cam: &Camera) -> Self {
         let mv = &cam.model * &cam.to_view_matrix();
         let mut mv_it = HomoTransform::identity_matrix();
         if let Some(inverse) = mv.inverse_matrix() {
@@ -39,12 +43,15 @@ impl LambertianShader{
             model_view: mv,
             model_view_IT: mv_it,
             ks_index: 10.,
-            tui,
         }
     }
 }
 
 impl Shader for LambertianShader {
+    fn name(&self) -> &'static str {
+        "lambertian"
+    }
+
     fn shade(&self, normal: &Vec<Vector3>, diffuse: &[u8;4], bar: &Vector3) -> [u8;4] {
         let mut n = Vec::new();
         for i in 0..normal.len() {
@@ -62,18 +69,8 @@ impl Shader for LambertianShader {
         }else {
             cos
         };
-        let index = ((LUMINANCE_CHARS.len() - 1) as f32 * intensity).ceil() as usize;
-        let final_char = LUMINANCE_CHARS[index];
-
         let (r, g, b) = (intensity * diffuse[0] as f32, intensity * diffuse[1] as f32, intensity* diffuse[2] as f32);
 
-
-        if self.tui {
-            [r as u8 , g as u8, b as u8, final_char as u8]
-        }else {
-            [r as u8 , g as u8, b as u8, diffuse[3]]
-        }
-
-
+        [r as u8 , g as u8, b as u8, diffuse[3]]
     }
 }
\ No newline at end of file
@@ -0,0 +1,196 @@
+use clap::ValueEnum;
+
+use crate::department::pipeline::shader::LUMINANCE_CHARS;
+
+/// how rendered pixels are packed into terminal cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CellMode {
+    /// one pixel per cell, printed as a colored dot.
+    Dot,
+    /// two vertical pixels per cell using '▀' with separate fg/bg colors.
+    HalfBlock,
+    /// 2x2 pixels per cell using the quadrant block characters.
+    Quadrant,
+    /// 2x4 pixels per cell using braille dots.
+    Braille,
+    /// one pixel per cell, the glyph is picked from a luminance ramp.
+    Ascii,
+    /// like ascii but without any color, for dumb terminals and logs.
+    AsciiMono,
+}
+
+impl CellMode {
+    /// how many pixels (columns, rows) one terminal cell stands for.
+    pub fn cell_pixels(&self) -> (u32, u32) {
+        match self {
+            CellMode::Dot | CellMode::Ascii | CellMode::AsciiMono => (1, 1),
+            CellMode::HalfBlock => (1, 2),
+            CellMode::Quadrant => (2, 2),
+            CellMode::Braille => (2, 4),
+        }
+    }
+
+    /// render resolution needed to fill a terminal of `cells` columns and rows.
+    pub fn render_dimension(&self, cells: (u32, u32)) -> (u32, u32) {
+        let (w, h) = self.cell_pixels();
+        (cells.0 * w, cells.1 * h)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub fg: Option<[u8; 3]>,
+    // None keeps the terminal background.
+    pub bg: Option<[u8; 3]>,
+}
+
+impl Cell {
+    pub const EMPTY: Cell = Cell { ch: ' ', fg: None, bg: None };
+
+    pub fn is_empty(&self) -> bool {
+        *self == Cell::EMPTY
+    }
+}
+
+// indexed by bit mask: top left 1, top right 2, bottom left 4, bottom right 8.
+const QUADRANT_CHARS: [char; 16] = [' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█'];
+
+// braille dot bit for pixel (x, y) inside a 2x4 cell.
+const BRAILLE_BITS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+struct PixelView<'a> {
+    display: &'a [u8],
+    coverage: &'a [bool],
+    width: u32,
+    height: u32,
+}
+
+impl<'a> PixelView<'a> {
+    // None if the pixel is outside the frame or nothing was drawn there.
+    fn get(&self, x: u32, y: u32) -> Option<[u8; 3]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let idx = (y * self.width + x) as usize;
+        if !self.coverage[idx] {
+            return None;
+        }
+        let p = &self.display[idx * 4..idx * 4 + 4];
+        Some([p[0], p[1], p[2]])
+    }
+}
+
+fn average(colors: &[[u8; 3]]) -> [u8; 3] {
+    let mut sum = [0u32; 3];
+    for c in colors {
+        for i in 0..3 {
+            sum[i] += c[i] as u32;
+        }
+    }
+    let n = colors.len().max(1) as u32;
+    [(sum[0] / n) as u8, (sum[1] / n) as u8, (sum[2] / n) as u8]
+}
+
+fn luminance(c: &[u8; 3]) -> f32 {
+    0.2126 * c[0] as f32 + 0.7152 * c[1] as f32 + 0.0722 * c[2] as f32
+}
+
+fn encode_dot(view: &PixelView, x: u32, y: u32) -> Cell {
+    match view.get(x, y) {
+        Some(c) => Cell { ch: '•', fg: Some(c), bg: None },
+        None => Cell::EMPTY,
+    }
+}
+
+fn encode_ascii(view: &PixelView, x: u32, y: u32, colored: bool) -> Cell {
+    match view.get(x, y) {
+        Some(c) => {
+            let index = ((LUMINANCE_CHARS.len() - 1) as f32 * luminance(&c) / 255.).round() as usize;
+            Cell { ch: LUMINANCE_CHARS[index], fg: if colored { Some(c) } else { None }, bg: None }
+        }
+        None => Cell::EMPTY,
+    }
+}
+
+fn encode_half_block(view: &PixelView, x: u32, y: u32) -> Cell {
+    match (view.get(x, y), view.get(x, y + 1)) {
+        (Some(top), bottom) => Cell { ch: '▀', fg: Some(top), bg: bottom },
+        (None, Some(bottom)) => Cell { ch: '▄', fg: Some(bottom), bg: None },
+        (None, None) => Cell::EMPTY,
+    }
+}
+
+fn encode_quadrant(view: &PixelView, x: u32, y: u32) -> Cell {
+    let pixels = [view.get(x, y), view.get(x + 1, y), view.get(x, y + 1), view.get(x + 1, y + 1)];
+    let covered: Vec<[u8; 3]> = pixels.iter().flatten().cloned().collect();
+    if covered.is_empty() {
+        return Cell::EMPTY;
+    }
+
+    if covered.len() < pixels.len() {
+        // covered pixels are drawn, the rest shows the terminal background.
+        let mask = pixels.iter().enumerate().filter(|(_, p)| p.is_some()).fold(0, |m, (i, _)| m | 1 << i);
+        return Cell { ch: QUADRANT_CHARS[mask], fg: Some(average(&covered)), bg: None };
+    }
+
+    // fully covered, split the pixels into a bright and a dark group.
+    let mean = covered.iter().map(luminance).sum::<f32>() / covered.len() as f32;
+    let mut mask = 0;
+    let (mut bright, mut dark) = (Vec::new(), Vec::new());
+    for (i, c) in covered.iter().enumerate() {
+        if luminance(c) > mean {
+            mask |= 1 << i;
+            bright.push(*c);
+        } else {
+            dark.push(*c);
+        }
+    }
+
+    if bright.is_empty() {
+        Cell { ch: '█', fg: Some(average(&dark)), bg: None }
+    } else {
+        Cell { ch: QUADRANT_CHARS[mask], fg: Some(average(&bright)), bg: Some(average(&dark)) }
+    }
+}
+
+fn encode_braille(view: &PixelView, x: u32, y: u32) -> Cell {
+    let mut bits = 0u8;
+    let mut covered = Vec::with_capacity(8);
+    for dx in 0..2 {
+        for dy in 0..4 {
+            if let Some(c) = view.get(x + dx, y + dy) {
+                bits |= BRAILLE_BITS[dx as usize][dy as usize];
+                covered.push(c);
+            }
+        }
+    }
+    if bits == 0 {
+        return Cell::EMPTY;
+    }
+    let ch = char::from_u32(0x2800 + bits as u32).unwrap();
+    Cell { ch, fg: Some(average(&covered)), bg: None }
+}
+
+/// pack an rgba frame of `width` x `height` pixels into a row-major grid of terminal cells.
+pub fn encode(mode: CellMode, display: &[u8], coverage: &[bool], width: u32, height: u32) -> (u32, u32, Vec<Cell>) {
+    let (cw, ch) = mode.cell_pixels();
+    let (cols, rows) = ((width + cw - 1) / cw, (height + ch - 1) / ch);
+    let view = PixelView { display, coverage, width, height };
+
+    let mut cells = Vec::with_capacity((cols * rows) as usize);
+    for row in 0..rows {
+        for col in 0..cols {
+            let (x, y) = (col * cw, row * ch);
+            cells.push(match mode {
+                CellMode::Dot => encode_dot(&view, x, y),
+                CellMode::HalfBlock => encode_half_block(&view, x, y),
+                CellMode::Quadrant => encode_quadrant(&view, x, y),
+                CellMode::Braille => encode_braille(&view, x, y),
+                CellMode::Ascii => encode_ascii(&view, x, y, true),
+                CellMode::AsciiMono => encode_ascii(&view, x, y, false),
+            });
+        }
+    }
+    (cols, rows, cells)
+}
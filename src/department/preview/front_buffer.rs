@@ -0,0 +1,104 @@
+use std::io::Write;
+
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+
+use super::cell_encoder::Cell;
+use super::palette::{self, ColorDepth};
+
+/// the cells that are currently on screen, so a frame only emits what changed.
+pub struct FrontBuffer {
+    cols: u32,
+    rows: u32,
+    cells: Vec<Cell>,
+    // (x, y, width, height) areas drawn by something else, like the hud, which are left alone.
+    overlay: Vec<(u32, u32, u32, u32)>,
+}
+
+// colors of the last printed cell, to skip redundant SGR sequences inside a run.
+struct Pen {
+    fg: Option<Color>,
+    bg: Option<Color>,
+}
+
+impl Pen {
+    fn apply<W: Write>(&mut self, out: &mut W, fg: Option<Color>, bg: Option<Color>) {
+        if (self.fg.is_some() && fg.is_none()) || (self.bg.is_some() && bg.is_none()) {
+            queue!(out, ResetColor);
+            self.fg = None;
+            self.bg = None;
+        }
+        if fg != self.fg {
+            if let Some(c) = fg {
+                queue!(out, SetForegroundColor(c));
+            }
+            self.fg = fg;
+        }
+        if bg != self.bg {
+            if let Some(c) = bg {
+                queue!(out, SetBackgroundColor(c));
+            }
+            self.bg = bg;
+        }
+    }
+}
+
+impl FrontBuffer {
+    pub fn new() -> Self {
+        Self { cols: 0, rows: 0, cells: Vec::new(), overlay: Vec::new() }
+    }
+
+    /// forget what is on screen, call it after the screen was cleared.
+    pub fn invalidate(&mut self) {
+        self.cells.iter_mut().for_each(|c| *c = Cell::EMPTY);
+    }
+
+    pub fn set_overlay(&mut self, overlay: Vec<(u32, u32, u32, u32)>) {
+        self.overlay = overlay;
+    }
+
+    fn covered(&self, col: u32, row: u32) -> bool {
+        self.overlay.iter().any(|(x, y, w, h)| col >= *x && col < x + w && row >= *y && row < y + h)
+    }
+
+    /// queue the difference between the screen and `cells`, a row-major grid of `cols` x `rows`.
+    /// changed cells next to each other on a row share one cursor move, and cells that became
+    /// empty are cleared.
+    pub fn present<W: Write>(&mut self, out: &mut W, cols: u32, rows: u32, cells: &[Cell], depth: ColorDepth) {
+        if cols != self.cols || rows != self.rows {
+            // the app clears the screen when the terminal is resized.
+            self.cols = cols;
+            self.rows = rows;
+            self.cells = vec![Cell::EMPTY; (cols * rows) as usize];
+        }
+
+        let mut pen = Pen { fg: None, bg: None };
+        queue!(out, ResetColor);
+
+        for row in 0..rows as usize {
+            let mut in_run = false;
+            for col in 0..cols as usize {
+                let idx = row * cols as usize + col;
+                let cell = &cells[idx];
+                if *cell == self.cells[idx] || self.covered(col as u32, row as u32) {
+                    in_run = false;
+                    continue;
+                }
+
+                if !in_run {
+                    queue!(out, MoveTo(col as u16, row as u16));
+                    in_run = true;
+                }
+
+                let fg = cell.fg.map(|c| palette::to_term_color(depth, &c));
+                let bg = cell.bg.map(|c| palette::to_term_color(depth, &c));
+                pen.apply(out, fg, bg);
+                queue!(out, Print(cell.ch));
+                self.cells[idx] = *cell;
+            }
+        }
+
+        queue!(out, ResetColor);
+    }
+}
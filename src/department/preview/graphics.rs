@@ -0,0 +1,307 @@
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use crossterm::terminal;
+use lazy_static::lazy_static;
+use log::debug;
+
+use crate::department::common::base64;
+use crate::department::common::constant::{GRAPHICS_CELL_HEIGHT, GRAPHICS_CELL_WIDTH};
+use crate::util::ARG;
+use super::cell_encoder::CellMode;
+use super::palette;
+
+/// how a frame is put on the terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum GraphicsProtocol {
+    /// glyphs picked by the cell mode.
+    Cells,
+    /// DEC sixel images, xterm -ti vt340, foot, mlterm, WezTerm.
+    Sixel,
+    /// kitty graphics protocol, kitty, WezTerm, konsole.
+    Kitty,
+}
+
+impl GraphicsProtocol {
+    /// render resolution needed to fill a terminal of `cells` columns and rows.
+    pub fn render_dimension(&self, cell_mode: CellMode, cells: (u32, u32)) -> (u32, u32) {
+        match self {
+            GraphicsProtocol::Cells => cell_mode.render_dimension(cells),
+            // the last row is left free, an image touching the bottom makes the terminal scroll.
+            _ => {
+                let (w, h) = cell_size();
+                (cells.0 * w, cells.1.saturating_sub(1).max(1) * h)
+            }
+        }
+    }
+
+    /// render pixel in the middle of the terminal cell at `column`, `row`.
+    pub fn cell_to_pixel(&self, cell_mode: CellMode, column: u16, row: u16) -> (u32, u32) {
+        let (w, h) = match self {
+            GraphicsProtocol::Cells => cell_mode.cell_pixels(),
+            _ => cell_size(),
+        };
+        (column as u32 * w + w / 2, row as u32 * h + h / 2)
+    }
+}
+
+lazy_static! {
+    static ref PROTOCOL: GraphicsProtocol = ARG.graphics.unwrap_or_else(detect);
+    // asked once, for terminals that leave the pixel size of the window at 0.
+    static ref REPORTED_CELL_SIZE: Option<(u32, u32)> = query_cell_size();
+}
+
+/// the protocol given on the command line, or the detected one. the terminal is asked before the
+/// app reads any input, so the cell size is asked along with it when images are drawn.
+pub fn protocol() -> GraphicsProtocol {
+    let protocol = *PROTOCOL;
+    if protocol != GraphicsProtocol::Cells {
+        lazy_static::initialize(&REPORTED_CELL_SIZE);
+    }
+    protocol
+}
+
+/// pixels of one terminal cell, from the window size, the terminal's answer to `CSI 16 t`, or
+/// a common font size.
+pub fn cell_size() -> (u32, u32) {
+    terminal::window_size().ok()
+        .and_then(|size| cell_size_from_window(size.columns, size.rows, size.width, size.height))
+        .or_else(|| *REPORTED_CELL_SIZE)
+        .unwrap_or((GRAPHICS_CELL_WIDTH, GRAPHICS_CELL_HEIGHT))
+}
+
+/// the cell size of a window of `columns` x `rows` cells and `width` x `height` pixels, None when
+/// the terminal doesn't tell the pixels.
+pub fn cell_size_from_window(columns: u16, rows: u16, width: u16, height: u16) -> Option<(u32, u32)> {
+    if columns == 0 || rows == 0 || width < columns || height < rows {
+        return None;
+    }
+    Some(((width / columns) as u32, (height / rows) as u32))
+}
+
+/// the cell size in a reply to `CSI 16 t`, which looks like `ESC [ 6 ; height ; width t`.
+pub fn cell_size_from_reply(reply: &str) -> Option<(u32, u32)> {
+    let start = reply.find("\x1b[6;")?;
+    let (height, width) = reply[start + 4..].split('t').next()?.split_once(';')?;
+    let (w, h) = (width.parse().ok()?, height.parse().ok()?);
+    Some((w, h)).filter(|_| w > 0 && h > 0)
+}
+
+const QUERY_TIMEOUT: Duration = Duration::from_millis(200);
+
+// kitty answers the graphics query with `_Gi=31;OK`, every terminal answers the primary device
+// attributes query, so the reply to that one ends the read.
+const KITTY_QUERY: &str = "\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\";
+const DA1_QUERY: &str = "\x1b[c";
+const CELL_SIZE_QUERY: &str = "\x1b[16t";
+
+/// guess the protocol from the environment, then ask the terminal.
+pub fn detect() -> GraphicsProtocol {
+    if let Some(p) = detect_from_env(
+        std::env::var("TERM").ok().as_deref(),
+        std::env::var("TERM_PROGRAM").ok().as_deref(),
+        std::env::var("KITTY_WINDOW_ID").is_ok(),
+    ) {
+        return p;
+    }
+
+    match query_terminal(KITTY_QUERY) {
+        Some(reply) => detect_from_reply(&reply),
+        None => GraphicsProtocol::Cells,
+    }
+}
+
+fn query_cell_size() -> Option<(u32, u32)> {
+    let size = terminal::window_size().ok();
+    if size.is_some_and(|s| cell_size_from_window(s.columns, s.rows, s.width, s.height).is_some()) {
+        return None;
+    }
+    query_terminal(CELL_SIZE_QUERY).and_then(|reply| cell_size_from_reply(&reply))
+}
+
+pub fn detect_from_env(term: Option<&str>, term_program: Option<&str>, kitty_window: bool) -> Option<GraphicsProtocol> {
+    if kitty_window || term == Some("xterm-kitty") || term_program == Some("WezTerm") {
+        return Some(GraphicsProtocol::Kitty);
+    }
+    if term.is_some_and(|t| t.starts_with("foot")) {
+        return Some(GraphicsProtocol::Sixel);
+    }
+    None
+}
+
+/// pick the protocol from what the terminal answered to the kitty and device attributes queries.
+pub fn detect_from_reply(reply: &str) -> GraphicsProtocol {
+    if reply.contains("_Gi=31;OK") {
+        return GraphicsProtocol::Kitty;
+    }
+
+    // primary device attributes look like `ESC [ ? 62 ; 4 ; 22 c`, 4 means sixel.
+    let attributes = reply.rfind("\x1b[?")
+        .and_then(|start| reply[start + 3..].split('c').next())
+        .unwrap_or("");
+    if attributes.split(';').any(|a| a == "4") {
+        GraphicsProtocol::Sixel
+    } else {
+        GraphicsProtocol::Cells
+    }
+}
+
+// whether the reply holds the answer to the device attributes query, the last one asked.
+fn is_complete(reply: &[u8]) -> bool {
+    reply.windows(3).position(|w| w == b"\x1b[?").is_some_and(|start| reply[start..].contains(&b'c'))
+}
+
+// send `query` followed by the device attributes query and read what the terminal answers.
+fn query_terminal(query: &str) -> Option<String> {
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return None;
+    }
+
+    terminal::enable_raw_mode().ok()?;
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(query.as_bytes());
+    let _ = stdout.write_all(DA1_QUERY.as_bytes());
+    let _ = stdout.flush();
+
+    let reply = read_reply();
+    let _ = terminal::disable_raw_mode();
+    debug!("terminal query {:?} reply {:?}", query, reply);
+    reply
+}
+
+// read stdin until the reply is complete or the timeout passes. the fd is polled, so nothing is
+// left blocked on stdin to take the keys meant for the app.
+#[cfg(unix)]
+fn read_reply() -> Option<String> {
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    let mut reply = Vec::new();
+    let mut buf = [0u8; 256];
+    while !is_complete(&reply) {
+        let left = deadline.saturating_duration_since(Instant::now());
+        let mut fd = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+        // SAFETY: one valid pollfd is passed, and the buffer outlives the read into it.
+        if left.is_zero() || unsafe { libc::poll(&mut fd, 1, left.as_millis().max(1) as libc::c_int) } <= 0 {
+            break;
+        }
+        let n = unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr().cast(), buf.len()) };
+        if n <= 0 {
+            break;
+        }
+        reply.extend_from_slice(&buf[..n as usize]);
+    }
+    Some(String::from_utf8_lossy(&reply).into_owned()).filter(|r| !r.is_empty())
+}
+
+#[cfg(not(unix))]
+fn read_reply() -> Option<String> {
+    None
+}
+
+// kitty takes at most 4096 bytes of payload per escape sequence.
+const KITTY_CHUNK: usize = 4096;
+const KITTY_IMAGE_ID: u32 = 1;
+
+/// encode an rgba frame as kitty graphics commands. the image keeps the same id and placement,
+/// so every frame replaces the previous one in place.
+pub fn encode_kitty(display: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let rgb: Vec<u8> = display.chunks_exact(4).flat_map(|p| [p[0], p[1], p[2]]).collect();
+    let payload = base64::encode(&rgb);
+
+    let mut out = Vec::with_capacity(payload.len() + payload.len() / KITTY_CHUNK * 32 + 64);
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK).collect();
+    for (n, chunk) in chunks.iter().enumerate() {
+        let more = if n + 1 < chunks.len() { 1 } else { 0 };
+        if n == 0 {
+            // q=2 keeps the terminal quiet, C=1 leaves the cursor where it is.
+            write!(out, "\x1b_Ga=T,f=24,s={},v={},i={},p=1,q=2,C=1,m={};", width, height, KITTY_IMAGE_ID, more).unwrap();
+        } else {
+            write!(out, "\x1b_Gm={};", more).unwrap();
+        }
+        out.extend_from_slice(chunk);
+        out.extend_from_slice(b"\x1b\\");
+    }
+    out
+}
+
+/// remove the images put by `encode_kitty`, they outlive a screen clear.
+pub fn delete_kitty_images<W: Write>(out: &mut W) {
+    let _ = write!(out, "\x1b_Ga=d,d=I,i={},q=2\x1b\\", KITTY_IMAGE_ID);
+    let _ = out.flush();
+}
+
+/// encode an rgba frame as a sixel image, colors are snapped to the xterm-256 palette.
+pub fn encode_sixel(display: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let indices: Vec<u8> = display.chunks_exact(4).map(|p| palette::nearest_256(&[p[0], p[1], p[2]])).collect();
+
+    let mut out = Vec::with_capacity(w * h / 2);
+    // P2=0 paints the pixels without a sixel bit with the background, so the old frame doesn't show through.
+    write!(out, "\x1bP0;0;0q\"1;1;{};{}", width, height).unwrap();
+
+    let mut used = [false; 256];
+    indices.iter().for_each(|i| used[*i as usize] = true);
+    for (index, _) in used.iter().enumerate().filter(|(_, u)| **u) {
+        let c = palette::palette_256(index as u8);
+        let percent = |v: u8| v as u32 * 100 / 255;
+        write!(out, "#{};2;{};{};{}", index, percent(c[0]), percent(c[1]), percent(c[2])).unwrap();
+    }
+
+    let mut band_colors = Vec::with_capacity(256);
+    let mut row = vec![0u8; w];
+    for band in (0..h).step_by(6) {
+        let band_rows = (h - band).min(6);
+
+        band_colors.clear();
+        let mut seen = [false; 256];
+        for y in band..band + band_rows {
+            for &i in &indices[y * w..(y + 1) * w] {
+                if !seen[i as usize] {
+                    seen[i as usize] = true;
+                    band_colors.push(i);
+                }
+            }
+        }
+
+        for (n, &color) in band_colors.iter().enumerate() {
+            for x in 0..w {
+                let mut bits = 0u8;
+                for dy in 0..band_rows {
+                    if indices[(band + dy) * w + x] == color {
+                        bits |= 1 << dy;
+                    }
+                }
+                row[x] = b'?' + bits;
+            }
+
+            if n > 0 {
+                // back to the start of the band for the next color.
+                out.push(b'$');
+            }
+            write!(out, "#{}", color).unwrap();
+            // trailing empty sixels draw nothing.
+            let end = row.iter().rposition(|c| *c != b'?').map_or(0, |p| p + 1);
+            push_sixel_runs(&mut out, &row[..end]);
+        }
+        out.push(b'-');
+    }
+
+    out.extend_from_slice(b"\x1b\\");
+    out
+}
+
+// run length encode a row of sixel characters, `!<count><char>`.
+fn push_sixel_runs(out: &mut Vec<u8>, row: &[u8]) {
+    let mut x = 0;
+    while x < row.len() {
+        let ch = row[x];
+        let run = row[x..].iter().take_while(|c| **c == ch).count();
+        if run > 3 {
+            write!(out, "!{}", run).unwrap();
+            out.push(ch);
+        } else {
+            (0..run).for_each(|_| out.push(ch));
+        }
+        x += run;
+    }
+}
@@ -2,5 +2,9 @@ pub mod position;
 pub mod vector;
 pub mod matrix;
 pub mod output_buffer;
+pub mod cell_encoder;
+pub mod palette;
+pub mod front_buffer;
+pub mod graphics;
 pub mod homo_transformation;
 pub mod matrix_builder;
@@ -1,14 +1,17 @@
-use std::io::Stdout;
+use std::io::{Stdout, Write};
 use std::path::Path;
 
 use crossterm::{queue, style};
 use crossterm::cursor::MoveTo;
-use crossterm::style::Color;
 use crossterm::style::Stylize;
 use image;
 use image::{ImageFormat, RgbaImage};
 use log::error;
 
+use super::cell_encoder::{self, CellMode};
+use super::front_buffer::FrontBuffer;
+use super::graphics::{self, GraphicsProtocol};
+use super::palette::{self, ColorDepth, Dither};
 use super::matrix::Matrix;
 use super::position::Pos3;
 
@@ -19,8 +22,16 @@ pub struct OutputBuffer<'a> {
     height: u32,
     pub display: Vec<u8>,
     pub depth: Vec<f32>,
+    // whether anything was drawn on a pixel, kept apart from the alpha channel.
+    pub coverage: Vec<bool>,
     tui: bool,
     pub stdout: Option<&'a Stdout>,
+    // what the terminal currently shows, without it every cell is printed each frame.
+    pub front: Option<&'a mut FrontBuffer>,
+    pub cell_mode: CellMode,
+    pub color_depth: ColorDepth,
+    pub dither: Dither,
+    pub graphics: GraphicsProtocol,
 }
 
 const RGB_STEP: usize = 4;
@@ -38,8 +49,14 @@ impl<'a> OutputBuffer<'a> {
             width, height,
             display: _display,
             depth: _depth,
+            coverage: vec![false; pixels_num],
             tui,
-            stdout: None
+            stdout: None,
+            front: None,
+            cell_mode: CellMode::Dot,
+            color_depth: ColorDepth::TrueColor,
+            dither: Dither::None,
+            graphics: GraphicsProtocol::Cells,
         }
     }
 
@@ -52,11 +69,22 @@ impl<'a> OutputBuffer<'a> {
     }
 
     pub fn put_pixel(&mut self, x: u32, y: u32, rgb: &[u8]) {
-        let start = (y * self.width + x) as usize * RGB_STEP;
+        let idx = (y * self.width + x) as usize;
+        let start = idx * RGB_STEP;
         let buf = &mut self.display[start..(start + RGB_STEP)];
         for i in 0..RGB_STEP {
             buf[i] = rgb[i];
         }
+        self.coverage[idx] = true;
+    }
+
+    // load a frame rendered by the gpu, which clears to a transparent black, so every pixel with
+    // some alpha counts as drawn, black ones included.
+    pub fn load_frame(&mut self, rgba: &[u8]) {
+        self.display.copy_from_slice(rgba);
+        for (covered, [_, _, _, a]) in self.coverage.iter_mut().zip(rgba.iter().array_chunks()) {
+            *covered = *a != 0;
+        }
     }
 
     pub fn pos_to_pixel(&self, x: f32, y: f32) -> (f32, f32) {
@@ -99,16 +127,50 @@ impl<'a> OutputBuffer<'a> {
             return ;
         }
         let mut stdout = self.stdout.unwrap();
-        let (mut x, mut y) = (0, 0);
-        for (n, [r,g,b,c]) in self.display.iter().array_chunks().enumerate() {
-            if *c as u8 == 0 {
+        if self.graphics != GraphicsProtocol::Cells {
+            let image = match self.graphics {
+                GraphicsProtocol::Sixel if self.dither != Dither::None => {
+                    let mut dithered = self.display.clone();
+                    palette::dither(ColorDepth::Ansi256, self.dither, &mut dithered, &self.coverage, self.width, self.height);
+                    graphics::encode_sixel(&dithered, self.width, self.height)
+                }
+                GraphicsProtocol::Sixel => graphics::encode_sixel(&self.display, self.width, self.height),
+                _ => graphics::encode_kitty(&self.display, self.width, self.height),
+            };
+            queue!(stdout, MoveTo(0, 0));
+            let _ = stdout.write_all(&image);
+            return;
+        }
+
+        let (cols, rows, cells) = if self.dither != Dither::None && self.color_depth != ColorDepth::TrueColor {
+            // display is still sent to the encoder afterwards, so dither a copy.
+            let mut dithered = self.display.clone();
+            palette::dither(self.color_depth, self.dither, &mut dithered, &self.coverage, self.width, self.height);
+            cell_encoder::encode(self.cell_mode, &dithered, &self.coverage, self.width, self.height)
+        } else {
+            cell_encoder::encode(self.cell_mode, &self.display, &self.coverage, self.width, self.height)
+        };
+        if let Some(front) = self.front.as_deref_mut() {
+            front.present(&mut stdout, cols, rows, &cells, self.color_depth);
+            return;
+        }
+
+        for (n, cell) in cells.iter().enumerate() {
+            if cell.is_empty() {
                 continue;
             }
 
-            x = n % self.width as usize;
-            y = n / self.width as usize;
+            let x = n % cols as usize;
+            let y = n / cols as usize;
+            let mut content = style::style(cell.ch);
+            if let Some(fg) = cell.fg {
+                content = content.with(palette::to_term_color(self.color_depth, &fg));
+            }
+            if let Some(bg) = cell.bg {
+                content = content.on(palette::to_term_color(self.color_depth, &bg));
+            }
             queue!(stdout, MoveTo(x as u16, y as u16));
-            queue!(stdout, style::PrintStyledContent(('•' as char).with(Color::Rgb {r:*r ,g: *g, b:*b})));
+            queue!(stdout, style::PrintStyledContent(content));
         }
     }
 }
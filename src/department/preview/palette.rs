@@ -0,0 +1,192 @@
+use clap::ValueEnum;
+use crossterm::style::Color;
+
+/// how many colors the terminal can show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ColorDepth {
+    #[value(name = "truecolor")]
+    TrueColor,
+    #[value(name = "256")]
+    Ansi256,
+    #[value(name = "16")]
+    Ansi16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Dither {
+    None,
+    /// 4x4 bayer matrix.
+    Ordered,
+    FloydSteinberg,
+}
+
+// the usual xterm values of the 16 system colors.
+const ANSI16: [[u8; 3]; 16] = [
+    [0, 0, 0], [205, 0, 0], [0, 205, 0], [205, 205, 0],
+    [0, 0, 238], [205, 0, 205], [0, 205, 205], [229, 229, 229],
+    [127, 127, 127], [255, 0, 0], [0, 255, 0], [255, 255, 0],
+    [92, 92, 255], [255, 0, 255], [0, 255, 255], [255, 255, 255],
+];
+
+const ANSI16_TERM: [Color; 16] = [
+    Color::Black, Color::DarkRed, Color::DarkGreen, Color::DarkYellow,
+    Color::DarkBlue, Color::DarkMagenta, Color::DarkCyan, Color::Grey,
+    Color::DarkGrey, Color::Red, Color::Green, Color::Yellow,
+    Color::Blue, Color::Magenta, Color::Cyan, Color::White,
+];
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// pick the color depth from `COLORTERM` and `TERM`.
+pub fn detect() -> ColorDepth {
+    detect_from(std::env::var("COLORTERM").ok().as_deref(), std::env::var("TERM").ok().as_deref())
+}
+
+pub fn detect_from(colorterm: Option<&str>, term: Option<&str>) -> ColorDepth {
+    if let Some(ct) = colorterm {
+        let ct = ct.to_ascii_lowercase();
+        if ct == "truecolor" || ct == "24bit" {
+            return ColorDepth::TrueColor;
+        }
+    }
+
+    match term {
+        Some(t) if t.ends_with("-direct") || t.contains("truecolor") => ColorDepth::TrueColor,
+        Some(t) if t.contains("256color") => ColorDepth::Ansi256,
+        _ => ColorDepth::Ansi16,
+    }
+}
+
+fn distance(a: &[u8; 3], b: &[u8; 3]) -> u32 {
+    // weighted so that green errors count more than blue ones, like the eye does.
+    let dr = a[0] as i32 - b[0] as i32;
+    let dg = a[1] as i32 - b[1] as i32;
+    let db = a[2] as i32 - b[2] as i32;
+    (2 * dr * dr + 4 * dg * dg + 3 * db * db) as u32
+}
+
+fn nearest_cube_level(v: u8) -> usize {
+    CUBE_LEVELS.iter().enumerate().min_by_key(|(_, l)| (**l as i32 - v as i32).abs()).unwrap().0
+}
+
+/// nearest xterm-256 color, only the 6x6x6 cube and the gray ramp are used
+/// since the first 16 entries depend on the terminal theme.
+pub fn nearest_256(c: &[u8; 3]) -> u8 {
+    let (r, g, b) = (nearest_cube_level(c[0]), nearest_cube_level(c[1]), nearest_cube_level(c[2]));
+    let cube_index = 16 + 36 * r + 6 * g + b;
+
+    let avg = (c[0] as u32 + c[1] as u32 + c[2] as u32) / 3;
+    let gray_step = if avg < 8 { 0 } else { ((avg - 8) / 10).min(23) };
+    let gray_index = 232 + gray_step as usize;
+
+    if distance(c, &palette_256(gray_index as u8)) < distance(c, &palette_256(cube_index as u8)) {
+        gray_index as u8
+    } else {
+        cube_index as u8
+    }
+}
+
+pub fn nearest_16(c: &[u8; 3]) -> u8 {
+    ANSI16.iter().enumerate().min_by_key(|(_, p)| distance(c, p)).unwrap().0 as u8
+}
+
+/// rgb value of an xterm-256 palette entry.
+pub fn palette_256(index: u8) -> [u8; 3] {
+    match index {
+        0..=15 => ANSI16[index as usize],
+        16..=231 => {
+            let i = (index - 16) as usize;
+            [CUBE_LEVELS[i / 36], CUBE_LEVELS[(i / 6) % 6], CUBE_LEVELS[i % 6]]
+        }
+        _ => {
+            let v = 8 + 10 * (index - 232);
+            [v, v, v]
+        }
+    }
+}
+
+/// closest color the terminal can actually show.
+pub fn quantize(depth: ColorDepth, c: &[u8; 3]) -> [u8; 3] {
+    match depth {
+        ColorDepth::TrueColor => *c,
+        ColorDepth::Ansi256 => palette_256(nearest_256(c)),
+        ColorDepth::Ansi16 => ANSI16[nearest_16(c) as usize],
+    }
+}
+
+pub fn to_term_color(depth: ColorDepth, c: &[u8; 3]) -> Color {
+    match depth {
+        ColorDepth::TrueColor => Color::Rgb { r: c[0], g: c[1], b: c[2] },
+        ColorDepth::Ansi256 => Color::AnsiValue(nearest_256(c)),
+        ColorDepth::Ansi16 => ANSI16_TERM[nearest_16(c) as usize],
+    }
+}
+
+// rough distance between neighbouring palette colors, scales the ordered dither offset.
+fn palette_step(depth: ColorDepth) -> f32 {
+    match depth {
+        ColorDepth::TrueColor => 0.,
+        ColorDepth::Ansi256 => 40.,
+        ColorDepth::Ansi16 => 128.,
+    }
+}
+
+/// dither the covered pixels of an rgba frame in place, snapping them to the palette of `depth`.
+pub fn dither(depth: ColorDepth, method: Dither, display: &mut [u8], coverage: &[bool], width: u32, height: u32) {
+    if depth == ColorDepth::TrueColor || method == Dither::None {
+        return;
+    }
+
+    let (w, h) = (width as usize, height as usize);
+    match method {
+        Dither::None => {}
+        Dither::Ordered => {
+            let step = palette_step(depth);
+            for y in 0..h {
+                for x in 0..w {
+                    let idx = y * w + x;
+                    if !coverage[idx] {
+                        continue;
+                    }
+                    let offset = (BAYER_4X4[y % 4][x % 4] as f32 + 0.5) / 16. - 0.5;
+                    let p = &mut display[idx * 4..idx * 4 + 3];
+                    let c = [0, 1, 2].map(|i| (p[i] as f32 + offset * step).clamp(0., 255.) as u8);
+                    p.copy_from_slice(&quantize(depth, &c));
+                }
+            }
+        }
+        Dither::FloydSteinberg => {
+            let mut err = vec![[0f32; 3]; w * h];
+            for y in 0..h {
+                for x in 0..w {
+                    let idx = y * w + x;
+                    if !coverage[idx] {
+                        continue;
+                    }
+                    let p = &mut display[idx * 4..idx * 4 + 3];
+                    let wanted = [0, 1, 2].map(|i| (p[i] as f32 + err[idx][i]).clamp(0., 255.));
+                    let got = quantize(depth, &wanted.map(|v| v as u8));
+                    p.copy_from_slice(&got);
+
+                    let e = [0, 1, 2].map(|i| wanted[i] - got[i] as f32);
+                    let mut spread = |dx: isize, dy: usize, factor: f32| {
+                        let nx = x as isize + dx;
+                        if nx < 0 || nx >= w as isize || y + dy >= h {
+                            return;
+                        }
+                        let n = (y + dy) * w + nx as usize;
+                        for i in 0..3 {
+                            err[n][i] += e[i] * factor;
+                        }
+                    };
+                    spread(1, 0, 7. / 16.);
+                    spread(-1, 1, 3. / 16.);
+                    spread(0, 1, 5. / 16.);
+                    spread(1, 1, 1. / 16.);
+                }
+            }
+        }
+    }
+}
@@ -1,27 +1,50 @@
 use std::error::Error;
 use std::io::{Stdout, stdout, Write};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 
 use crossterm;
 use crossterm::{event, execute, terminal};
-use crossterm::event::{Event};
+use crossterm::event::{Event, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent};
 use crossterm::terminal::{ClearType, disable_raw_mode, enable_raw_mode, EnterAlternateScreen};
 use game_loop::{GameLoop, Time, TimeTrait};
+use log::{debug, info};
+use winit::dpi::PhysicalSize;
 
+use crate::department::common::constant::{FPS, TUI_HEIGHT, TUI_WIDTH};
 use crate::department::common::self_type;
+use crate::department::control::ModelController;
 use crate::department::control::camera_controller::CameraController;
+use crate::department::control::keymap::{Action, Keymap};
+use crate::department::control::input_log;
+use crate::department::model::scene;
+use crate::wgpu::instance::InstanceRaw;
+use crate::department::control::terminal_mouse::{MouseAction, TerminalMouse};
+use crate::department::net::remote_input::{self, RemoteControl};
+use crate::department::model::object_loader::ObjectLoader;
 use crate::department::model::triangle_resources::TriangleResources;
 use crate::department::pipeline::rasterizer::RasterRunner;
 use crate::department::preview::homo_transformation::HomoTransform;
+use crate::department::preview::cell_encoder::CellMode;
+use crate::department::preview::palette::{self, ColorDepth, Dither};
+use crate::department::preview::front_buffer::FrontBuffer;
+use crate::department::preview::graphics::{self, GraphicsProtocol};
 use crate::department::preview::output_buffer::OutputBuffer;
 use crate::department::preview::vector::Vector3;
+use crate::department::net::router;
+use crate::department::pick::{self, Hit, PickScene};
+use crate::department::tui::model_browser::BrowserAction;
+use crate::department::tui::term::{Hud, HudStats};
+use crate::department::view::camera_trait::CameraTrait;
 use crate::department::types::msg::TransferMsg;
+use crate::util::ARG;
 
 
 pub mod term;
+pub mod model_browser;
 pub mod tui_with_window;
 pub mod tui_split;
+pub mod viewer;
 
 
 pub struct TuiApp {
@@ -30,10 +53,26 @@ pub struct TuiApp {
     theta: f32,
     camera_controller: CameraController,
     gpu: Option<self_type::StateImp>,
+    dimension: (u32, u32),
+    cell_mode: CellMode,
+    color_depth: ColorDepth,
+    dither: Dither,
+    front: FrontBuffer,
+    graphics: GraphicsProtocol,
+    hud: Hud,
+    mouse: TerminalMouse,
+    remote: RemoteControl,
+    // terminal size in cells.
+    cells: (u32, u32),
+    // file the displayed model was loaded from.
+    model_path: String,
+    // model matrix of every instance for the cpu rasterizer.
+    models: Vec<HomoTransform>,
+    // meshes of the cpu model for picking.
+    picker: Option<PickScene>,
+    res: Option<TriangleResources>,
 }
 
-static FPS: u32 = 30;
-
 static TIME_STEP: Duration = Duration::from_nanos(1_000_000_000 / FPS as u64);
 
 pub fn game_loop<G, U, R>(game: G, updates_per_second: u32, max_frame_time: f64, mut update: U, mut render: R) -> GameLoop<G, game_loop::Time, ()>
@@ -47,20 +86,101 @@ pub fn game_loop<G, U, R>(game: G, updates_per_second: u32, max_frame_time: f64,
     game_loop
 }
 
+/// current terminal size in cells, falls back to the default tui size if it can't be queried.
+pub fn terminal_dimension() -> (u32, u32) {
+    match terminal::size() {
+        Ok((w, h)) if w > 0 && h > 0 => (w as u32, h as u32),
+        _ => (TUI_WIDTH, TUI_HEIGHT),
+    }
+}
+
+/// whether the key toggles the hud overlay.
+pub fn is_hud_key(key: &KeyEvent, keymap: &Keymap) -> bool {
+    is_app_action(key, keymap, Action::ToggleHud)
+}
+
+/// whether the key opens the model browser.
+pub fn is_browser_key(key: &KeyEvent, keymap: &Keymap) -> bool {
+    is_app_action(key, keymap, Action::ToggleBrowser)
+}
+
+// actions handled by the app itself rather than the camera controller, without modifiers.
+fn is_app_action(key: &KeyEvent, keymap: &Keymap, action: Action) -> bool {
+    key.kind == KeyEventKind::Press && key.modifiers == KeyModifiers::NONE && keymap.tui_action(&key.code) == Some(action)
+}
+
+// the camera controller of a terminal app, its keys come from `keymap`.
+fn tui_controller(keymap: Keymap) -> CameraController {
+    let mut controller = CameraController::new(2.0, 0.2, true);
+    controller.set_keymap(keymap);
+    controller
+}
+
+/// stats of a frame that started at `started`.
+/// `triangles` is per instance, the hud shows what all instances add up to.
+pub fn hud_stats<T: CameraTrait>(started: Instant, triangles: Option<usize>, camera: &T, models: &ModelController, shader: &str, model_path: &str) -> HudStats {
+    let eye = camera.to_view_position();
+    let model = std::path::Path::new(model_path).file_name()
+        .map_or(model_path.to_string(), |n| n.to_string_lossy().into_owned());
+    let count = models.instances().len();
+    HudStats {
+        frame_time: started.elapsed(),
+        triangles: triangles.map(|t| t * count),
+        camera: [eye[0], eye[1], eye[2]],
+        model,
+        shader: shader.to_string(),
+        clients: router::client_count(),
+        instance: (models.selected(), count),
+    }
+}
+
+/// log a mouse pick, show it on the hud and select the instance that was hit.
+pub fn report_pick(hit: Option<Hit>, scene: Option<&PickScene>, controller: &mut CameraController, hud: &mut Hud) {
+    match (hit, scene) {
+        (Some(hit), Some(scene)) => {
+            info!("picked {}", scene.describe(&hit));
+            hud.set_picked(Some(format!("#{} tri {}", hit.instance + 1, hit.triangle)));
+            controller.process_select(hit.instance);
+        }
+        _ => hud.set_picked(Some("nothing".to_string())),
+    }
+}
+
+/// load a model for the cpu rasterizer, which can't draw models without a diffuse texture.
+pub fn load_cpu_model(path: &str) -> anyhow::Result<TriangleResources> {
+    let res = ObjectLoader::try_load_triangle_resources(path)?;
+    if res.image.is_none() {
+        anyhow::bail!("no diffuse texture found, the cpu renderer needs one");
+    }
+    Ok(res)
+}
+
 impl TuiApp {
-    pub fn new(raster: RasterRunner) -> Self {
-        Self { raster, stdout: stdout(), theta: 0., gpu: None, camera_controller: CameraController::new(2.0, 0.2, true) }
+    pub fn new(raster: RasterRunner, keymap: Keymap) -> Self {
+        Self { raster, stdout: stdout(), theta: 0., gpu: None, camera_controller: tui_controller(keymap),
+            dimension: (0, 0), cell_mode: ARG.cell,
+            color_depth: ARG.color.unwrap_or_else(palette::detect), dither: ARG.dither, front: FrontBuffer::new(),
+            graphics: graphics::protocol(), hud: Hud::new(), mouse: TerminalMouse::new(),
+            remote: RemoteControl::new(remote_input::receiver()), cells: (0, 0),
+            model_path: ARG.obj_path.clone(), models: Vec::new(), picker: None, res: None }
     }
 
     pub fn run(mut self, res: TriangleResources, state: Option<self_type::StateImp>) -> Result<(), Box<dyn Error>> {
+        self.camera_controller.set_input_log(input_log::from_args(true));
+        self.camera_controller.model_ctrl.set_instances(scene::from_args());
+        self.models = self.camera_controller.model_ctrl.instances().iter().map(|i| i.to_raw().model_transform()).collect();
         enable_raw_mode()?;
 
         execute!(self.stdout, crossterm::cursor::Hide);
         execute!(self.stdout, EnterAlternateScreen, event::EnableMouseCapture);
         execute!(self.stdout, crossterm::terminal::Clear(ClearType::All));
 
-        let dimension = (256, 79);
         self.gpu = state;
+        self.res = Some(res);
+        if self.gpu.is_none() {
+            self.picker = PickScene::load(&self.model_path).ok();
+        }
+        self.resize(terminal_dimension());
 
         let _lop = game_loop(self, FPS, 0.1, |g| {
             // update
@@ -76,15 +196,20 @@ impl TuiApp {
                                 Event::FocusGained => {}
                                 Event::FocusLost => {}
                                 Event::Key(k) => {
-                                    if !g.game.camera_controller.process_tui_keyboard(&k) {
+                                    if g.game.hud.browser.is_open() {
+                                        g.game.browser_key(&k);
+                                    } else if is_hud_key(&k, g.game.camera_controller.keymap()) {
+                                        g.game.toggle_hud();
+                                    } else if is_browser_key(&k, g.game.camera_controller.keymap()) {
+                                        g.game.toggle_browser();
+                                    } else if !g.game.camera_controller.process_tui_keyboard(&k) {
                                         should_exit = true;
                                     }
                                 }
-                                Event::Mouse(_) => {}
+                                Event::Mouse(m) => g.game.mouse_event(&m),
                                 Event::Paste(_) => {}
                                 Event::Resize(w, h) => {
-                                    println!("terminal window update to new size {} {}", w, h);
-                                    //self.draw((w as u32, h as u32), &res);
+                                    g.game.resize((w as u32, h as u32));
                                 }
                             }
                         } else {
@@ -102,7 +227,8 @@ impl TuiApp {
                 g.exit();
             }
             // execute!(g.game.stdout, terminal::Clear(ClearType::All));
-            g.game.draw((dimension.0 as u32, dimension.1 as u32), &res);
+            let dimension = g.game.dimension;
+            g.game.draw(dimension);
 
             let st = TIME_STEP.as_secs_f64() - Time::now().sub(&g.current_instant());
             if st > 0. {
@@ -112,44 +238,170 @@ impl TuiApp {
         Ok(())
     }
 
+    pub fn resize(&mut self, cells: (u32, u32)) {
+        if cells.0 == 0 || cells.1 == 0 {
+            return;
+        }
+        debug!("terminal window update to new size {:?}", cells);
+        let dim = self.graphics.render_dimension(self.cell_mode, cells);
+        self.dimension = dim;
+        self.cells = cells;
+        if let Some(ref mut gpu) = self.gpu {
+            gpu.resize(PhysicalSize::new(dim.0, dim.1), 1.0);
+        }
+        self.raster.resize(dim.0, dim.1);
+        let _ = self.raster.encoder_tx.enc.send(TransferMsg::Resize(dim.0, dim.1));
+        execute!(self.stdout, terminal::Clear(ClearType::All));
+        self.front.invalidate();
+        self.hud.invalidate();
+    }
+
+    pub fn toggle_hud(&mut self) {
+        self.hud.toggle();
+        self.redraw_all();
+    }
+
+    pub fn toggle_browser(&mut self) {
+        self.hud.toggle_browser();
+        self.redraw_all();
+    }
+
+    // clear the screen, the next frame and overlay are printed in full.
+    fn redraw_all(&mut self) {
+        execute!(self.stdout, crossterm::terminal::Clear(ClearType::All));
+        self.front.invalidate();
+        self.hud.invalidate();
+    }
+
+    fn load_model(&mut self, path: &str) -> anyhow::Result<()> {
+        match self.gpu {
+            Some(ref mut gpu) => gpu.load_model(path),
+            None => {
+                self.res = Some(load_cpu_model(path)?);
+                self.picker = PickScene::load(path).ok();
+                Ok(())
+            }
+        }
+    }
+
+    fn browser_key(&mut self, key: &KeyEvent) {
+        match self.hud.browser.handle_key(key, self.camera_controller.keymap()) {
+            BrowserAction::None => {}
+            BrowserAction::Close => self.redraw_all(),
+            BrowserAction::Load(path) => {
+                let path = path.to_string_lossy().into_owned();
+                let status = match self.load_model(&path) {
+                    Ok(()) => {
+                        self.model_path = path;
+                        format!("loaded {}", self.model_path)
+                    }
+                    Err(e) => format!("could not load {}: {}", path, e),
+                };
+                self.hud.browser.set_status(status);
+                self.redraw_all();
+            }
+        }
+    }
+
+    fn mouse_event(&mut self, event: &MouseEvent) {
+        if self.hud.browser.is_open() {
+            return;
+        }
+        if let MouseAction::Click { column, row } = self.mouse.handle(event, &mut self.camera_controller) {
+            let ndc = pick::ndc(self.graphics.cell_to_pixel(self.cell_mode, column, row), self.dimension);
+            let (hit, scene) = match self.gpu {
+                Some(ref gpu) => (gpu.pick(ndc, self.dimension, &self.camera_controller.model_ctrl), gpu.picker()),
+                None => {
+                    let models: Vec<_> = self.cpu_models().iter().map(pick::to_column_major).collect();
+                    let hit = self.picker.as_ref().and_then(|p| p.pick(&self.raster.camera().screen_ray(ndc), &models));
+                    (hit, self.picker.as_ref())
+                }
+            };
+            report_pick(hit, scene, &mut self.camera_controller, &mut self.hud);
+        }
+    }
+
+    // model matrices the cpu rasterizer draws the instances with.
+    fn cpu_models(&self) -> Vec<HomoTransform> {
+        let base = HomoTransform::rotation_matrix(&Vector3::from_xyz(0., 1., 0.), self.theta) * HomoTransform::scale((1.5, 1.5, 1.5));
+        self.models.iter().map(|m| &base * m).collect()
+    }
+
+    fn draw_hud(&mut self, stats: HudStats) {
+        self.hud.tick(stats);
+        if self.graphics != GraphicsProtocol::Cells {
+            // images are printed over the whole screen every frame.
+            self.hud.invalidate();
+        }
+        self.hud.draw(self.cells, self.camera_controller.keymap());
+    }
+
     pub fn update(&mut self, last_frame_time: f64) {
+        self.remote.update(&mut self.camera_controller);
         if let Some(ref mut gpu) = self.gpu {
             gpu.update_outside(&mut self.camera_controller, Duration::from_secs_f64(last_frame_time));
+        } else {
+            let dt = self.camera_controller.update_camera(self.raster.camera_mut(), Duration::from_secs_f64(last_frame_time));
+            self.models = self.camera_controller.model_ctrl.update_model(dt).iter().map(InstanceRaw::model_transform).collect();
         }
     }
 
 
-    pub fn draw(&mut self, dim: (u32, u32), res: &TriangleResources) {
+    pub fn draw(&mut self, dim: (u32, u32)) {
+        let now = Instant::now();
+        self.front.set_overlay(self.hud.areas(self.cells));
         if let Some(ref mut gpu) = self.gpu {
             let mut out_buf = OutputBuffer::new(dim.0 as u32, dim.1 as u32, true);
             out_buf.stdout = Some(&mut self.stdout);
+            out_buf.cell_mode = self.cell_mode;
+            out_buf.color_depth = self.color_depth;
+            out_buf.dither = self.dither;
+            out_buf.front = Some(&mut self.front);
+            out_buf.graphics = self.graphics;
             let out = gpu.render(false);
-            out_buf.display.copy_from_slice(&out.0);
+            out_buf.load_frame(&out.0);
             //self.raster.encoder_tx.enc.send(TransferMsg::RenderPc(out)).unwrap();
             out_buf.queue_to_stdout();
             drop(out_buf);
             self.stdout.flush().unwrap();
+            let stats = hud_stats(now, Some(gpu.triangle_count()), gpu.camera(), &self.camera_controller.model_ctrl, "wgpu", &self.model_path);
+            self.draw_hud(stats);
             return;
         }
 
 
-        let now = std::time::Instant::now();
+        let models = self.cpu_models();
+        let res = match self.res {
+            Some(ref res) => res,
+            None => return,
+        };
         let mut out_buf = OutputBuffer::new(dim.0 as u32, dim.1 as u32, true);
         out_buf.stdout = Some(&mut self.stdout);
-        self.raster.set_model(HomoTransform::rotation_matrix(&Vector3::from_xyz(0., 1., 0.), self.theta) * HomoTransform::scale((1.5, 1.5, 1.5)));
-        self.raster.render_frame(res, &mut out_buf);
+        out_buf.cell_mode = self.cell_mode;
+        out_buf.color_depth = self.color_depth;
+        out_buf.dither = self.dither;
+        out_buf.front = Some(&mut self.front);
+        out_buf.graphics = self.graphics;
+        self.raster.render_instances(res, &mut out_buf, &models);
         out_buf.queue_to_stdout();
         let data = out_buf.display.clone();
         drop(out_buf);
         self.stdout.flush().unwrap();
 
-        println!("this frame cost {} milli sec", now.elapsed().as_millis());
-        self.raster.encoder_tx.enc.send(TransferMsg::RenderedData(data)).unwrap();
+        let stats = hud_stats(now, Some(res.model.mesh.indices.len() / 3), self.raster.camera(), &self.camera_controller.model_ctrl, self.raster.shader_name(), &self.model_path);
+        self.draw_hud(stats);
+        // nobody watches the stream, skip encoding.
+        if router::streaming() {
+            self.raster.encoder_tx.enc.send(TransferMsg::RenderedData(data)).unwrap();
+        }
     }
 }
 
 impl Drop for TuiApp {
     fn drop(&mut self) {
+        if self.graphics == GraphicsProtocol::Kitty {
+            graphics::delete_kitty_images(&mut self.stdout);
+        }
         execute!(self.stdout, terminal::Clear(ClearType::All));
         execute!(self.stdout, terminal::LeaveAlternateScreen, event::DisableMouseCapture);
         execute!(self.stdout, crossterm::cursor::Show);
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, StatefulWidget, Widget};
+
+use crate::department::control::keymap::{Action, Keymap};
+use super::term::STATS_HEIGHT;
+
+/// file extensions the loaders understand.
+pub const MODEL_EXTENSIONS: &[&str] = &["obj"];
+
+/// what the browser found out about a model file without uploading it anywhere.
+#[derive(Clone, Debug)]
+pub struct ModelInfo {
+    pub meshes: usize,
+    pub triangles: usize,
+    pub materials: Vec<String>,
+    /// diffuse texture paths and whether they exist on disk.
+    pub textures: Vec<(String, bool)>,
+}
+
+impl ModelInfo {
+    /// the cpu rasterizer samples the diffuse texture of every triangle.
+    pub fn has_all_textures(&self) -> bool {
+        !self.textures.is_empty() && self.textures.iter().all(|(_, found)| *found)
+    }
+}
+
+/// every model file under `dir`, sorted by path.
+pub fn scan(dir: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| MODEL_EXTENSIONS.iter().any(|m| m.eq_ignore_ascii_case(e))) {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    found
+}
+
+pub fn inspect(path: &Path) -> Result<ModelInfo, String> {
+    let (models, materials) = tobj::load_obj(path, &tobj::LoadOptions { triangulate: true, ..Default::default() })
+        .map_err(|e| e.to_string())?;
+    let materials = materials.unwrap_or_default();
+    let dir = path.parent().unwrap_or(Path::new("."));
+
+    Ok(ModelInfo {
+        meshes: models.len(),
+        triangles: models.iter().map(|m| m.mesh.indices.len() / 3).sum(),
+        materials: materials.iter().map(|m| m.name.clone()).collect(),
+        textures: materials.iter()
+            .filter(|m| !m.diffuse_texture.is_empty())
+            .map(|m| (m.diffuse_texture.clone(), dir.join(&m.diffuse_texture).is_file()))
+            .collect(),
+    })
+}
+
+pub enum BrowserAction {
+    None,
+    Close,
+    Load(PathBuf),
+}
+
+/// terminal panel listing the model files of a directory.
+pub struct ModelBrowser {
+    open: bool,
+    dir: PathBuf,
+    entries: Vec<PathBuf>,
+    selected: usize,
+    infos: HashMap<PathBuf, Result<ModelInfo, String>>,
+    status: Option<String>,
+}
+
+impl ModelBrowser {
+    pub fn new(dir: &str) -> Self {
+        Self { open: false, dir: PathBuf::from(dir), entries: Vec::new(), selected: 0, infos: HashMap::new(), status: None }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// open the panel with a fresh listing, or close it.
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        if self.open {
+            self.rescan();
+        }
+    }
+
+    pub fn rescan(&mut self) {
+        self.entries = scan(&self.dir);
+        self.infos.clear();
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        self.inspect_selected();
+    }
+
+    /// message shown under the listing, like the result of the last load.
+    pub fn set_status(&mut self, status: String) {
+        self.status = Some(status);
+    }
+
+    pub fn info(&self, path: &Path) -> Option<&ModelInfo> {
+        self.infos.get(path).and_then(|i| i.as_ref().ok())
+    }
+
+    fn inspect_selected(&mut self) {
+        if let Some(path) = self.entries.get(self.selected) {
+            if !self.infos.contains_key(path) {
+                self.infos.insert(path.clone(), inspect(path));
+            }
+        }
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent, keymap: &Keymap) -> BrowserAction {
+        if key.kind != KeyEventKind::Press {
+            return BrowserAction::None;
+        }
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected + 1 < self.entries.len() {
+                    self.selected += 1;
+                }
+            }
+            KeyCode::Char('r') => self.rescan(),
+            KeyCode::Enter => {
+                if let Some(path) = self.entries.get(self.selected) {
+                    return BrowserAction::Load(path.clone());
+                }
+            }
+            KeyCode::Esc => {
+                self.open = false;
+                return BrowserAction::Close;
+            }
+            code if keymap.tui_action(&code) == Some(Action::ToggleBrowser) => {
+                self.open = false;
+                return BrowserAction::Close;
+            }
+            _ => {}
+        }
+        self.inspect_selected();
+        BrowserAction::None
+    }
+
+    /// left column under the stats box.
+    pub fn area(&self, screen: Rect) -> Rect {
+        let top = STATS_HEIGHT.min(screen.height);
+        let width = 64.min(screen.width / 2).max(32.min(screen.width));
+        Rect::new(0, top, width, screen.height - top)
+    }
+
+    pub fn render(&self, buf: &mut Buffer, screen: Rect) {
+        let area = self.area(screen);
+        if area.area() == 0 {
+            return;
+        }
+        Clear.render(area, buf);
+        let block = Block::default().borders(Borders::ALL).title(format!(" models in {} ", self.dir.display()));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let details_height = 8.min(inner.height / 2);
+        let list_area = Rect::new(inner.x, inner.y, inner.width, inner.height - details_height);
+        let details_area = Rect::new(inner.x, inner.y + list_area.height, inner.width, details_height);
+
+        let items: Vec<ListItem> = self.entries.iter()
+            .map(|p| ListItem::new(p.strip_prefix(&self.dir).unwrap_or(p).display().to_string()))
+            .collect();
+        let mut state = ListState::default();
+        state.select(if self.entries.is_empty() { None } else { Some(self.selected) });
+        StatefulWidget::render(
+            List::new(items).highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow)).highlight_symbol("> "),
+            list_area, buf, &mut state,
+        );
+
+        let label = Style::default().fg(Color::DarkGray);
+        let mut lines = Vec::new();
+        match self.entries.get(self.selected).and_then(|p| self.infos.get(p)) {
+            Some(Ok(info)) => {
+                lines.push(Spans::from(vec![Span::styled("meshes     ", label), Span::raw(info.meshes.to_string())]));
+                lines.push(Spans::from(vec![Span::styled("triangles  ", label), Span::raw(info.triangles.to_string())]));
+                lines.push(Spans::from(vec![Span::styled("materials  ", label), Span::raw(info.materials.join(", "))]));
+                for (texture, found) in &info.textures {
+                    let (mark, color) = if *found { ("found  ", Color::Green) } else { ("missing", Color::Red) };
+                    lines.push(Spans::from(vec![
+                        Span::styled("texture    ", label),
+                        Span::styled(mark, Style::default().fg(color)),
+                        Span::raw(format!(" {}", texture)),
+                    ]));
+                }
+            }
+            Some(Err(e)) => lines.push(Spans::from(Span::styled(e.clone(), Style::default().fg(Color::Red)))),
+            None => lines.push(Spans::from(Span::styled("no model files found", label))),
+        }
+        if let Some(status) = &self.status {
+            lines.push(Spans::from(Span::styled(status.clone(), Style::default().add_modifier(Modifier::BOLD))));
+        }
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::TOP).title(" enter load, r rescan, esc close "))
+            .render(details_area, buf);
+    }
+}
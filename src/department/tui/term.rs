@@ -1,42 +1,214 @@
-use std::sync::mpsc::Receiver;
-use std::time::Duration;
-
-use tokio;
-use tokio::runtime::Runtime;
-use tui::Frame;
-use tui::backend::Backend;
-
-pub struct TermRenderer
-{
-    keyboard_rx: Receiver<String>,
-    rt: Runtime
+use std::io::{stdout, Stdout};
+use std::time::{Duration, Instant};
+
+use tui::backend::{Backend, CrosstermBackend};
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+
+use crate::department::control::keymap::{Action, Keymap};
+use crate::util::ARG;
+use super::model_browser::ModelBrowser;
+
+/// what the hud shows, filled in by the app every frame.
+#[derive(Clone, Debug, Default)]
+pub struct HudStats {
+    /// time spent rendering and printing the last frame.
+    pub frame_time: Duration,
+    /// None when the frame comes from the gpu and the mesh isn't known here.
+    pub triangles: Option<usize>,
+    pub camera: [f32; 3],
+    pub model: String,
+    pub shader: String,
+    pub clients: usize,
+    /// selected instance and how many there are.
+    pub instance: (usize, usize),
 }
 
+const STATS_WIDTH: u16 = 34;
+/// rows of the stats box, the model browser goes under it.
+pub const STATS_HEIGHT: u16 = 10;
+const HELP_WIDTH: u16 = 42;
+
+// weight of the newest frame in the averaged fps.
+const FPS_SMOOTHING: f32 = 0.1;
+
+/// overlay drawn with tui widgets on top of the rendered frame.
+pub struct Hud {
+    visible: bool,
+    backend: CrosstermBackend<Stdout>,
+    // what the overlay put on screen last time, None when it has to be drawn in full.
+    previous: Option<Buffer>,
+    last_tick: Option<Instant>,
+    fps: f32,
+    stats: HudStats,
+    // short description of the last mouse pick.
+    picked: Option<String>,
+    pub browser: ModelBrowser,
+}
+
+impl Hud {
+    pub fn new() -> Self {
+        Self {
+            visible: true,
+            backend: CrosstermBackend::new(stdout()),
+            previous: None,
+            last_tick: None,
+            fps: 0.,
+            stats: HudStats::default(),
+            picked: None,
+            browser: ModelBrowser::new(&ARG.model_dir),
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
 
-impl TermRenderer where {
-    pub fn new_with(rx: Receiver<String>) ->Self {
-        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
-        TermRenderer {
-            keyboard_rx: rx,
-            rt: runtime
+    /// the caller has to clear the screen afterwards so the frame under the overlay is printed again.
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.invalidate();
+    }
+
+    /// same as `toggle` but for the model browser panel.
+    pub fn toggle_browser(&mut self) {
+        self.browser.toggle();
+        self.invalidate();
+    }
+
+    /// forget what is on screen, the next draw prints the whole overlay.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+
+    /// record a finished frame.
+    pub fn tick(&mut self, stats: HudStats) {
+        let now = Instant::now();
+        if let Some(last) = self.last_tick {
+            let elapsed = now.duration_since(last).as_secs_f32();
+            if elapsed > 0. {
+                let fps = 1. / elapsed;
+                self.fps = if self.fps == 0. { fps } else { self.fps + (fps - self.fps) * FPS_SMOOTHING };
+            }
         }
+        self.last_tick = Some(now);
+        self.stats = stats;
     }
 
-    pub fn run(self) {
-        self.rt.block_on(async {
-            let _ = &self.update();
-        });
+    pub fn set_picked(&mut self, picked: Option<String>) {
+        self.picked = picked;
     }
 
-    async fn update(&self) {
-        loop {
-            if let Ok(_s) = self.keyboard_rx.recv_timeout(Duration::from_secs(1)) {
-                
+    /// cells covered by the overlay on a terminal of `cells` columns and rows, as (x, y, width, height).
+    pub fn areas(&self, cells: (u32, u32)) -> Vec<(u32, u32, u32, u32)> {
+        self.panels(screen_rect(cells)).iter()
+            .filter(|r| r.area() > 0)
+            .map(|r| (r.x as u32, r.y as u32, r.width as u32, r.height as u32))
+            .collect()
+    }
+
+    fn panels(&self, screen: Rect) -> Vec<Rect> {
+        let mut panels = Vec::new();
+        if self.visible {
+            let (stats, help) = layout(screen);
+            panels.push(stats);
+            panels.push(help);
+        }
+        if self.browser.is_open() {
+            panels.push(self.browser.area(screen));
+        }
+        panels
+    }
+
+    /// print the overlay, only the cells that changed since the last draw are written.
+    pub fn draw(&mut self, cells: (u32, u32), keymap: &Keymap) {
+        let screen = screen_rect(cells);
+        let panels = self.panels(screen);
+        if panels.is_empty() {
+            return;
+        }
+        let mut buf = Buffer::empty(screen);
+        if self.visible {
+            ui(&mut buf, screen, self.fps, &self.stats, self.picked.as_deref(), keymap);
+        }
+        if self.browser.is_open() {
+            self.browser.render(&mut buf, screen);
+        }
+
+        let covered = |x: u16, y: u16| panels.iter()
+            .any(|r| x >= r.x && x < r.x + r.width && y >= r.y && y < r.y + r.height);
+
+        let result = match self.previous.as_ref().filter(|p| p.area == screen) {
+            Some(previous) => self.backend.draw(previous.diff(&buf).into_iter()),
+            None => {
+                let content = buf.content.iter().enumerate().filter_map(|(i, c)| {
+                    let (x, y) = buf.pos_of(i);
+                    if covered(x, y) { Some((x, y, c)) } else { None }
+                });
+                self.backend.draw(content)
             }
+        };
+        if result.is_ok() {
+            let _ = Backend::flush(&mut self.backend);
+            self.previous = Some(buf);
         }
     }
 }
 
-fn ui<B:Backend>(_f: &mut Frame<B>, _render: &TermRenderer) {
+fn screen_rect(cells: (u32, u32)) -> Rect {
+    Rect::new(0, 0, cells.0.min(u16::MAX as u32) as u16, cells.1.min(u16::MAX as u32) as u16)
+}
+
+// stats box in the top left corner, key help in the top right one.
+fn layout(screen: Rect) -> (Rect, Rect) {
+    let stats = Rect::new(0, 0, STATS_WIDTH, STATS_HEIGHT).intersection(screen);
+    let help_width = HELP_WIDTH.min(screen.width.saturating_sub(STATS_WIDTH + 1));
+    let help = Rect::new(screen.width - help_width, 0, help_width, Action::ALL.len() as u16 + 3).intersection(screen);
+    (stats, help)
+}
 
-}
\ No newline at end of file
+fn ui(buf: &mut Buffer, screen: Rect, fps: f32, stats: &HudStats, picked: Option<&str>, keymap: &Keymap) {
+    let (stats_area, help_area) = layout(screen);
+    let label = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+    let line = |name: &'static str, text: String| Spans::from(vec![
+        Span::styled(format!("{:<10}", name), label),
+        Span::styled(text, value),
+    ]);
+
+    let lines = vec![
+        line("fps", format!("{:.1}", fps)),
+        line("frame", format!("{:.2} ms", stats.frame_time.as_secs_f64() * 1000.)),
+        line("triangles", stats.triangles.map_or(String::from("-"), |t| t.to_string())),
+        line("camera", format!("{:.2} {:.2} {:.2}", stats.camera[0], stats.camera[1], stats.camera[2])),
+        line("model", format!("{} / {}", stats.model, stats.shader)),
+        line("instance", format!("{} of {}", stats.instance.0 + 1, stats.instance.1)),
+        line("picked", picked.unwrap_or("-").to_string()),
+        line("clients", stats.clients.to_string()),
+    ];
+    Clear.render(stats_area, buf);
+    Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(" dognut "))
+        .render(stats_area, buf);
+
+    if help_area.area() == 0 {
+        return;
+    }
+    let mut help: Vec<Spans> = Action::ALL.iter()
+        .map(|action| Spans::from(vec![
+            Span::styled(format!("{:<18}", keymap.keys(*action).join(" / ")), Style::default().fg(Color::Yellow)),
+            Span::raw(action.description()),
+        ]))
+        .collect();
+    help.push(Spans::from(vec![
+        Span::styled(format!("{:<18}", "ctrl + move keys"), Style::default().fg(Color::Yellow)),
+        Span::raw("move the model"),
+    ]));
+    Clear.render(help_area, buf);
+    Paragraph::new(help)
+        .block(Block::default().borders(Borders::ALL).title(" keys "))
+        .render(help_area, buf);
+}
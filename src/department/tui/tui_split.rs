@@ -1,53 +1,95 @@
 use std::io::{Stdout, stdout, Write};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use crossterm::{event, execute};
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent, MouseEvent};
 use crossterm::terminal::{ClearType};
 use game_loop::TimeTrait;
+use log::debug;
+use winit::dpi::PhysicalSize;
+use crate::department::common::constant::FPS;
+use crate::department::control::input_log;
+use crate::department::control::keymap::Keymap;
+use crate::department::model::scene;
+use crate::department::control::terminal_mouse::{MouseAction, TerminalMouse};
+use crate::department::net::remote_input::{self, RemoteControl};
+use crate::department::net::router;
+use crate::department::preview::cell_encoder::CellMode;
+use crate::department::preview::palette::{self, ColorDepth, Dither};
+use crate::department::preview::front_buffer::FrontBuffer;
+use crate::department::preview::graphics::{self, GraphicsProtocol};
 use crate::department::preview::output_buffer::OutputBuffer;
 use crate::department::types::msg::TransferMsg;
+use crate::util::ARG;
 use crate::department::types::multi_sender::MultiSender;
-use super::game_loop;
+use crate::department::pick;
+use super::{game_loop, hud_stats, is_browser_key, is_hud_key, report_pick, terminal_dimension, tui_controller};
+use super::model_browser::BrowserAction;
+use super::term::{Hud, HudStats};
 
 
-const TUI_WIDE_WIDTH: u32 = 256 * 2;
-const TUI_SPLIT_WIDTH: u32 = 256;
-const TUI_SPLIT_HEIGHT: u32 = 79;
-
 pub struct TuiSplitApp {
     stdout: Stdout,
     theta: f32,
     camera_controller: crate::department::control::camera_controller::CameraController,
     gpu: Option<crate::department::common::self_type::StateImp>,
     ms: MultiSender<TransferMsg>,
+    // size of one half of the split screen, the gpu renders twice this width.
+    dimension: (u32, u32),
+    cell_mode: CellMode,
+    color_depth: ColorDepth,
+    dither: Dither,
+    front: FrontBuffer,
+    graphics: GraphicsProtocol,
+    hud: Hud,
+    mouse: TerminalMouse,
+    remote: RemoteControl,
+    // terminal size in cells.
+    cells: (u32, u32),
+    // file the displayed model was loaded from.
+    model_path: String,
 }
 
 
-static FPS: u32 = 30;
 static TIME_STEP: Duration = Duration::from_nanos(1_000_000_000 / FPS as u64);
 
 
 impl TuiSplitApp {
-    pub fn new(ms: MultiSender<TransferMsg>) -> Self {
+    pub fn new(ms: MultiSender<TransferMsg>, keymap: Keymap) -> Self {
         Self {
             stdout: stdout(),
             theta: 0.,
             gpu: None,
-            camera_controller: crate::department::control::camera_controller::CameraController::new(2.0, 0.2, true),
+            camera_controller: tui_controller(keymap),
             ms,
+            dimension: (0, 0),
+            cell_mode: ARG.cell,
+            color_depth: ARG.color.unwrap_or_else(palette::detect),
+            dither: ARG.dither,
+            front: FrontBuffer::new(),
+            graphics: graphics::protocol(),
+            hud: Hud::new(),
+            mouse: TerminalMouse::new(),
+            remote: RemoteControl::new(remote_input::receiver()),
+            cells: (0, 0),
+            model_path: ARG.obj_path.clone(),
         }
     }
 
     pub async fn run(mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.camera_controller.set_input_log(input_log::from_args(true));
+        self.camera_controller.model_ctrl.set_instances(scene::from_args());
         crossterm::terminal::enable_raw_mode()?;
 
         execute!(self.stdout, crossterm::cursor::Hide);
         execute!(self.stdout, crossterm::terminal::EnterAlternateScreen, event::EnableMouseCapture);
         execute!(self.stdout, crossterm::terminal::Clear(ClearType::All));
 
-        let cam = crate::department::common::self_type::camera_instance(TUI_SPLIT_WIDTH, TUI_SPLIT_HEIGHT);
-        let gpu = crate::wgpu::wgpu_helper::State::new(winit::dpi::LogicalSize { width: TUI_WIDE_WIDTH, height: TUI_SPLIT_HEIGHT }, cam).await;
+        let cells = terminal_dimension();
+        let dim = self.graphics.render_dimension(self.cell_mode, cells);
+        let cam = crate::department::common::self_type::camera_instance(dim.0, dim.1);
+        let gpu = crate::wgpu::wgpu_helper::State::new(winit::dpi::LogicalSize { width: dim.0 * 2, height: dim.1 }, cam).await;
         self.gpu = Some(gpu);
+        self.resize(cells);
 
 
         let _lop = game_loop(self, FPS, 0.1, |g| {
@@ -64,15 +106,20 @@ impl TuiSplitApp {
                                 Event::FocusGained => {}
                                 Event::FocusLost => {}
                                 Event::Key(k) => {
-                                    if !g.game.camera_controller.process_tui_keyboard(&k) {
+                                    if g.game.hud.browser.is_open() {
+                                        g.game.browser_key(&k);
+                                    } else if is_hud_key(&k, g.game.camera_controller.keymap()) {
+                                        g.game.toggle_hud();
+                                    } else if is_browser_key(&k, g.game.camera_controller.keymap()) {
+                                        g.game.toggle_browser();
+                                    } else if !g.game.camera_controller.process_tui_keyboard(&k) {
                                         should_exit = true;
                                     }
                                 }
-                                Event::Mouse(_) => {}
+                                Event::Mouse(m) => g.game.mouse_event(&m),
                                 Event::Paste(_) => {}
                                 Event::Resize(w, h) => {
-                                    println!("terminal window update to new size {} {}", w, h);
-                                    //self.draw((w as u32, h as u32), &res);
+                                    g.game.resize((w as u32, h as u32));
                                 }
                             }
                         } else {
@@ -92,7 +139,8 @@ impl TuiSplitApp {
                 g.exit();
             }
             // execute!(g.game.stdout, terminal::Clear(ClearType::All));
-            g.game.draw((TUI_SPLIT_WIDTH, TUI_SPLIT_HEIGHT));
+            let dimension = g.game.dimension;
+            g.game.draw(dimension);
 
             let st = TIME_STEP.as_secs_f64() - game_loop::Time::now().sub(&g.current_instant());
             if st > 0. {
@@ -104,24 +152,121 @@ impl TuiSplitApp {
         Ok(())
     }
 
+    pub fn resize(&mut self, cells: (u32, u32)) {
+        if cells.0 == 0 || cells.1 == 0 {
+            return;
+        }
+        debug!("terminal window update to new size {:?}", cells);
+        let dim = self.graphics.render_dimension(self.cell_mode, cells);
+        self.dimension = dim;
+        self.cells = cells;
+        if let Some(ref mut gpu) = self.gpu {
+            gpu.resize(PhysicalSize::new(dim.0 * 2, dim.1), 1.0);
+            gpu.update_projection(dim.0, dim.1);
+        }
+        let _ = self.ms.enc.send(TransferMsg::Resize(dim.0, dim.1));
+        execute!(self.stdout, crossterm::terminal::Clear(ClearType::All));
+        self.front.invalidate();
+        self.hud.invalidate();
+    }
+
+    pub fn toggle_hud(&mut self) {
+        self.hud.toggle();
+        self.redraw_all();
+    }
+
+    pub fn toggle_browser(&mut self) {
+        self.hud.toggle_browser();
+        self.redraw_all();
+    }
+
+    // clear the screen, the next frame and overlay are printed in full.
+    fn redraw_all(&mut self) {
+        execute!(self.stdout, crossterm::terminal::Clear(ClearType::All));
+        self.front.invalidate();
+        self.hud.invalidate();
+    }
+
+    fn load_model(&mut self, path: &str) -> anyhow::Result<()> {
+        match self.gpu {
+            Some(ref mut gpu) => gpu.load_model(path),
+            None => anyhow::bail!("the gpu renderer isn't ready yet"),
+        }
+    }
+
+    fn browser_key(&mut self, key: &KeyEvent) {
+        match self.hud.browser.handle_key(key, self.camera_controller.keymap()) {
+            BrowserAction::None => {}
+            BrowserAction::Close => self.redraw_all(),
+            BrowserAction::Load(path) => {
+                let path = path.to_string_lossy().into_owned();
+                let status = match self.load_model(&path) {
+                    Ok(()) => {
+                        self.model_path = path;
+                        format!("loaded {}", self.model_path)
+                    }
+                    Err(e) => format!("could not load {}: {}", path, e),
+                };
+                self.hud.browser.set_status(status);
+                self.redraw_all();
+            }
+        }
+    }
+
+    fn mouse_event(&mut self, event: &MouseEvent) {
+        if self.hud.browser.is_open() {
+            return;
+        }
+        if let MouseAction::Click { column, row } = self.mouse.handle(event, &mut self.camera_controller) {
+            // the terminal shows the right half of a frame twice as wide.
+            let (x, y) = self.graphics.cell_to_pixel(self.cell_mode, column, row);
+            let ndc = pick::ndc((x + self.dimension.0, y), (self.dimension.0 * 2, self.dimension.1));
+            if let Some(ref gpu) = self.gpu {
+                let hit = gpu.pick(ndc, self.dimension, &self.camera_controller.model_ctrl);
+                report_pick(hit, gpu.picker(), &mut self.camera_controller, &mut self.hud);
+            }
+        }
+    }
+
+    fn draw_hud(&mut self, stats: HudStats) {
+        self.hud.tick(stats);
+        if self.graphics != GraphicsProtocol::Cells {
+            // images are printed over the whole screen every frame.
+            self.hud.invalidate();
+        }
+        self.hud.draw(self.cells, self.camera_controller.keymap());
+    }
+
     pub fn update(&mut self, last_frame_time: f64) {
+        self.remote.update(&mut self.camera_controller);
         if let Some(ref mut gpu) = self.gpu {
             gpu.update_outside(&mut self.camera_controller, Duration::from_secs_f64(last_frame_time));
         }
     }
 
     pub fn draw(&mut self, dim: (u32, u32)) {
+        let now = Instant::now();
+        self.front.set_overlay(self.hud.areas(self.cells));
         if let Some(ref mut gpu) = self.gpu {
             let mut out_buf = OutputBuffer::new(dim.0 as u32, dim.1 as u32, false);
             out_buf.stdout = Some(&mut self.stdout);
+            out_buf.cell_mode = self.cell_mode;
+            out_buf.color_depth = self.color_depth;
+            out_buf.dither = self.dither;
+            out_buf.front = Some(&mut self.front);
+            out_buf.graphics = self.graphics;
             let out = gpu.render(false);
-            let (this, that) = crate::util::split_screen(&out.0, (TUI_WIDE_WIDTH, TUI_SPLIT_HEIGHT), (TUI_SPLIT_WIDTH, TUI_SPLIT_HEIGHT));
-            out_buf.display.copy_from_slice(&that);
-            self.ms.enc.try_send(TransferMsg::RenderedData(this)).unwrap();
+            let (this, that) = crate::util::split_screen(&out.0, (dim.0 * 2, dim.1), dim);
+            out_buf.load_frame(&that);
+            if router::streaming() {
+                self.ms.enc.try_send(TransferMsg::RenderedData(this)).unwrap();
+            }
             out_buf.queue_to_stdout();
             drop(out);
             drop(out_buf);
             self.stdout.flush().unwrap();
+            let stats = hud_stats(now, Some(gpu.triangle_count()), gpu.camera(), &self.camera_controller.model_ctrl, "wgpu", &self.model_path);
+            self.draw_hud(stats);
             return;
         }
     }
@@ -129,6 +274,9 @@ impl TuiSplitApp {
 
 impl Drop for TuiSplitApp {
     fn drop(&mut self) {
+        if self.graphics == GraphicsProtocol::Kitty {
+            graphics::delete_kitty_images(&mut self.stdout);
+        }
         execute!(self.stdout, crossterm::terminal::Clear(ClearType::All));
         execute!(self.stdout, crossterm::terminal::LeaveAlternateScreen, event::DisableMouseCapture);
         execute!(self.stdout, crossterm::cursor::Show);
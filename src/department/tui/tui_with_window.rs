@@ -1,19 +1,37 @@
 use std::error::Error;
 use std::io::{Stdout, stdout, Write};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use crossterm::{event, execute};
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent, MouseEvent};
 use crossterm::terminal::{ClearType,disable_raw_mode, enable_raw_mode, EnterAlternateScreen};
 use game_loop::TimeTrait;
+use log::debug;
+use crate::department::common::constant::FPS;
 use crate::department::common::self_type;
 use crate::department::control::camera_controller::CameraController;
+use crate::department::control::keymap::Keymap;
+use crate::department::control::input_log;
+use crate::department::model::scene;
+use crate::wgpu::instance::InstanceRaw;
+use crate::department::control::terminal_mouse::{MouseAction, TerminalMouse};
+use crate::department::net::remote_input::{self, RemoteControl};
+use crate::department::net::router;
 use crate::department::model::triangle_resources::TriangleResources;
 use crate::department::pipeline::rasterizer::RasterRunner;
 use crate::department::preview::homo_transformation::HomoTransform;
+use crate::department::preview::cell_encoder::CellMode;
+use crate::department::preview::palette::{self, ColorDepth, Dither};
+use crate::department::preview::front_buffer::FrontBuffer;
+use crate::department::preview::graphics::{self, GraphicsProtocol};
 use crate::department::preview::output_buffer::OutputBuffer;
 use crate::department::preview::vector::Vector3;
-use crate::department::tui::game_loop;
+use crate::department::pick::{self, PickScene};
+use crate::department::tui::{game_loop, hud_stats, is_browser_key, is_hud_key, load_cpu_model, report_pick, terminal_dimension, tui_controller};
+use crate::department::view::camera_trait::CameraTrait;
+use crate::department::tui::model_browser::BrowserAction;
+use crate::department::tui::term::{Hud, HudStats};
 use crate::department::types::msg::TransferMsg;
+use crate::util::ARG;
 use crate::department::types::multi_sender::MultiSender;
 
 pub struct TuiWinApp {
@@ -26,33 +44,69 @@ pub struct TuiWinApp {
     time_step: Duration,
     res: TriangleResources,
     ms: MultiSender<TransferMsg>,
+    dimension: (u32, u32),
+    cell_mode: CellMode,
+    color_depth: ColorDepth,
+    dither: Dither,
+    front: FrontBuffer,
+    graphics: GraphicsProtocol,
+    hud: Hud,
+    mouse: TerminalMouse,
+    remote: RemoteControl,
+    // terminal size in cells.
+    cells: (u32, u32),
+    // file the displayed model was loaded from.
+    model_path: String,
+    // model matrix of every instance for the cpu rasterizer.
+    models: Vec<HomoTransform>,
+    // meshes of the cpu model for picking.
+    picker: Option<PickScene>,
 }
 
 
 impl TuiWinApp {
-    pub fn new(raster: RasterRunner, res: TriangleResources, ms: MultiSender<TransferMsg>) -> Self {
+    pub fn new(raster: RasterRunner, res: TriangleResources, ms: MultiSender<TransferMsg>, keymap: Keymap) -> Self {
         Self {
             raster,
             stdout: stdout(),
             theta: 0.,
             gpu: None,
-            camera_controller: CameraController::new(2.0, 0.2, true),
-            fps: 30,
+            camera_controller: tui_controller(keymap),
+            fps: FPS,
             time_step: Duration::from_nanos(1_000_000_000 / 30 as u64),
             res,
             ms,
+            dimension: (0, 0),
+            cell_mode: ARG.cell,
+            color_depth: ARG.color.unwrap_or_else(palette::detect),
+            dither: ARG.dither,
+            front: FrontBuffer::new(),
+            graphics: graphics::protocol(),
+            hud: Hud::new(),
+            mouse: TerminalMouse::new(),
+            remote: RemoteControl::new(remote_input::receiver()),
+            cells: (0, 0),
+            model_path: ARG.obj_path.clone(),
+            models: Vec::new(),
+            picker: None,
         }
     }
 
     pub fn run(mut self, state: Option<self_type::StateImp>) -> Result<(), Box<dyn Error>> {
+        self.camera_controller.set_input_log(input_log::from_args(true));
+        self.camera_controller.model_ctrl.set_instances(scene::from_args());
+        self.models = self.camera_controller.model_ctrl.instances().iter().map(|i| i.to_raw().model_transform()).collect();
         enable_raw_mode()?;
 
         execute!(self.stdout, crossterm::cursor::Hide);
         execute!(self.stdout, EnterAlternateScreen, event::EnableMouseCapture);
         execute!(self.stdout, crossterm::terminal::Clear(ClearType::All));
 
-        let dimension = (256, 79);
         self.gpu = state;
+        if self.gpu.is_none() {
+            self.picker = PickScene::load(&self.model_path).ok();
+        }
+        self.resize(terminal_dimension());
         let fps = self.fps.clone();
         let _lop = game_loop(self, fps, 0.1, |g| {
             // update
@@ -68,15 +122,20 @@ impl TuiWinApp {
                                 Event::FocusGained => {}
                                 Event::FocusLost => {}
                                 Event::Key(k) => {
-                                    if !g.game.camera_controller.process_tui_keyboard(&k) {
+                                    if g.game.hud.browser.is_open() {
+                                        g.game.browser_key(&k);
+                                    } else if is_hud_key(&k, g.game.camera_controller.keymap()) {
+                                        g.game.toggle_hud();
+                                    } else if is_browser_key(&k, g.game.camera_controller.keymap()) {
+                                        g.game.toggle_browser();
+                                    } else if !g.game.camera_controller.process_tui_keyboard(&k) {
                                         should_exit = true;
                                     }
                                 }
-                                Event::Mouse(_) => {}
+                                Event::Mouse(m) => g.game.mouse_event(&m),
                                 Event::Paste(_) => {}
                                 Event::Resize(w, h) => {
-                                    println!("terminal window update to new size {} {}", w, h);
-                                    //self.draw((w as u32, h as u32), &res);
+                                    g.game.resize((w as u32, h as u32));
                                 }
                             }
                         } else {
@@ -96,7 +155,8 @@ impl TuiWinApp {
                 drop(&g.game);
                 g.exit();
             }
-            g.game.draw((dimension.0 as u32, dimension.1 as u32));
+            let dimension = g.game.dimension;
+            g.game.draw(dimension);
 
             let st = g.game.time_step.as_secs_f64() - game_loop::Time::now().sub(&g.current_instant());
             if st > 0. {
@@ -107,43 +167,169 @@ impl TuiWinApp {
         Ok(())
     }
 
+    pub fn resize(&mut self, cells: (u32, u32)) {
+        if cells.0 == 0 || cells.1 == 0 {
+            return;
+        }
+        debug!("terminal window update to new size {:?}", cells);
+        let dim = self.graphics.render_dimension(self.cell_mode, cells);
+        self.dimension = dim;
+        self.cells = cells;
+        // the gpu renders the terminal view as a second target, projected with the aspect of that
+        // target, so the window keeps its own size and projection.
+        if let Some(ref mut gpu) = self.gpu {
+            gpu.resize_tui(dim);
+        } else {
+            let _ = self.ms.enc.send(TransferMsg::Resize(dim.0, dim.1));
+        }
+        self.raster.resize(dim.0, dim.1);
+        execute!(self.stdout, crossterm::terminal::Clear(ClearType::All));
+        self.front.invalidate();
+        self.hud.invalidate();
+    }
+
+    pub fn toggle_hud(&mut self) {
+        self.hud.toggle();
+        self.redraw_all();
+    }
+
+    pub fn toggle_browser(&mut self) {
+        self.hud.toggle_browser();
+        self.redraw_all();
+    }
+
+    // clear the screen, the next frame and overlay are printed in full.
+    fn redraw_all(&mut self) {
+        execute!(self.stdout, crossterm::terminal::Clear(ClearType::All));
+        self.front.invalidate();
+        self.hud.invalidate();
+    }
+
+    fn load_model(&mut self, path: &str) -> anyhow::Result<()> {
+        match self.gpu {
+            Some(ref mut gpu) => gpu.load_model(path),
+            None => {
+                self.res = load_cpu_model(path)?;
+                self.picker = PickScene::load(path).ok();
+                Ok(())
+            }
+        }
+    }
+
+    fn browser_key(&mut self, key: &KeyEvent) {
+        match self.hud.browser.handle_key(key, self.camera_controller.keymap()) {
+            BrowserAction::None => {}
+            BrowserAction::Close => self.redraw_all(),
+            BrowserAction::Load(path) => {
+                let path = path.to_string_lossy().into_owned();
+                let status = match self.load_model(&path) {
+                    Ok(()) => {
+                        self.model_path = path;
+                        format!("loaded {}", self.model_path)
+                    }
+                    Err(e) => format!("could not load {}: {}", path, e),
+                };
+                self.hud.browser.set_status(status);
+                self.redraw_all();
+            }
+        }
+    }
+
+    fn mouse_event(&mut self, event: &MouseEvent) {
+        if self.hud.browser.is_open() {
+            return;
+        }
+        if let MouseAction::Click { column, row } = self.mouse.handle(event, &mut self.camera_controller) {
+            let ndc = pick::ndc(self.graphics.cell_to_pixel(self.cell_mode, column, row), self.dimension);
+            let (hit, scene) = match self.gpu {
+                Some(ref gpu) => (gpu.pick(ndc, self.dimension, &self.camera_controller.model_ctrl), gpu.picker()),
+                None => {
+                    let models: Vec<_> = self.cpu_models().iter().map(pick::to_column_major).collect();
+                    let hit = self.picker.as_ref().and_then(|p| p.pick(&self.raster.camera().screen_ray(ndc), &models));
+                    (hit, self.picker.as_ref())
+                }
+            };
+            report_pick(hit, scene, &mut self.camera_controller, &mut self.hud);
+        }
+    }
+
+    // model matrices the cpu rasterizer draws the instances with.
+    fn cpu_models(&self) -> Vec<HomoTransform> {
+        let base = HomoTransform::rotation_matrix(&Vector3::from_xyz(0., 1., 0.), self.theta) * HomoTransform::scale((1.5, 1.5, 1.5));
+        self.models.iter().map(|m| &base * m).collect()
+    }
+
+    fn draw_hud(&mut self, stats: HudStats) {
+        self.hud.tick(stats);
+        if self.graphics != GraphicsProtocol::Cells {
+            // images are printed over the whole screen every frame.
+            self.hud.invalidate();
+        }
+        self.hud.draw(self.cells, self.camera_controller.keymap());
+    }
+
     pub fn update(&mut self, last_frame_time: f64) {
+        self.remote.update(&mut self.camera_controller);
         if let Some(ref mut gpu) = self.gpu {
             gpu.update_outside(&mut self.camera_controller, Duration::from_secs_f64(last_frame_time));
+        } else {
+            let dt = self.camera_controller.update_camera(self.raster.camera_mut(), Duration::from_secs_f64(last_frame_time));
+            self.models = self.camera_controller.model_ctrl.update_model(dt).iter().map(InstanceRaw::model_transform).collect();
         }
     }
 
 
     pub fn draw(&mut self, dim: (u32, u32)) {
+        let now = Instant::now();
+        self.front.set_overlay(self.hud.areas(self.cells));
         if let Some(ref mut gpu) = self.gpu {
             let mut out_buf = OutputBuffer::new(dim.0 as u32, dim.1 as u32, true);
             out_buf.stdout = Some(&mut self.stdout);
+            out_buf.cell_mode = self.cell_mode;
+            out_buf.color_depth = self.color_depth;
+            out_buf.dither = self.dither;
+            out_buf.front = Some(&mut self.front);
+            out_buf.graphics = self.graphics;
             let out = gpu.render(true);
-            out_buf.display.copy_from_slice(&out.1.unwrap());
+            out_buf.load_frame(&out.1.unwrap());
             self.ms.win.try_send(TransferMsg::RenderedData(out.0)).unwrap();
             out_buf.queue_to_stdout();
             drop(out_buf);
             self.stdout.flush().unwrap();
+            let stats = hud_stats(now, Some(gpu.triangle_count()), gpu.camera(), &self.camera_controller.model_ctrl, "wgpu", &self.model_path);
+            self.draw_hud(stats);
             return;
         }
 
 
-        let _now = std::time::Instant::now();
+        let models = self.cpu_models();
         let mut out_buf = OutputBuffer::new(dim.0 as u32, dim.1 as u32, true);
         out_buf.stdout = Some(&mut self.stdout);
-        self.raster.set_model(HomoTransform::rotation_matrix(&Vector3::from_xyz(0., 1., 0.), self.theta) * HomoTransform::scale((1.5, 1.5, 1.5)));
-        self.raster.render_frame(&self.res, &mut out_buf);
+        out_buf.cell_mode = self.cell_mode;
+        out_buf.color_depth = self.color_depth;
+        out_buf.dither = self.dither;
+        out_buf.front = Some(&mut self.front);
+        out_buf.graphics = self.graphics;
+        self.raster.render_instances(&self.res, &mut out_buf, &models);
         out_buf.queue_to_stdout();
         let data = out_buf.display.clone();
         drop(out_buf);
         self.stdout.flush().unwrap();
 
-        self.raster.encoder_tx.enc.send(TransferMsg::RenderedData(data)).unwrap();
+        let stats = hud_stats(now, Some(self.res.model.mesh.indices.len() / 3), self.raster.camera(), &self.camera_controller.model_ctrl, self.raster.shader_name(), &self.model_path);
+        self.draw_hud(stats);
+        // nobody watches the stream, skip encoding.
+        if router::streaming() {
+            self.raster.encoder_tx.enc.send(TransferMsg::RenderedData(data)).unwrap();
+        }
     }
 }
 
 impl Drop for TuiWinApp {
     fn drop(&mut self) {
+        if self.graphics == GraphicsProtocol::Kitty {
+            graphics::delete_kitty_images(&mut self.stdout);
+        }
         execute!(self.stdout, crossterm::terminal::Clear(ClearType::All));
         execute!(self.stdout, crossterm::terminal::LeaveAlternateScreen, event::DisableMouseCapture);
         execute!(self.stdout, crossterm::cursor::Show);
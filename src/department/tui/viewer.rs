@@ -0,0 +1,167 @@
+use std::error::Error;
+use std::io::{Stdout, Write};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossterm::{event, execute, terminal};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{ClearType, disable_raw_mode, enable_raw_mode, EnterAlternateScreen};
+use log::{debug, info};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::department::control::keymap::{Action, Keymap};
+use crate::department::control::terminal_mouse::TerminalMouse;
+use crate::department::net::client::VideoFrame;
+use crate::department::net::frame_queue::FrameQueue;
+use crate::department::net::remote_input::{self, RemoteMouse};
+use crate::department::net::session;
+use crate::department::preview::cell_encoder::CellMode;
+use crate::department::preview::front_buffer::FrontBuffer;
+use crate::department::preview::graphics::{self, GraphicsProtocol};
+use crate::department::preview::output_buffer::OutputBuffer;
+use crate::department::preview::palette::{ColorDepth, Dither};
+use crate::pb::netpacket::NetPacket;
+use super::terminal_dimension;
+
+// how long to wait for terminal events before looking for a new frame.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// shows the frames of a remote dognut server in the terminal and sends keys and mouse back.
+pub struct ViewerApp {
+    stdout: Stdout,
+    // the newest frame not drawn yet, closed when the stream ends.
+    frames: Arc<FrameQueue<VideoFrame>>,
+    input: UnboundedSender<NetPacket>,
+    keymap: Keymap,
+    mouse: TerminalMouse,
+    // the newest frame, drawn again after a resize.
+    last: Option<VideoFrame>,
+    front: FrontBuffer,
+    cell_mode: CellMode,
+    color_depth: ColorDepth,
+    dither: Dither,
+    graphics: GraphicsProtocol,
+    dimension: (u32, u32),
+}
+
+impl ViewerApp {
+    pub fn new(frames: Arc<FrameQueue<VideoFrame>>, input: UnboundedSender<NetPacket>, keymap: Keymap, cell_mode: CellMode,
+               color_depth: ColorDepth, dither: Dither, graphics: GraphicsProtocol) -> Self {
+        Self { stdout: std::io::stdout(), frames, input, keymap, mouse: TerminalMouse::new(), last: None,
+            front: FrontBuffer::new(), cell_mode, color_depth, dither, graphics, dimension: (0, 0) }
+    }
+
+    /// draw frames until the quit key or ctrl-c is pressed or the server goes away.
+    pub fn run(mut self) -> Result<(), Box<dyn Error>> {
+        enable_raw_mode()?;
+        execute!(self.stdout, crossterm::cursor::Hide)?;
+        execute!(self.stdout, EnterAlternateScreen, event::EnableMouseCapture)?;
+        execute!(self.stdout, terminal::Clear(ClearType::All))?;
+        self.resize(terminal_dimension());
+
+        loop {
+            while event::poll(POLL_INTERVAL)? {
+                match event::read()? {
+                    Event::Key(k) => {
+                        if !self.key(&k) {
+                            return Ok(());
+                        }
+                    }
+                    Event::Mouse(m) => {
+                        self.mouse.handle(&m, &mut RemoteMouse { out: &self.input });
+                    }
+                    Event::Resize(w, h) => self.resize((w as u32, h as u32)),
+                    _ => {}
+                }
+            }
+
+            match self.frames.try_pop() {
+                Some(frame) => {
+                    self.last = Some(frame);
+                    self.draw();
+                }
+                None if self.frames.is_closed() => {
+                    info!("stream ended");
+                    return Ok(());
+                }
+                None => {}
+            }
+        }
+    }
+
+    // send the action of a key to the server, returns false when the viewer should quit.
+    fn key(&mut self, key: &KeyEvent) -> bool {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return false;
+        }
+        match self.keymap.tui_action(&key.code) {
+            Some(Action::Quit) => false,
+            Some(Action::RequestKeyframe) => {
+                if key.kind == KeyEventKind::Press {
+                    debug!("asking for a keyframe");
+                    let _ = self.input.send(session::keyframe_request_packet("requested by user"));
+                }
+                true
+            }
+            Some(action) => {
+                let pressed = key.kind == KeyEventKind::Press;
+                let model = key.modifiers.contains(KeyModifiers::CONTROL);
+                // the server is gone when this fails, the frame channel tells the loop.
+                let _ = self.input.send(remote_input::key_packet(action, pressed, model));
+                true
+            }
+            None => true,
+        }
+    }
+
+    fn resize(&mut self, cells: (u32, u32)) {
+        if cells.0 == 0 || cells.1 == 0 {
+            return;
+        }
+        debug!("terminal window update to new size {:?}", cells);
+        self.dimension = self.graphics.render_dimension(self.cell_mode, cells);
+        execute!(self.stdout, terminal::Clear(ClearType::All));
+        self.front.invalidate();
+        self.draw();
+    }
+
+    // scale the last frame to the terminal and print it.
+    fn draw(&mut self) {
+        let frame = match self.last {
+            Some(ref frame) => frame,
+            None => return,
+        };
+        let (w, h) = self.dimension;
+        let image = match image::RgbaImage::from_raw(frame.width, frame.height, frame.rgba.clone()) {
+            Some(image) => image,
+            None => return,
+        };
+        let scaled = image::imageops::resize(&image, w, h, image::imageops::FilterType::Triangle);
+
+        let mut out_buf = OutputBuffer::new(w, h, true);
+        out_buf.stdout = Some(&mut self.stdout);
+        out_buf.cell_mode = self.cell_mode;
+        out_buf.color_depth = self.color_depth;
+        out_buf.dither = self.dither;
+        out_buf.front = Some(&mut self.front);
+        out_buf.graphics = self.graphics;
+        out_buf.load_frame(scaled.as_raw());
+        out_buf.queue_to_stdout();
+        drop(out_buf);
+        self.stdout.flush().unwrap();
+    }
+}
+
+impl Drop for ViewerApp {
+    fn drop(&mut self) {
+        // tells the receiver to stop.
+        self.frames.close();
+        if self.graphics == GraphicsProtocol::Kitty {
+            graphics::delete_kitty_images(&mut self.stdout);
+        }
+        execute!(self.stdout, terminal::Clear(ClearType::All));
+        execute!(self.stdout, terminal::LeaveAlternateScreen, event::DisableMouseCapture);
+        execute!(self.stdout, crossterm::cursor::Show);
+        disable_raw_mode().unwrap();
+    }
+}
@@ -1,6 +1,24 @@
+use crossbeam_channel::{Receiver, Sender};
+use lazy_static::lazy_static;
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum ControlMsg {
     GenIDR,
     Discard,
     StartSend,
-}
\ No newline at end of file
+}
+
+lazy_static! {
+    // requests for the encoder, a waiting keyframe request covers the ones after it.
+    static ref ENCODER_CONTROL: (Sender<ControlMsg>, Receiver<ControlMsg>) = crossbeam_channel::bounded(1);
+}
+
+/// ask the encoder to make the next frame a keyframe, returns false when a request is already waiting.
+pub fn request_keyframe() -> bool {
+    ENCODER_CONTROL.0.try_send(ControlMsg::GenIDR).is_ok()
+}
+
+/// where the encoder takes its requests from.
+pub fn encoder_control() -> Receiver<ControlMsg> {
+    ENCODER_CONTROL.1.clone()
+}
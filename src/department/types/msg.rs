@@ -1,3 +1,5 @@
+use crate::department::control::input_log::InputEvent;
+
 #[derive(Clone, PartialEq)]
 pub enum DognutOption {
     StartEncode = 1,
@@ -11,5 +13,8 @@ pub enum TransferMsg {
     CompressedData(Vec<u8>),
     DogOpt(DognutOption),
     QuitThread,
+    Resize(u32, u32),
+    /// input of a remote viewer for the camera controller.
+    RemoteInput(InputEvent),
     Test(u32)
 }
@@ -2,7 +2,6 @@ use std::ffi::c_int;
 use std::thread::JoinHandle;
 use std::time::{Instant};
 
-use crossbeam_channel::{unbounded};
 use ffmpeg::codec;
 use ffmpeg::encoder::video;
 use ffmpeg::ffi;
@@ -21,7 +20,7 @@ use protobuf::Message;
 
 
 
-use crate::department::types::control::ControlMsg;
+use crate::department::types::control::{self, ControlMsg};
 use crate::department::types::msg::{TransferMsg, DognutOption};
 use crate::department::types::multi_sender::MultiSender;
 use crate::pb;
@@ -70,8 +69,6 @@ impl RgbaEncoder {
         let scaler = scaling::Context::get(Pixel::RGBA, dimension.0, dimension.1,
         Pixel::YUV420P, dimension.0, dimension.1, Flags::BILINEAR)?;
 
-        let msg_rx = unbounded();
-
         Ok(Self {
             ms,
             rx,
@@ -79,7 +76,7 @@ impl RgbaEncoder {
             dimension,
 
             scale_ctx: scaler,
-            message_rx: msg_rx.1,
+            message_rx: control::encoder_control(),
             codec,
             next_frame_idr: false,
             instant: Instant::now(),
@@ -98,6 +95,7 @@ impl RgbaEncoder {
         (*raw_context).rc_max_rate = 2 * 1000 * 1000;
         (*raw_context).rc_min_rate = (2.5 * 1000. * 1000.) as i64;
         (*raw_context).framerate = ffi::AVRational{num:30, den: 1};
+        // keyframes come when a client joins or asks for one, see `control::request_keyframe`.
         (*raw_context).gop_size = 100000000;
         // disable b frame for realtime streaming
         (*raw_context).max_b_frames = 0;
@@ -109,6 +107,10 @@ impl RgbaEncoder {
         k = std::ffi::CString::new("x264-params").unwrap();
         v = std::ffi::CString::new("keyint=60:min-keyint=60:scenecut=0:force-cfr=1").unwrap();
         ffi::av_opt_set((*raw_context).priv_data as *mut _, k.as_ptr(), v.as_ptr(), 0);
+        // a forced I frame has to be an IDR frame, or a new decoder can't start from it.
+        k = std::ffi::CString::new("forced-idr").unwrap();
+        v = std::ffi::CString::new("1").unwrap();
+        ffi::av_opt_set((*raw_context).priv_data as *mut _, k.as_ptr(), v.as_ptr(), 0);
 
         return Context::wrap(raw_context, None);
     }
@@ -181,9 +183,19 @@ impl RgbaEncoder {
             // }
         }
 
+        let _ = self.ms.net.send(TransferMsg::Resize(self.dimension.0, self.dimension.1));
         let mut packet = ffmpeg::Packet::empty();
         let mut index = 0;
         loop {
+            while let Ok(msg) = self.message_rx.try_recv() {
+                match msg {
+                    ControlMsg::GenIDR => {
+                        info!("next frame is a keyframe on request");
+                        self.next_frame_idr = true;
+                    }
+                    ControlMsg::Discard | ControlMsg::StartSend => {}
+                }
+            }
             if let Ok(msg) = self.rx.try_recv() {
                 match msg {
                     TransferMsg::RenderedData(data) => {
@@ -196,6 +208,9 @@ impl RgbaEncoder {
                     TransferMsg::QuitThread => {
                         break;
                     }
+                    TransferMsg::Resize(w, h) => {
+                        warn!("h264 encoder keeps {:?}, ignore resize to {}x{}", self.dimension, w, h);
+                    }
                     TransferMsg::Test(_) => {}
                     _ => {}
                 }
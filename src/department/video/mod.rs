@@ -37,7 +37,7 @@ impl ImgEncoder {
         return handle;
     }
 
-    pub fn run_encoding_pipeline(self) {
+    pub fn run_encoding_pipeline(mut self) {
         loop {
             let msg = self.rx.recv().unwrap();
             match msg {
@@ -54,12 +54,17 @@ impl ImgEncoder {
                     info!("encoder thread quit on msg");
                     return;
                 }
+                TransferMsg::Resize(w, h) => {
+                    self.dimension = (w, h);
+                }
                 _ => {}
             }
         }
 
         //self.ms.win.send(TransferMsg::DogOpt(DognutOption::EncoderStarted)).expect("must send ok");
 
+        // the router tells new clients the size of the frames.
+        let _ = self.ms.net.send(TransferMsg::Resize(self.dimension.0, self.dimension.1));
         let _index = 0;
         loop {
             if let Ok(msg) = self.rx.recv() {
@@ -80,6 +85,11 @@ impl ImgEncoder {
                         info!("img encoder thread quit on msg");
                         return;
                     }
+                    TransferMsg::Resize(w, h) => {
+                        info!("img encoder dimension update to {}x{}", w, h);
+                        self.dimension = (w, h);
+                        let _ = self.ms.net.send(TransferMsg::Resize(w, h));
+                    }
                     _ => {}
                 }
             }
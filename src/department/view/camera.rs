@@ -1,5 +1,6 @@
 use std::f32::consts::PI;
 
+use cgmath::SquareMatrix;
 use image::GenericImageView;
 use winit::event::VirtualKeyCode;
 
@@ -12,6 +13,7 @@ use crate::department::preview::matrix::HMat;
 use crate::department::preview::output_buffer::OutputBuffer;
 use crate::department::preview::position::Pos3;
 use crate::department::preview::vector::Vector3;
+use crate::department::pick::{self, Ray};
 use crate::department::view::camera_trait;
 
 pub struct Camera {
@@ -48,6 +50,14 @@ impl camera_trait::CameraTrait for Camera {
             Camera::perspective_projection_mat_right_hand(self.fov_y, self.ratio, self.n, self.z)
         };
     }
+
+    fn screen_ray(&self, ndc: (f32, f32)) -> Ray {
+        let view_proj = pick::to_column_major(&(&self.to_view_matrix() * &self.perspective_projection));
+        let inverse = view_proj.invert().unwrap_or_else(cgmath::Matrix4::identity);
+        let eye = cgmath::Point3::new(self.eye.x(), self.eye.y(), self.eye.z());
+        let forward = cgmath::Vector3::new(self.forward.x(), self.forward.y(), self.forward.z());
+        pick::unproject(&inverse, ndc, eye, forward)
+    }
 }
 
 impl Camera {
@@ -1,3 +1,5 @@
+use crate::department::pick::Ray;
+
 pub trait CameraTrait {
     fn update_camera(&mut self, forward_dt: f32, right_dt: f32, scroll_dt: f32, up_dt: f32, hori: f32, ver: f32, sensi: f32);
 
@@ -7,4 +9,7 @@ pub trait CameraTrait {
 
     fn update_projection(&mut self, width: u32, height: u32);
 
+    /// world space ray through the point at `ndc` on screen, see `pick::ndc`.
+    fn screen_ray(&self, ndc: (f32, f32)) -> Ray;
+
 }
@@ -0,0 +1,43 @@
+use std::path::Path;
+use std::time::Duration;
+
+use image::{ImageFormat, RgbaImage};
+use log::{error, info};
+use winit::dpi::LogicalSize;
+
+use crate::department::common::constant::{HEIGHT, WIDTH};
+use crate::department::common::self_type;
+use crate::department::control::camera_controller::CameraController;
+use crate::department::control::input_log::{InputLog, InputReplay};
+use crate::wgpu::wgpu_helper::State;
+
+/// render every frame of an input log offscreen and save them as `frame_00000.png` in `out_dir`.
+/// the recorded time steps are used, so the frames match what was on screen while recording.
+pub async fn replay(replay: InputReplay, out_dir: &str) -> anyhow::Result<usize> {
+    std::fs::create_dir_all(out_dir)?;
+    let camera = self_type::camera_instance(WIDTH, HEIGHT);
+    let mut state = State::new(LogicalSize { width: WIDTH, height: HEIGHT }, camera).await;
+    // the controller the log was recorded from, a terminal log sums its input between updates.
+    let mut controller = CameraController::new(2.0, 0.2, replay.tui());
+    controller.model_ctrl.set_instances(state.camera_controller.model_ctrl.instances().to_vec());
+    controller.set_input_log(Some(InputLog::Replay(replay)));
+    state.camera_controller = controller;
+
+    let mut frames = 0;
+    while state.camera_controller.is_replaying() {
+        // the time step passed here is replaced by the recorded one.
+        state.update(Duration::ZERO);
+        if !state.camera_controller.is_replaying() {
+            break;
+        }
+        let (data, _) = state.render(false);
+        let path = Path::new(out_dir).join(format!("frame_{:05}.png", frames));
+        match RgbaImage::from_raw(WIDTH, HEIGHT, data) {
+            Some(img) => img.save_with_format(&path, ImageFormat::Png)?,
+            None => error!("frame {} has the wrong size", frames),
+        }
+        frames += 1;
+    }
+    info!("replayed {} frames into {}", frames, out_dir);
+    Ok(frames)
+}
@@ -6,6 +6,7 @@ use winit::dpi::LogicalSize;
 use winit::event_loop::EventLoop;
 use winit::window::WindowBuilder;
 use crate::department::common::constant::{HEIGHT, WIDTH};
+use crate::department::net::router;
 use crate::department::types::msg::{DognutOption, TransferMsg};
 use crate::department::types::multi_sender::MultiSender;
 
@@ -50,7 +51,7 @@ impl LocalWindow {
                 TransferMsg::RenderedData(rgba) => {
                     self.pixels.get_frame_mut().copy_from_slice(&rgba);
                     self.pixels.render().expect("should render success");
-                    if self.encoder_start_working {
+                    if self.encoder_start_working && router::streaming() {
                         self.ms.enc.try_send(TransferMsg::RenderedData(rgba)).unwrap();
                     }
                 }
@@ -3,3 +3,4 @@ pub mod window;
 pub mod render;
 pub mod camera_trait;
 pub mod local_window;
+pub mod headless;
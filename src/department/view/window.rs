@@ -8,7 +8,7 @@ use log::{error, info};
 use pixels::{Error, Pixels, SurfaceTexture};
 use pixels::wgpu::Color;
 use winit::dpi::{LogicalSize};
-use winit::event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent};
+use winit::event::{ElementState, Event, KeyboardInput, MouseButton, VirtualKeyCode, WindowEvent};
 use winit::event_loop::{EventLoop};
 use winit::window::WindowBuilder;
 use winit_input_helper::WinitInputHelper;
@@ -17,6 +17,9 @@ use crate::department::types::msg::{TransferMsg, DognutOption};
 use crate::department::types::multi_sender::MultiSender;
 use crate::department::common::constant::{HEIGHT, WHOLE_WIDTH, WIDTH};
 use crate::department::common::self_type;
+use crate::department::control::input_log;
+use crate::department::control::keymap::Keymap;
+use crate::department::net::router;
 use crate::department::Game;
 use crate::wgpu::wgpu_helper::State;
 
@@ -27,10 +30,12 @@ pub const TIME_STEP: Duration = Duration::from_nanos(1_000_000_000 / FPS as u64)
 /// Representation of the application state. In this example, a box will bounce around the screen.
 ///
 
-pub async fn run(win_receiver: crossbeam_channel::Receiver<TransferMsg>, ms: MultiSender<TransferMsg>, split: bool) -> Result<(), Error> {
+pub async fn run(win_receiver: crossbeam_channel::Receiver<TransferMsg>, ms: MultiSender<TransferMsg>, split: bool, keymap: Keymap) -> Result<(), Error> {
     let setting_width = if split { WHOLE_WIDTH } else { WIDTH };
     let camera = self_type::camera_instance(setting_width, HEIGHT);
-    let state = State::new(LogicalSize { width: setting_width, height: HEIGHT }, camera).await;
+    let mut state = State::new(LogicalSize { width: setting_width, height: HEIGHT }, camera).await;
+    state.camera_controller.set_input_log(input_log::from_args(false));
+    state.camera_controller.set_keymap(keymap);
 
     let event_loop = EventLoop::new();
     let _input = WinitInputHelper::new();
@@ -62,8 +67,12 @@ pub async fn run(win_receiver: crossbeam_channel::Receiver<TransferMsg>, ms: Mul
 
     let mut index = 0;
 
+    // last cursor position in physical pixels, for picking.
+    let mut cursor: Option<(f32, f32)> = None;
+
     game_loop(event_loop, window, game, FPS as u32, 0.1,
         |g| {
+            g.game.update_controls();
             if !g.game.paused {
                 g.game.state.update(std::time::Duration::from_secs_f64(g.last_frame_time()));
             }
@@ -82,7 +91,7 @@ pub async fn run(win_receiver: crossbeam_channel::Receiver<TransferMsg>, ms: Mul
             if split {
                let (this, that) = crate::util::split_screen(&out.0, (WHOLE_WIDTH, HEIGHT), (WIDTH, HEIGHT));
                 g.game.pixels.get_frame_mut().copy_from_slice(&that.as_slice());
-                if start_enc_render {
+                if start_enc_render && router::streaming() {
                     if let Err(e) = ms.enc.try_send(TransferMsg::RenderedData(this)) {
                         error!("send raw rgba fail: reason {:?}", e);
                     }
@@ -91,7 +100,7 @@ pub async fn run(win_receiver: crossbeam_channel::Receiver<TransferMsg>, ms: Mul
                 }
             }else {
                 g.game.pixels.get_frame_mut().copy_from_slice(&out.0.as_slice());
-                if start_enc_render {
+                if start_enc_render && router::streaming() {
                     if let Err(e) = ms.enc.try_send(TransferMsg::RenderedData(out.0)) {
                         error!("send raw rgba fail: reason {:?}", e);
                     }
@@ -149,6 +158,16 @@ pub async fn run(win_receiver: crossbeam_channel::Receiver<TransferMsg>, ms: Mul
                         WindowEvent::ModifiersChanged(ms) => {
                             g.game.state.camera_controller.ctrl_pressed = ms.ctrl();
                         }
+                        WindowEvent::CursorMoved { position, .. } => {
+                            cursor = Some((position.x as f32, position.y as f32));
+                        }
+                        WindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, .. } => {
+                            if let Some(Ok((x, y))) = cursor.map(|c| g.game.pixels.window_pos_to_pixel(c)) {
+                                // in split mode the window shows the right half of the frame.
+                                let offset = if split { WIDTH } else { 0 };
+                                g.game.pick((x as u32 + offset, y as u32));
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -28,6 +28,17 @@ const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_2_0;
 #[derive(PartialEq,Clone,Default,Debug)]
 // @@protoc_insertion_point(message:proto.avpacket.VideoHeader)
 pub struct VideoHeader {
+    // message fields
+    // @@protoc_insertion_point(field:proto.avpacket.VideoHeader.codec)
+    pub codec: ::protobuf::EnumOrUnknown<Codec>,
+    // @@protoc_insertion_point(field:proto.avpacket.VideoHeader.pixel_format)
+    pub pixel_format: ::protobuf::EnumOrUnknown<PixelFormat>,
+    // @@protoc_insertion_point(field:proto.avpacket.VideoHeader.width)
+    pub width: u32,
+    // @@protoc_insertion_point(field:proto.avpacket.VideoHeader.height)
+    pub height: u32,
+    // @@protoc_insertion_point(field:proto.avpacket.VideoHeader.fps)
+    pub fps: u32,
     // special fields
     // @@protoc_insertion_point(special_field:proto.avpacket.VideoHeader.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -45,8 +56,33 @@ impl VideoHeader {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut fields = ::std::vec::Vec::with_capacity(5);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "codec",
+            |m: &VideoHeader| { &m.codec },
+            |m: &mut VideoHeader| { &mut m.codec },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "pixel_format",
+            |m: &VideoHeader| { &m.pixel_format },
+            |m: &mut VideoHeader| { &mut m.pixel_format },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "width",
+            |m: &VideoHeader| { &m.width },
+            |m: &mut VideoHeader| { &mut m.width },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "height",
+            |m: &VideoHeader| { &m.height },
+            |m: &mut VideoHeader| { &mut m.height },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "fps",
+            |m: &VideoHeader| { &m.fps },
+            |m: &mut VideoHeader| { &mut m.fps },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<VideoHeader>(
             "VideoHeader",
             fields,
@@ -65,6 +101,21 @@ impl ::protobuf::Message for VideoHeader {
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
+                8 => {
+                    self.codec = is.read_enum_or_unknown()?;
+                },
+                16 => {
+                    self.pixel_format = is.read_enum_or_unknown()?;
+                },
+                24 => {
+                    self.width = is.read_uint32()?;
+                },
+                32 => {
+                    self.height = is.read_uint32()?;
+                },
+                40 => {
+                    self.fps = is.read_uint32()?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -77,12 +128,42 @@ impl ::protobuf::Message for VideoHeader {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u64 {
         let mut my_size = 0;
+        if self.codec != ::protobuf::EnumOrUnknown::new(Codec::Raw) {
+            my_size += ::protobuf::rt::int32_size(1, self.codec.value());
+        }
+        if self.pixel_format != ::protobuf::EnumOrUnknown::new(PixelFormat::Rgba) {
+            my_size += ::protobuf::rt::int32_size(2, self.pixel_format.value());
+        }
+        if self.width != 0 {
+            my_size += ::protobuf::rt::uint32_size(3, self.width);
+        }
+        if self.height != 0 {
+            my_size += ::protobuf::rt::uint32_size(4, self.height);
+        }
+        if self.fps != 0 {
+            my_size += ::protobuf::rt::uint32_size(5, self.fps);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.codec != ::protobuf::EnumOrUnknown::new(Codec::Raw) {
+            os.write_enum(1, ::protobuf::EnumOrUnknown::value(&self.codec))?;
+        }
+        if self.pixel_format != ::protobuf::EnumOrUnknown::new(PixelFormat::Rgba) {
+            os.write_enum(2, ::protobuf::EnumOrUnknown::value(&self.pixel_format))?;
+        }
+        if self.width != 0 {
+            os.write_uint32(3, self.width)?;
+        }
+        if self.height != 0 {
+            os.write_uint32(4, self.height)?;
+        }
+        if self.fps != 0 {
+            os.write_uint32(5, self.fps)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -100,11 +181,21 @@ impl ::protobuf::Message for VideoHeader {
     }
 
     fn clear(&mut self) {
+        self.codec = ::protobuf::EnumOrUnknown::new(Codec::Raw);
+        self.pixel_format = ::protobuf::EnumOrUnknown::new(PixelFormat::Rgba);
+        self.width = 0;
+        self.height = 0;
+        self.fps = 0;
         self.special_fields.clear();
     }
 
     fn default_instance() -> &'static VideoHeader {
         static instance: VideoHeader = VideoHeader {
+            codec: ::protobuf::EnumOrUnknown::from_i32(0),
+            pixel_format: ::protobuf::EnumOrUnknown::from_i32(0),
+            width: 0,
+            height: 0,
+            fps: 0,
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
@@ -358,37 +449,133 @@ impl ::protobuf::reflect::ProtobufValue for VideoPacket {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+// @@protoc_insertion_point(enum:proto.avpacket.Codec)
+pub enum Codec {
+    // @@protoc_insertion_point(enum_value:proto.avpacket.Codec.Raw)
+    Raw = 0,
+    // @@protoc_insertion_point(enum_value:proto.avpacket.Codec.Jpeg)
+    Jpeg = 1,
+    // @@protoc_insertion_point(enum_value:proto.avpacket.Codec.H264)
+    H264 = 2,
+}
+
+impl ::protobuf::Enum for Codec {
+    const NAME: &'static str = "Codec";
+
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Codec> {
+        match value {
+            0 => ::std::option::Option::Some(Codec::Raw),
+            1 => ::std::option::Option::Some(Codec::Jpeg),
+            2 => ::std::option::Option::Some(Codec::H264),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    const VALUES: &'static [Codec] = &[
+        Codec::Raw,
+        Codec::Jpeg,
+        Codec::H264,
+    ];
+}
+
+impl ::protobuf::EnumFull for Codec {
+    fn enum_descriptor() -> ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().enum_by_package_relative_name("Codec").unwrap()).clone()
+    }
+
+    fn descriptor(&self) -> ::protobuf::reflect::EnumValueDescriptor {
+        let index = *self as usize;
+        Self::enum_descriptor().value_by_index(index)
+    }
+}
+
+impl ::std::default::Default for Codec {
+    fn default() -> Self {
+        Codec::Raw
+    }
+}
+
+impl Codec {
+    fn generated_enum_descriptor_data() -> ::protobuf::reflect::GeneratedEnumDescriptorData {
+        ::protobuf::reflect::GeneratedEnumDescriptorData::new::<Codec>("Codec")
+    }
+}
+
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+// @@protoc_insertion_point(enum:proto.avpacket.PixelFormat)
+pub enum PixelFormat {
+    // @@protoc_insertion_point(enum_value:proto.avpacket.PixelFormat.Rgba)
+    Rgba = 0,
+    // @@protoc_insertion_point(enum_value:proto.avpacket.PixelFormat.Yuv420p)
+    Yuv420p = 1,
+}
+
+impl ::protobuf::Enum for PixelFormat {
+    const NAME: &'static str = "PixelFormat";
+
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<PixelFormat> {
+        match value {
+            0 => ::std::option::Option::Some(PixelFormat::Rgba),
+            1 => ::std::option::Option::Some(PixelFormat::Yuv420p),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    const VALUES: &'static [PixelFormat] = &[
+        PixelFormat::Rgba,
+        PixelFormat::Yuv420p,
+    ];
+}
+
+impl ::protobuf::EnumFull for PixelFormat {
+    fn enum_descriptor() -> ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().enum_by_package_relative_name("PixelFormat").unwrap()).clone()
+    }
+
+    fn descriptor(&self) -> ::protobuf::reflect::EnumValueDescriptor {
+        let index = *self as usize;
+        Self::enum_descriptor().value_by_index(index)
+    }
+}
+
+impl ::std::default::Default for PixelFormat {
+    fn default() -> Self {
+        PixelFormat::Rgba
+    }
+}
+
+impl PixelFormat {
+    fn generated_enum_descriptor_data() -> ::protobuf::reflect::GeneratedEnumDescriptorData {
+        ::protobuf::reflect::GeneratedEnumDescriptorData::new::<PixelFormat>("PixelFormat")
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x0eavpacket.proto\x12\x0eproto.avpacket\"\r\n\x0bVideoHeader\"\xaf\
-    \x01\n\x0bVideoPacket\x12\x12\n\x04data\x18\x01\x20\x01(\x0cR\x04data\
-    \x12\x19\n\x08data_len\x18\x02\x20\x01(\rR\x07dataLen\x12\x10\n\x03pts\
-    \x18\x03\x20\x01(\x03R\x03pts\x12\x10\n\x03dts\x18\x04\x20\x01(\x03R\x03\
-    dts\x12\x1a\n\x08duration\x18\x05\x20\x01(\x03R\x08duration\x12\x14\n\
-    \x05flags\x18\x06\x20\x01(\x05R\x05flags\x12\x1b\n\tidr_frame\x18\x07\
-    \x20\x01(\x08R\x08idrFrameJ\xcd\x03\n\x06\x12\x04\0\0\x0f\x01\n\x08\n\
-    \x01\x0c\x12\x03\0\0\x12\n\x08\n\x01\x02\x12\x03\x01\0\x17\n\n\n\x02\x04\
-    \0\x12\x04\x03\0\x05\x01\n\n\n\x03\x04\0\x01\x12\x03\x03\x08\x13\n\n\n\
-    \x02\x04\x01\x12\x04\x07\0\x0f\x01\n\n\n\x03\x04\x01\x01\x12\x03\x07\x08\
-    \x13\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x08\x02\x11\n\x0c\n\x05\x04\x01\
-    \x02\0\x05\x12\x03\x08\x02\x07\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\x08\
-    \x08\x0c\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\x08\x0f\x10\n\x0b\n\x04\
-    \x04\x01\x02\x01\x12\x03\t\x02\x16\n\x0c\n\x05\x04\x01\x02\x01\x05\x12\
-    \x03\t\x02\x08\n\x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\t\t\x11\n\x0c\n\
-    \x05\x04\x01\x02\x01\x03\x12\x03\t\x14\x15\n\x0b\n\x04\x04\x01\x02\x02\
-    \x12\x03\n\x02\x10\n\x0c\n\x05\x04\x01\x02\x02\x05\x12\x03\n\x02\x07\n\
-    \x0c\n\x05\x04\x01\x02\x02\x01\x12\x03\n\x08\x0b\n\x0c\n\x05\x04\x01\x02\
-    \x02\x03\x12\x03\n\x0e\x0f\n\x0b\n\x04\x04\x01\x02\x03\x12\x03\x0b\x02\
-    \x10\n\x0c\n\x05\x04\x01\x02\x03\x05\x12\x03\x0b\x02\x07\n\x0c\n\x05\x04\
-    \x01\x02\x03\x01\x12\x03\x0b\x08\x0b\n\x0c\n\x05\x04\x01\x02\x03\x03\x12\
-    \x03\x0b\x0e\x0f\n\x0b\n\x04\x04\x01\x02\x04\x12\x03\x0c\x02\x15\n\x0c\n\
-    \x05\x04\x01\x02\x04\x05\x12\x03\x0c\x02\x07\n\x0c\n\x05\x04\x01\x02\x04\
-    \x01\x12\x03\x0c\x08\x10\n\x0c\n\x05\x04\x01\x02\x04\x03\x12\x03\x0c\x13\
-    \x14\n\x0b\n\x04\x04\x01\x02\x05\x12\x03\r\x02\x12\n\x0c\n\x05\x04\x01\
-    \x02\x05\x05\x12\x03\r\x02\x07\n\x0c\n\x05\x04\x01\x02\x05\x01\x12\x03\r\
-    \x08\r\n\x0c\n\x05\x04\x01\x02\x05\x03\x12\x03\r\x10\x11\n\x0b\n\x04\x04\
-    \x01\x02\x06\x12\x03\x0e\x02\x15\n\x0c\n\x05\x04\x01\x02\x06\x05\x12\x03\
-    \x0e\x02\x06\n\x0c\n\x05\x04\x01\x02\x06\x01\x12\x03\x0e\x07\x10\n\x0c\n\
-    \x05\x04\x01\x02\x06\x03\x12\x03\x0e\x13\x14b\x06proto3\
+    \n\x0eavpacket.proto\x12\x0eproto.avpacket\"\xba\x01\n\x0bVideoHeader\
+    \x12+\n\x05codec\x18\x01\x20\x01(\x0e2\x15.proto.avpacket.CodecR\x05cod\
+    ec\x12>\n\x0cpixel_format\x18\x02\x20\x01(\x0e2\x1b.proto.avpacket.Pixe\
+    lFormatR\x0bpixelFormat\x12\x14\n\x05width\x18\x03\x20\x01(\rR\x05width\
+    \x12\x16\n\x06height\x18\x04\x20\x01(\rR\x06height\x12\x10\n\x03fps\x18\
+    \x05\x20\x01(\rR\x03fps\"\xaf\x01\n\x0bVideoPacket\x12\x12\n\x04data\
+    \x18\x01\x20\x01(\x0cR\x04data\x12\x19\n\x08data_len\x18\x02\x20\x01(\r\
+    R\x07dataLen\x12\x10\n\x03pts\x18\x03\x20\x01(\x03R\x03pts\x12\x10\n\
+    \x03dts\x18\x04\x20\x01(\x03R\x03dts\x12\x1a\n\x08duration\x18\x05\x20\
+    \x01(\x03R\x08duration\x12\x14\n\x05flags\x18\x06\x20\x01(\x05R\x05flag\
+    s\x12\x1b\n\tidr_frame\x18\x07\x20\x01(\x08R\x08idrFrame*$\n\x05Codec\
+    \x12\x07\n\x03Raw\x10\0\x12\x08\n\x04Jpeg\x10\x01\x12\x08\n\x04H264\x10\
+    \x02*$\n\x0bPixelFormat\x12\x08\n\x04Rgba\x10\0\x12\x0b\n\x07Yuv420p\
+    \x10\x01b\x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -409,7 +596,9 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
             let mut messages = ::std::vec::Vec::with_capacity(2);
             messages.push(VideoHeader::generated_message_descriptor_data());
             messages.push(VideoPacket::generated_message_descriptor_data());
-            let mut enums = ::std::vec::Vec::with_capacity(0);
+            let mut enums = ::std::vec::Vec::with_capacity(2);
+            enums.push(Codec::generated_enum_descriptor_data());
+            enums.push(PixelFormat::generated_enum_descriptor_data());
             ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
                 file_descriptor_proto(),
                 deps,
@@ -165,11 +165,1504 @@ impl ::protobuf::reflect::ProtobufValue for NetPacket {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:proto.net_packet.KeyEvent)
+pub struct KeyEvent {
+    // message fields
+    // @@protoc_insertion_point(field:proto.net_packet.KeyEvent.action)
+    pub action: ::std::string::String,
+    // @@protoc_insertion_point(field:proto.net_packet.KeyEvent.pressed)
+    pub pressed: bool,
+    // @@protoc_insertion_point(field:proto.net_packet.KeyEvent.model)
+    pub model: bool,
+    // special fields
+    // @@protoc_insertion_point(special_field:proto.net_packet.KeyEvent.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a KeyEvent {
+    fn default() -> &'a KeyEvent {
+        <KeyEvent as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl KeyEvent {
+    pub fn new() -> KeyEvent {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "action",
+            |m: &KeyEvent| { &m.action },
+            |m: &mut KeyEvent| { &mut m.action },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "pressed",
+            |m: &KeyEvent| { &m.pressed },
+            |m: &mut KeyEvent| { &mut m.pressed },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "model",
+            |m: &KeyEvent| { &m.model },
+            |m: &mut KeyEvent| { &mut m.model },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<KeyEvent>(
+            "KeyEvent",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for KeyEvent {
+    const NAME: &'static str = "KeyEvent";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.action = is.read_string()?;
+                },
+                16 => {
+                    self.pressed = is.read_bool()?;
+                },
+                24 => {
+                    self.model = is.read_bool()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.action.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.action);
+        }
+        if self.pressed != false {
+            my_size += 1 + 1;
+        }
+        if self.model != false {
+            my_size += 1 + 1;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.action.is_empty() {
+            os.write_string(1, &self.action)?;
+        }
+        if self.pressed != false {
+            os.write_bool(2, self.pressed)?;
+        }
+        if self.model != false {
+            os.write_bool(3, self.model)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> KeyEvent {
+        KeyEvent::new()
+    }
+
+    fn clear(&mut self) {
+        self.action.clear();
+        self.pressed = false;
+        self.model = false;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static KeyEvent {
+        static instance: KeyEvent = KeyEvent {
+            action: ::std::string::String::new(),
+            pressed: false,
+            model: false,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for KeyEvent {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("KeyEvent").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for KeyEvent {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for KeyEvent {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:proto.net_packet.MouseEvent)
+pub struct MouseEvent {
+    // message fields
+    // @@protoc_insertion_point(field:proto.net_packet.MouseEvent.dx)
+    pub dx: f32,
+    // @@protoc_insertion_point(field:proto.net_packet.MouseEvent.dy)
+    pub dy: f32,
+    // @@protoc_insertion_point(field:proto.net_packet.MouseEvent.rotate)
+    pub rotate: bool,
+    // special fields
+    // @@protoc_insertion_point(special_field:proto.net_packet.MouseEvent.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a MouseEvent {
+    fn default() -> &'a MouseEvent {
+        <MouseEvent as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl MouseEvent {
+    pub fn new() -> MouseEvent {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "dx",
+            |m: &MouseEvent| { &m.dx },
+            |m: &mut MouseEvent| { &mut m.dx },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "dy",
+            |m: &MouseEvent| { &m.dy },
+            |m: &mut MouseEvent| { &mut m.dy },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "rotate",
+            |m: &MouseEvent| { &m.rotate },
+            |m: &mut MouseEvent| { &mut m.rotate },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<MouseEvent>(
+            "MouseEvent",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for MouseEvent {
+    const NAME: &'static str = "MouseEvent";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                13 => {
+                    self.dx = is.read_float()?;
+                },
+                21 => {
+                    self.dy = is.read_float()?;
+                },
+                24 => {
+                    self.rotate = is.read_bool()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.dx != 0. {
+            my_size += 1 + 4;
+        }
+        if self.dy != 0. {
+            my_size += 1 + 4;
+        }
+        if self.rotate != false {
+            my_size += 1 + 1;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.dx != 0. {
+            os.write_float(1, self.dx)?;
+        }
+        if self.dy != 0. {
+            os.write_float(2, self.dy)?;
+        }
+        if self.rotate != false {
+            os.write_bool(3, self.rotate)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> MouseEvent {
+        MouseEvent::new()
+    }
+
+    fn clear(&mut self) {
+        self.dx = 0.;
+        self.dy = 0.;
+        self.rotate = false;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static MouseEvent {
+        static instance: MouseEvent = MouseEvent {
+            dx: 0.,
+            dy: 0.,
+            rotate: false,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for MouseEvent {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("MouseEvent").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for MouseEvent {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MouseEvent {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:proto.net_packet.ScrollEvent)
+pub struct ScrollEvent {
+    // message fields
+    // @@protoc_insertion_point(field:proto.net_packet.ScrollEvent.lines)
+    pub lines: f32,
+    // special fields
+    // @@protoc_insertion_point(special_field:proto.net_packet.ScrollEvent.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a ScrollEvent {
+    fn default() -> &'a ScrollEvent {
+        <ScrollEvent as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ScrollEvent {
+    pub fn new() -> ScrollEvent {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "lines",
+            |m: &ScrollEvent| { &m.lines },
+            |m: &mut ScrollEvent| { &mut m.lines },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ScrollEvent>(
+            "ScrollEvent",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for ScrollEvent {
+    const NAME: &'static str = "ScrollEvent";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                13 => {
+                    self.lines = is.read_float()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.lines != 0. {
+            my_size += 1 + 4;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.lines != 0. {
+            os.write_float(1, self.lines)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> ScrollEvent {
+        ScrollEvent::new()
+    }
+
+    fn clear(&mut self) {
+        self.lines = 0.;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static ScrollEvent {
+        static instance: ScrollEvent = ScrollEvent {
+            lines: 0.,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for ScrollEvent {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("ScrollEvent").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for ScrollEvent {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ScrollEvent {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:proto.net_packet.Hello)
+pub struct Hello {
+    // message fields
+    // @@protoc_insertion_point(field:proto.net_packet.Hello.version)
+    pub version: u32,
+    // @@protoc_insertion_point(field:proto.net_packet.Hello.codecs)
+    pub codecs: ::std::vec::Vec<::protobuf::EnumOrUnknown<super::avpacket::Codec>>,
+    // @@protoc_insertion_point(field:proto.net_packet.Hello.columns)
+    pub columns: u32,
+    // @@protoc_insertion_point(field:proto.net_packet.Hello.rows)
+    pub rows: u32,
+    // @@protoc_insertion_point(field:proto.net_packet.Hello.token)
+    pub token: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:proto.net_packet.Hello.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Hello {
+    fn default() -> &'a Hello {
+        <Hello as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Hello {
+    pub fn new() -> Hello {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(5);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "version",
+            |m: &Hello| { &m.version },
+            |m: &mut Hello| { &mut m.version },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "codecs",
+            |m: &Hello| { &m.codecs },
+            |m: &mut Hello| { &mut m.codecs },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "columns",
+            |m: &Hello| { &m.columns },
+            |m: &mut Hello| { &mut m.columns },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "rows",
+            |m: &Hello| { &m.rows },
+            |m: &mut Hello| { &mut m.rows },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "token",
+            |m: &Hello| { &m.token },
+            |m: &mut Hello| { &mut m.token },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Hello>(
+            "Hello",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Hello {
+    const NAME: &'static str = "Hello";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.version = is.read_uint32()?;
+                },
+                18 => {
+                    ::protobuf::rt::read_repeated_packed_enum_or_unknown_into(is, &mut self.codecs)?
+                },
+                16 => {
+                    self.codecs.push(is.read_enum_or_unknown()?);
+                },
+                24 => {
+                    self.columns = is.read_uint32()?;
+                },
+                32 => {
+                    self.rows = is.read_uint32()?;
+                },
+                42 => {
+                    self.token = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.version != 0 {
+            my_size += ::protobuf::rt::uint32_size(1, self.version);
+        }
+        my_size += ::protobuf::rt::vec_packed_enum_or_unknown_size(2, &self.codecs);
+        if self.columns != 0 {
+            my_size += ::protobuf::rt::uint32_size(3, self.columns);
+        }
+        if self.rows != 0 {
+            my_size += ::protobuf::rt::uint32_size(4, self.rows);
+        }
+        if !self.token.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.token);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.version != 0 {
+            os.write_uint32(1, self.version)?;
+        }
+        os.write_repeated_packed_enum_or_unknown(2, &self.codecs)?;
+        if self.columns != 0 {
+            os.write_uint32(3, self.columns)?;
+        }
+        if self.rows != 0 {
+            os.write_uint32(4, self.rows)?;
+        }
+        if !self.token.is_empty() {
+            os.write_string(5, &self.token)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Hello {
+        Hello::new()
+    }
+
+    fn clear(&mut self) {
+        self.version = 0;
+        self.codecs.clear();
+        self.columns = 0;
+        self.rows = 0;
+        self.token.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Hello {
+        static instance: Hello = Hello {
+            version: 0,
+            codecs: ::std::vec::Vec::new(),
+            columns: 0,
+            rows: 0,
+            token: ::std::string::String::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Hello {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Hello").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Hello {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Hello {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:proto.net_packet.Welcome)
+pub struct Welcome {
+    // message fields
+    // @@protoc_insertion_point(field:proto.net_packet.Welcome.version)
+    pub version: u32,
+    // @@protoc_insertion_point(field:proto.net_packet.Welcome.header)
+    pub header: ::protobuf::MessageField<super::avpacket::VideoHeader>,
+    // @@protoc_insertion_point(field:proto.net_packet.Welcome.control)
+    pub control: bool,
+    // special fields
+    // @@protoc_insertion_point(special_field:proto.net_packet.Welcome.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Welcome {
+    fn default() -> &'a Welcome {
+        <Welcome as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Welcome {
+    pub fn new() -> Welcome {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "version",
+            |m: &Welcome| { &m.version },
+            |m: &mut Welcome| { &mut m.version },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, super::avpacket::VideoHeader>(
+            "header",
+            |m: &Welcome| { &m.header },
+            |m: &mut Welcome| { &mut m.header },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "control",
+            |m: &Welcome| { &m.control },
+            |m: &mut Welcome| { &mut m.control },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Welcome>(
+            "Welcome",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Welcome {
+    const NAME: &'static str = "Welcome";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.version = is.read_uint32()?;
+                },
+                18 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.header)?;
+                },
+                24 => {
+                    self.control = is.read_bool()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.version != 0 {
+            my_size += ::protobuf::rt::uint32_size(1, self.version);
+        }
+        if let Some(v) = self.header.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        if self.control != false {
+            my_size += 1 + 1;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.version != 0 {
+            os.write_uint32(1, self.version)?;
+        }
+        if let Some(v) = self.header.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(2, v, os)?;
+        }
+        if self.control != false {
+            os.write_bool(3, self.control)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Welcome {
+        Welcome::new()
+    }
+
+    fn clear(&mut self) {
+        self.version = 0;
+        self.header.clear();
+        self.control = false;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Welcome {
+        static instance: Welcome = Welcome {
+            version: 0,
+            header: ::protobuf::MessageField::none(),
+            control: false,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Welcome {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Welcome").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Welcome {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Welcome {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:proto.net_packet.SessionError)
+pub struct SessionError {
+    // message fields
+    // @@protoc_insertion_point(field:proto.net_packet.SessionError.reason)
+    pub reason: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:proto.net_packet.SessionError.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a SessionError {
+    fn default() -> &'a SessionError {
+        <SessionError as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl SessionError {
+    pub fn new() -> SessionError {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "reason",
+            |m: &SessionError| { &m.reason },
+            |m: &mut SessionError| { &mut m.reason },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<SessionError>(
+            "SessionError",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for SessionError {
+    const NAME: &'static str = "SessionError";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.reason = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.reason.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.reason);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.reason.is_empty() {
+            os.write_string(1, &self.reason)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> SessionError {
+        SessionError::new()
+    }
+
+    fn clear(&mut self) {
+        self.reason.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static SessionError {
+        static instance: SessionError = SessionError {
+            reason: ::std::string::String::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for SessionError {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("SessionError").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for SessionError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SessionError {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:proto.net_packet.KeyframeRequest)
+pub struct KeyframeRequest {
+    // message fields
+    // @@protoc_insertion_point(field:proto.net_packet.KeyframeRequest.reason)
+    pub reason: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:proto.net_packet.KeyframeRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a KeyframeRequest {
+    fn default() -> &'a KeyframeRequest {
+        <KeyframeRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl KeyframeRequest {
+    pub fn new() -> KeyframeRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "reason",
+            |m: &KeyframeRequest| { &m.reason },
+            |m: &mut KeyframeRequest| { &mut m.reason },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<KeyframeRequest>(
+            "KeyframeRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for KeyframeRequest {
+    const NAME: &'static str = "KeyframeRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.reason = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.reason.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.reason);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.reason.is_empty() {
+            os.write_string(1, &self.reason)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> KeyframeRequest {
+        KeyframeRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.reason.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static KeyframeRequest {
+        static instance: KeyframeRequest = KeyframeRequest {
+            reason: ::std::string::String::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for KeyframeRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("KeyframeRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for KeyframeRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for KeyframeRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:proto.net_packet.DiscoveryQuery)
+pub struct DiscoveryQuery {
+    // message fields
+    // @@protoc_insertion_point(field:proto.net_packet.DiscoveryQuery.version)
+    pub version: u32,
+    // special fields
+    // @@protoc_insertion_point(special_field:proto.net_packet.DiscoveryQuery.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a DiscoveryQuery {
+    fn default() -> &'a DiscoveryQuery {
+        <DiscoveryQuery as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl DiscoveryQuery {
+    pub fn new() -> DiscoveryQuery {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "version",
+            |m: &DiscoveryQuery| { &m.version },
+            |m: &mut DiscoveryQuery| { &mut m.version },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<DiscoveryQuery>(
+            "DiscoveryQuery",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for DiscoveryQuery {
+    const NAME: &'static str = "DiscoveryQuery";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.version = is.read_uint32()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.version != 0 {
+            my_size += ::protobuf::rt::uint32_size(1, self.version);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.version != 0 {
+            os.write_uint32(1, self.version)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> DiscoveryQuery {
+        DiscoveryQuery::new()
+    }
+
+    fn clear(&mut self) {
+        self.version = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static DiscoveryQuery {
+        static instance: DiscoveryQuery = DiscoveryQuery {
+            version: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for DiscoveryQuery {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("DiscoveryQuery").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for DiscoveryQuery {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DiscoveryQuery {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:proto.net_packet.Announcement)
+pub struct Announcement {
+    // message fields
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.version)
+    pub version: u32,
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.name)
+    pub name: ::std::string::String,
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.port)
+    pub port: u32,
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.model)
+    pub model: ::std::string::String,
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.width)
+    pub width: u32,
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.height)
+    pub height: u32,
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.fps)
+    pub fps: u32,
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.codecs)
+    pub codecs: ::std::vec::Vec<::protobuf::EnumOrUnknown<super::avpacket::Codec>>,
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.clients)
+    pub clients: u32,
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.discovery_port)
+    pub discovery_port: u32,
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.token_required)
+    pub token_required: bool,
+    // @@protoc_insertion_point(field:proto.net_packet.Announcement.tls)
+    pub tls: bool,
+    // special fields
+    // @@protoc_insertion_point(special_field:proto.net_packet.Announcement.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Announcement {
+    fn default() -> &'a Announcement {
+        <Announcement as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Announcement {
+    pub fn new() -> Announcement {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(12);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "version",
+            |m: &Announcement| { &m.version },
+            |m: &mut Announcement| { &mut m.version },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "name",
+            |m: &Announcement| { &m.name },
+            |m: &mut Announcement| { &mut m.name },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "port",
+            |m: &Announcement| { &m.port },
+            |m: &mut Announcement| { &mut m.port },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "model",
+            |m: &Announcement| { &m.model },
+            |m: &mut Announcement| { &mut m.model },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "width",
+            |m: &Announcement| { &m.width },
+            |m: &mut Announcement| { &mut m.width },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "height",
+            |m: &Announcement| { &m.height },
+            |m: &mut Announcement| { &mut m.height },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "fps",
+            |m: &Announcement| { &m.fps },
+            |m: &mut Announcement| { &mut m.fps },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "codecs",
+            |m: &Announcement| { &m.codecs },
+            |m: &mut Announcement| { &mut m.codecs },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "clients",
+            |m: &Announcement| { &m.clients },
+            |m: &mut Announcement| { &mut m.clients },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "discovery_port",
+            |m: &Announcement| { &m.discovery_port },
+            |m: &mut Announcement| { &mut m.discovery_port },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "token_required",
+            |m: &Announcement| { &m.token_required },
+            |m: &mut Announcement| { &mut m.token_required },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "tls",
+            |m: &Announcement| { &m.tls },
+            |m: &mut Announcement| { &mut m.tls },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Announcement>(
+            "Announcement",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Announcement {
+    const NAME: &'static str = "Announcement";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.version = is.read_uint32()?;
+                },
+                18 => {
+                    self.name = is.read_string()?;
+                },
+                24 => {
+                    self.port = is.read_uint32()?;
+                },
+                34 => {
+                    self.model = is.read_string()?;
+                },
+                40 => {
+                    self.width = is.read_uint32()?;
+                },
+                48 => {
+                    self.height = is.read_uint32()?;
+                },
+                56 => {
+                    self.fps = is.read_uint32()?;
+                },
+                66 => {
+                    ::protobuf::rt::read_repeated_packed_enum_or_unknown_into(is, &mut self.codecs)?
+                },
+                64 => {
+                    self.codecs.push(is.read_enum_or_unknown()?);
+                },
+                72 => {
+                    self.clients = is.read_uint32()?;
+                },
+                80 => {
+                    self.discovery_port = is.read_uint32()?;
+                },
+                88 => {
+                    self.token_required = is.read_bool()?;
+                },
+                96 => {
+                    self.tls = is.read_bool()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.version != 0 {
+            my_size += ::protobuf::rt::uint32_size(1, self.version);
+        }
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.name);
+        }
+        if self.port != 0 {
+            my_size += ::protobuf::rt::uint32_size(3, self.port);
+        }
+        if !self.model.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.model);
+        }
+        if self.width != 0 {
+            my_size += ::protobuf::rt::uint32_size(5, self.width);
+        }
+        if self.height != 0 {
+            my_size += ::protobuf::rt::uint32_size(6, self.height);
+        }
+        if self.fps != 0 {
+            my_size += ::protobuf::rt::uint32_size(7, self.fps);
+        }
+        my_size += ::protobuf::rt::vec_packed_enum_or_unknown_size(8, &self.codecs);
+        if self.clients != 0 {
+            my_size += ::protobuf::rt::uint32_size(9, self.clients);
+        }
+        if self.discovery_port != 0 {
+            my_size += ::protobuf::rt::uint32_size(10, self.discovery_port);
+        }
+        if self.token_required != false {
+            my_size += 1 + 1;
+        }
+        if self.tls != false {
+            my_size += 1 + 1;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.version != 0 {
+            os.write_uint32(1, self.version)?;
+        }
+        if !self.name.is_empty() {
+            os.write_string(2, &self.name)?;
+        }
+        if self.port != 0 {
+            os.write_uint32(3, self.port)?;
+        }
+        if !self.model.is_empty() {
+            os.write_string(4, &self.model)?;
+        }
+        if self.width != 0 {
+            os.write_uint32(5, self.width)?;
+        }
+        if self.height != 0 {
+            os.write_uint32(6, self.height)?;
+        }
+        if self.fps != 0 {
+            os.write_uint32(7, self.fps)?;
+        }
+        os.write_repeated_packed_enum_or_unknown(8, &self.codecs)?;
+        if self.clients != 0 {
+            os.write_uint32(9, self.clients)?;
+        }
+        if self.discovery_port != 0 {
+            os.write_uint32(10, self.discovery_port)?;
+        }
+        if self.token_required != false {
+            os.write_bool(11, self.token_required)?;
+        }
+        if self.tls != false {
+            os.write_bool(12, self.tls)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Announcement {
+        Announcement::new()
+    }
+
+    fn clear(&mut self) {
+        self.version = 0;
+        self.name.clear();
+        self.port = 0;
+        self.model.clear();
+        self.width = 0;
+        self.height = 0;
+        self.fps = 0;
+        self.codecs.clear();
+        self.clients = 0;
+        self.discovery_port = 0;
+        self.token_required = false;
+        self.tls = false;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Announcement {
+        static instance: Announcement = Announcement {
+            version: 0,
+            name: ::std::string::String::new(),
+            port: 0,
+            model: ::std::string::String::new(),
+            width: 0,
+            height: 0,
+            fps: 0,
+            codecs: ::std::vec::Vec::new(),
+            clients: 0,
+            discovery_port: 0,
+            token_required: false,
+            tls: false,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Announcement {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Announcement").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Announcement {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Announcement {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
 #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
 // @@protoc_insertion_point(enum:proto.net_packet.PacketKind)
 pub enum PacketKind {
     // @@protoc_insertion_point(enum_value:proto.net_packet.PacketKind.VideoPacket)
     VideoPacket = 0,
+    // @@protoc_insertion_point(enum_value:proto.net_packet.PacketKind.KeyInput)
+    KeyInput = 1,
+    // @@protoc_insertion_point(enum_value:proto.net_packet.PacketKind.MouseInput)
+    MouseInput = 2,
+    // @@protoc_insertion_point(enum_value:proto.net_packet.PacketKind.ScrollInput)
+    ScrollInput = 3,
+    // @@protoc_insertion_point(enum_value:proto.net_packet.PacketKind.Hello)
+    Hello = 4,
+    // @@protoc_insertion_point(enum_value:proto.net_packet.PacketKind.Welcome)
+    Welcome = 5,
+    // @@protoc_insertion_point(enum_value:proto.net_packet.PacketKind.Error)
+    Error = 6,
+    // @@protoc_insertion_point(enum_value:proto.net_packet.PacketKind.KeyframeRequest)
+    KeyframeRequest = 7,
+    // @@protoc_insertion_point(enum_value:proto.net_packet.PacketKind.DiscoveryQuery)
+    DiscoveryQuery = 8,
+    // @@protoc_insertion_point(enum_value:proto.net_packet.PacketKind.Announcement)
+    Announcement = 9,
 }
 
 impl ::protobuf::Enum for PacketKind {
@@ -182,12 +1675,30 @@ impl ::protobuf::Enum for PacketKind {
     fn from_i32(value: i32) -> ::std::option::Option<PacketKind> {
         match value {
             0 => ::std::option::Option::Some(PacketKind::VideoPacket),
+            1 => ::std::option::Option::Some(PacketKind::KeyInput),
+            2 => ::std::option::Option::Some(PacketKind::MouseInput),
+            3 => ::std::option::Option::Some(PacketKind::ScrollInput),
+            4 => ::std::option::Option::Some(PacketKind::Hello),
+            5 => ::std::option::Option::Some(PacketKind::Welcome),
+            6 => ::std::option::Option::Some(PacketKind::Error),
+            7 => ::std::option::Option::Some(PacketKind::KeyframeRequest),
+            8 => ::std::option::Option::Some(PacketKind::DiscoveryQuery),
+            9 => ::std::option::Option::Some(PacketKind::Announcement),
             _ => ::std::option::Option::None
         }
     }
 
     const VALUES: &'static [PacketKind] = &[
         PacketKind::VideoPacket,
+        PacketKind::KeyInput,
+        PacketKind::MouseInput,
+        PacketKind::ScrollInput,
+        PacketKind::Hello,
+        PacketKind::Welcome,
+        PacketKind::Error,
+        PacketKind::KeyframeRequest,
+        PacketKind::DiscoveryQuery,
+        PacketKind::Announcement,
     ];
 }
 
@@ -216,21 +1727,40 @@ impl PacketKind {
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x0fnetpacket.proto\x12\x10proto.net_packet\"Q\n\tNetPacket\x120\n\x04\
-    kind\x18\x01\x20\x01(\x0e2\x1c.proto.net_packet.PacketKindR\x04kind\x12\
-    \x12\n\x04data\x18\x02\x20\x01(\x0cR\x04data*\x1d\n\nPacketKind\x12\x0f\
-    \n\x0bVideoPacket\x10\0J\xe3\x01\n\x06\x12\x04\0\0\x0c\x01\n\x08\n\x01\
-    \x0c\x12\x03\0\0\x12\n\x08\n\x01\x02\x12\x03\x01\0\x19\n\n\n\x02\x05\0\
-    \x12\x04\x04\0\x06\x01\n\n\n\x03\x05\0\x01\x12\x03\x04\x05\x0f\n\x0b\n\
-    \x04\x05\0\x02\0\x12\x03\x05\x02\x12\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03\
-    \x05\x02\r\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\x05\x10\x11\n\n\n\x02\x04\
-    \0\x12\x04\t\0\x0c\x01\n\n\n\x03\x04\0\x01\x12\x03\t\x08\x11\n\x0b\n\x04\
-    \x04\0\x02\0\x12\x03\n\x02\x16\n\x0c\n\x05\x04\0\x02\0\x06\x12\x03\n\x02\
-    \x0c\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\n\r\x11\n\x0c\n\x05\x04\0\x02\0\
-    \x03\x12\x03\n\x14\x15\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x0b\x02\x11\n\
-    \x0c\n\x05\x04\0\x02\x01\x05\x12\x03\x0b\x02\x07\n\x0c\n\x05\x04\0\x02\
-    \x01\x01\x12\x03\x0b\x08\x0c\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x0b\
-    \x0f\x10b\x06proto3\
+    \n\x0fnetpacket.proto\x12\x10proto.net_packet\x1a\x0eavpacket.proto\"Q\
+    \n\tNetPacket\x120\n\x04kind\x18\x01\x20\x01(\x0e2\x1c.proto.net_packet\
+    .PacketKindR\x04kind\x12\x12\n\x04data\x18\x02\x20\x01(\x0cR\x04data\"R\
+    \n\x08KeyEvent\x12\x16\n\x06action\x18\x01\x20\x01(\tR\x06action\x12\
+    \x18\n\x07pressed\x18\x02\x20\x01(\x08R\x07pressed\x12\x14\n\x05model\
+    \x18\x03\x20\x01(\x08R\x05model\"D\n\nMouseEvent\x12\x0e\n\x02dx\x18\
+    \x01\x20\x01(\x02R\x02dx\x12\x0e\n\x02dy\x18\x02\x20\x01(\x02R\x02dy\
+    \x12\x16\n\x06rotate\x18\x03\x20\x01(\x08R\x06rotate\"#\n\x0bScrollEven\
+    t\x12\x14\n\x05lines\x18\x01\x20\x01(\x02R\x05lines\"\x94\x01\n\x05Hell\
+    o\x12\x18\n\x07version\x18\x01\x20\x01(\rR\x07version\x12-\n\x06codecs\
+    \x18\x02\x20\x03(\x0e2\x15.proto.avpacket.CodecR\x06codecs\x12\x18\n\
+    \x07columns\x18\x03\x20\x01(\rR\x07columns\x12\x12\n\x04rows\x18\x04\
+    \x20\x01(\rR\x04rows\x12\x14\n\x05token\x18\x05\x20\x01(\tR\x05token\"r\
+    \n\x07Welcome\x12\x18\n\x07version\x18\x01\x20\x01(\rR\x07version\x123\
+    \n\x06header\x18\x02\x20\x01(\x0b2\x1b.proto.avpacket.VideoHeaderR\x06h\
+    eader\x12\x18\n\x07control\x18\x03\x20\x01(\x08R\x07control\"&\n\x0cSes\
+    sionError\x12\x16\n\x06reason\x18\x01\x20\x01(\tR\x06reason\")\n\x0fKey\
+    frameRequest\x12\x16\n\x06reason\x18\x01\x20\x01(\tR\x06reason\"*\n\x0e\
+    DiscoveryQuery\x12\x18\n\x07version\x18\x01\x20\x01(\rR\x07version\"\
+    \xcf\x02\n\x0cAnnouncement\x12\x18\n\x07version\x18\x01\x20\x01(\rR\x07\
+    version\x12\x12\n\x04name\x18\x02\x20\x01(\tR\x04name\x12\x12\n\x04port\
+    \x18\x03\x20\x01(\rR\x04port\x12\x14\n\x05model\x18\x04\x20\x01(\tR\x05\
+    model\x12\x14\n\x05width\x18\x05\x20\x01(\rR\x05width\x12\x16\n\x06heig\
+    ht\x18\x06\x20\x01(\rR\x06height\x12\x10\n\x03fps\x18\x07\x20\x01(\rR\
+    \x03fps\x12-\n\x06codecs\x18\x08\x20\x03(\x0e2\x15.proto.avpacket.Codec\
+    R\x06codecs\x12\x18\n\x07clients\x18\t\x20\x01(\rR\x07clients\x12%\n\
+    \x0ediscovery_port\x18\n\x20\x01(\rR\rdiscoveryPort\x12%\n\x0etoken_req\
+    uired\x18\x0b\x20\x01(\x08R\rtokenRequired\x12\x10\n\x03tls\x18\x0c\x20\
+    \x01(\x08R\x03tls*\xaa\x01\n\nPacketKind\x12\x0f\n\x0bVideoPacket\x10\0\
+    \x12\x0c\n\x08KeyInput\x10\x01\x12\x0e\n\nMouseInput\x10\x02\x12\x0f\n\
+    \x0bScrollInput\x10\x03\x12\t\n\x05Hello\x10\x04\x12\x0b\n\x07Welcome\
+    \x10\x05\x12\t\n\x05Error\x10\x06\x12\x13\n\x0fKeyframeRequest\x10\x07\
+    \x12\x12\n\x0eDiscoveryQuery\x10\x08\x12\x10\n\x0cAnnouncement\x10\tb\
+    \x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -247,9 +1777,19 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
     static file_descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::FileDescriptor> = ::protobuf::rt::Lazy::new();
     file_descriptor.get(|| {
         let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
-            let mut deps = ::std::vec::Vec::with_capacity(0);
-            let mut messages = ::std::vec::Vec::with_capacity(1);
+            let mut deps = ::std::vec::Vec::with_capacity(1);
+            deps.push(super::avpacket::file_descriptor().clone());
+            let mut messages = ::std::vec::Vec::with_capacity(10);
             messages.push(NetPacket::generated_message_descriptor_data());
+            messages.push(KeyEvent::generated_message_descriptor_data());
+            messages.push(MouseEvent::generated_message_descriptor_data());
+            messages.push(ScrollEvent::generated_message_descriptor_data());
+            messages.push(Hello::generated_message_descriptor_data());
+            messages.push(Welcome::generated_message_descriptor_data());
+            messages.push(SessionError::generated_message_descriptor_data());
+            messages.push(KeyframeRequest::generated_message_descriptor_data());
+            messages.push(DiscoveryQuery::generated_message_descriptor_data());
+            messages.push(Announcement::generated_message_descriptor_data());
             let mut enums = ::std::vec::Vec::with_capacity(1);
             enums.push(PacketKind::generated_enum_descriptor_data());
             ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
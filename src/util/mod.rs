@@ -4,6 +4,11 @@ pub mod cmd_arg;
 
 use lazy_static::lazy_static;
 
+use crate::department::net::remote_input::ControlPolicy;
+use crate::department::preview::cell_encoder::CellMode;
+use crate::department::preview::graphics::GraphicsProtocol;
+use crate::department::preview::palette::{ColorDepth, Dither};
+
 
 /// render a object to window or terminal
 #[derive(Parser, Debug)]
@@ -23,9 +28,114 @@ pub struct Args {
     #[arg(long, default_value_t=String::from("./res/plane/plane.obj"))]
     pub obj_path: String,
 
+    /// directory listed by the model browser of the terminal ui.
+    #[arg(long, default_value_t=String::from("./res"))]
+    pub model_dir: String,
+
+    /// number of model instances, spawned in a grid.
+    #[arg(long, default_value_t=1)]
+    pub instances: usize,
+
+    /// scene file placing the model instances, lines like `x y z yaw`. wins over --instances.
+    #[arg(long)]
+    pub scene: Option<String>,
+
     /// only render a jpeg picture
     #[arg(short, default_value_t=false)]
     pub render_a_picture: bool,
+
+    /// how terminal cells encode pixels, finer modes render at a higher resolution.
+    #[arg(long, value_enum, default_value_t=CellMode::Dot)]
+    pub cell: CellMode,
+
+    /// terminal color depth, detected from COLORTERM/TERM when not given.
+    #[arg(long, value_enum)]
+    pub color: Option<ColorDepth>,
+
+    /// dithering used when the color depth is lower than truecolor.
+    #[arg(long, value_enum, default_value_t=Dither::None)]
+    pub dither: Dither,
+
+    /// image protocol of the terminal, detected by querying the terminal when not given.
+    #[arg(long, value_enum)]
+    pub graphics: Option<GraphicsProtocol>,
+
+    /// key bindings file, lines like `forward = w, up`. see --print-keymap for every action.
+    #[arg(long)]
+    pub keymap: Option<String>,
+
+    /// print the key bindings in the keymap file format and exit.
+    #[arg(long, default_value_t=false)]
+    pub print_keymap: bool,
+
+    /// write the controller input of every frame to this file.
+    #[arg(long)]
+    pub record: Option<String>,
+
+    /// feed the controller from a file written by --record instead of live input.
+    /// with -r the replay is rendered headless, one png per frame.
+    #[arg(long)]
+    pub replay: Option<String>,
+
+    /// directory for the frames of a headless replay.
+    #[arg(long, default_value_t=String::from("./replay"))]
+    pub replay_frames: String,
+
+    /// which connected viewers may drive the camera and model.
+    #[arg(long, value_enum, default_value_t=ControlPolicy::Single)]
+    pub remote_control: ControlPolicy,
+
+    /// name the server announces on the lan, the host name when not given.
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// network config file, lines like `port = 9527`. see --print-net-config for every key.
+    #[arg(long)]
+    pub net_config: Option<String>,
+
+    /// print the network config in the config file format and exit.
+    #[arg(long, default_value_t=false)]
+    pub print_net_config: bool,
+
+    /// address to bind the stream and discovery to, like 127.0.0.1 to stay off the lan.
+    #[arg(long)]
+    pub bind: Option<String>,
+
+    /// first tcp port tried for the stream, 0 lets the system pick one.
+    #[arg(long)]
+    pub port: Option<u32>,
+
+    /// first udp port tried for discovery.
+    #[arg(long)]
+    pub udp_port: Option<u32>,
+
+    /// number of ports tried when the first one is taken.
+    #[arg(long)]
+    pub port_range: Option<u32>,
+
+    /// clients watching at once, 0 for no limit.
+    #[arg(long)]
+    pub max_clients: Option<usize>,
+
+    /// kilobytes per second sent to one client, 0 for no limit.
+    #[arg(long)]
+    pub client_bandwidth: Option<u64>,
+
+    /// token viewers need to watch, the control token works as well.
+    #[arg(long)]
+    pub view_token: Option<String>,
+
+    /// token viewers need to send input, without one every viewer may.
+    #[arg(long)]
+    pub control_token: Option<String>,
+
+    /// pem certificate chain for tls on the stream port, needs --tls-key.
+    #[arg(long)]
+    pub tls_cert: Option<String>,
+
+    /// pem private key of --tls-cert.
+    #[arg(long)]
+    pub tls_key: Option<String>,
 }
 
 
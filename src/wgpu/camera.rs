@@ -4,6 +4,7 @@ use cgmath::*;
 
 use std::f32::consts::FRAC_PI_2;
 
+use crate::department::pick::{self, Ray};
 use crate::department::view::camera_trait;
 
 
@@ -18,7 +19,7 @@ pub const OPENGL_TO_WGPU_MATRIX: cgmath::Matrix4<f32> = cgmath::Matrix4::new(
 const SAFE_FRAC_PI_2: f32 = FRAC_PI_2 - 0.0001;
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Camera {
     pub position: Point3<f32>,
     pub(crate) yaw: Rad<f32>,
@@ -73,6 +74,11 @@ impl camera_trait::CameraTrait for Camera {
     fn update_projection(&mut self, width: u32, height: u32) {
         self.proj.resize(width, height);
     }
+
+    fn screen_ray(&self, ndc: (f32, f32)) -> Ray {
+        let inverse = (self.proj.calc_matrix() * self.calc_matrix()).invert().unwrap_or_else(Matrix4::identity);
+        pick::unproject(&inverse, ndc, self.position, self.direction())
+    }
 }
 
 impl Camera {
@@ -98,20 +104,25 @@ impl Camera {
         self.proj = proj;
     }
 
+    /// where the camera looks.
+    pub fn direction(&self) -> Vector3<f32> {
+        Vector3::new(
+            self.yaw.0.cos(),
+            self.pitch.0.sin(),
+            self.yaw.0.sin(),
+        ).normalize()
+    }
+
     pub fn calc_matrix(&self) -> Matrix4<f32> {
         Matrix4::look_to_rh(
             self.position,
-            Vector3::new(
-                self.yaw.0.cos(),
-                self.pitch.0.sin(),
-                self.yaw.0.sin(),
-            ).normalize(),
+            self.direction(),
             Vector3::unit_y(),
         )
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Projection {
     aspect: f32,
     fovy: Rad<f32>,
@@ -1,5 +1,8 @@
 use pixels::wgpu;
 
+use crate::department::preview::homo_transformation::HomoTransform;
+
+#[derive(Clone, Debug)]
 pub struct Instance {
     pub position: cgmath::Vector3<f32>,
     pub rotation: cgmath::Quaternion<f32>,
@@ -26,6 +29,11 @@ pub struct InstanceRaw {
 
 
 impl InstanceRaw {
+    /// the model matrix for the cpu rasterizer, which multiplies row vectors from the left.
+    pub fn model_transform(&self) -> HomoTransform {
+        HomoTransform::from_vec(self.model.iter().flatten().cloned().collect())
+    }
+
     pub fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
         use std::mem;
         wgpu::VertexBufferLayout {
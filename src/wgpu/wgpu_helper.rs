@@ -13,7 +13,7 @@ use winit::{
 use std::time::{Duration};
 use crate::department::types::msg::TransferMsg;
 use crate::department::types::multi_sender::MultiSender;
-use crate::department::common::constant::{WIDTH, HEIGHT};
+use crate::department::common::constant::{WIDTH, HEIGHT, TUI_WIDTH, TUI_HEIGHT};
 use crossbeam_channel::Receiver;
 use lazy_static::lazy_static;
 use log::info;
@@ -36,10 +36,35 @@ use crate::wgpu::light::LightUniform;
 
 use crate::util::{ARG};
 use crate::department::control::camera_controller::CameraController;
+use crate::department::control::ModelController;
+use crate::department::model::scene;
+use crate::department::pick::{self, Hit, PickScene};
 
 
 const NUM_INSTANCES_PER_ROW: u32 = 10;
 
+// texture to buffer copies need every row aligned to COPY_BYTES_PER_ROW_ALIGNMENT (256 bytes),
+// so rows are padded on the gpu side and the padding is stripped after read back.
+fn padded_bytes_per_row(width: u32) -> u32 {
+    let unpadded = width * std::mem::size_of::<u32>() as u32;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    (unpadded + align - 1) / align * align
+}
+
+fn unpad_rows(data: &[u8], size: (u32, u32)) -> Vec<u8> {
+    let padded = padded_bytes_per_row(size.0) as usize;
+    let unpadded = size.0 as usize * std::mem::size_of::<u32>();
+    if padded == unpadded {
+        return data.to_vec();
+    }
+
+    let mut out = Vec::with_capacity(unpadded * size.1 as usize);
+    for row in data.chunks(padded).take(size.1 as usize) {
+        out.extend_from_slice(&row[..unpadded]);
+    }
+    out
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 struct CameraUniform {
@@ -72,13 +97,16 @@ pub struct State<T> where T: camera_trait::CameraTrait {
     queue: wgpu::Queue,
     render_pipeline: wgpu::RenderPipeline,
     obj_model: model::Model,
+    texture_bind_group_layout: wgpu::BindGroupLayout,
     light_model: model::Model,
     camera: T,
     pub camera_controller: CameraController,
     camera_uniform: CameraUniform,
     camera_buffer: wgpu::Buffer,
     camera_bind_group: wgpu::BindGroup,
-    instances: Vec<Instance>,
+    tui_camera_buffer: wgpu::Buffer,
+    tui_camera_bind_group: wgpu::BindGroup,
+    instance_count: usize,
     #[allow(dead_code)]
     instance_buffer: wgpu::Buffer,
     depth_texture: texture::Texture,
@@ -90,9 +118,21 @@ pub struct State<T> where T: camera_trait::CameraTrait {
     light_render_pipeline: wgpu::RenderPipeline,
     pub mouse_pressed: bool,
     pub scale_factor: f64,
+    // cpu side copy of the model meshes for picking, None when it couldn't be read.
+    picker: Option<PickScene>,
     light_degree: u32,
 }
 
+fn load_picker(path: &str) -> Option<PickScene> {
+    match PickScene::load(path) {
+        Ok(scene) => Some(scene),
+        Err(e) => {
+            log::error!("picking disabled, could not read {}: {}", path, e);
+            None
+        }
+    }
+}
+
 impl<T> State<T> where T: camera_trait::CameraTrait {
     pub async fn new(size: LogicalSize<u32>, camera: T) -> Self {
         // The instance is a handle to our GPU
@@ -147,7 +187,8 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
             });
         //let camera = camera::Camera::new((0.0, 0., 10.), cgmath::Deg(-90.0), cgmath::Deg(-0.0));
         //let projection = camera::Projection::new(size.width, size.height, cgmath::Deg(45.), 0.1, 100.0);
-        let camera_controller = CameraController::new(2.0, 0.2, false);
+        let mut camera_controller = CameraController::new(2.0, 0.2, false);
+        camera_controller.model_ctrl.set_instances(scene::from_args());
 
         let mut camera_uniform = CameraUniform::new();
         camera_uniform.update_view_proj(&camera);
@@ -158,15 +199,9 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
-        const SPACE_BETWEEN: f32 = 3.0;
-        
-        let position = cgmath::Vector3 { x:0., y: 0., z: -5. };
-
-        let rotation = cgmath::Quaternion::from_axis_angle(position.normalize(), cgmath::Deg(0.0));
-        let instances = vec![Instance{position, rotation}];
+        let instances = camera_controller.model_ctrl.instances().to_vec();
 
         let instance_data = instances.iter().map(Instance::to_raw).collect::<Vec<_>>();
-        println!("instance {:?}", instance_data.len());
         let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Instance Buffer"),
             contents: bytemuck::cast_slice(&instance_data),
@@ -197,6 +232,21 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
             label: Some("camera_bind_group"),
         });
 
+        // the terminal target has an aspect of its own, its pass reads the camera from this buffer.
+        let tui_camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Tui Camera Buffer"),
+            contents: bytemuck::cast_slice(&[camera_uniform]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let tui_camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &camera_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: tui_camera_buffer.as_entire_binding(),
+            }],
+            label: Some("tui_camera_bind_group"),
+        });
+
         log::warn!("Load model");
         let obj_model = resources::load_model(
             &ARG.obj_path,
@@ -215,7 +265,7 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
         let depth_texture =
             texture::Texture::create_depth_texture(&device, (size.width, size.height), "depth_texture");
 
-        let tui_depth_texture = texture::Texture::create_depth_texture(&device, (256, 79), "tui_depth_texture");
+        let tui_depth_texture = texture::Texture::create_depth_texture(&device, (TUI_WIDTH, TUI_HEIGHT), "tui_depth_texture");
 
         let light_uniform = LightUniform::default();
 
@@ -283,17 +333,20 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
         };
 
         Self {
-            tui_size: (256, 79),
+            tui_size: (TUI_WIDTH, TUI_HEIGHT),
             device,
             queue,
             render_pipeline,
             obj_model,
+            texture_bind_group_layout,
             camera,
             camera_controller,
             camera_buffer,
             camera_bind_group,
+            tui_camera_buffer,
+            tui_camera_bind_group,
             camera_uniform,
-            instances,
+            instance_count: instance_data.len(),
             instance_buffer,
             depth_texture,
             tui_depth_texture,
@@ -305,15 +358,13 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
             light_render_pipeline,
             mouse_pressed: false,
             scale_factor: 1.0f64,
+            picker: load_picker(&ARG.obj_path),
             light_degree: 0,
         }
     }
 
     pub fn resize(&mut self, new_size: PhysicalSize<u32>, scale_factor: f64) {
         let logical_size = new_size.to_logical::<u32>(scale_factor);
-        if logical_size.width % 256 != 0 {
-            return ;
-        }
         if logical_size.width > 0 && logical_size.height > 0 {
             
             info!("window update from {:?} to {:?}", self.size, logical_size);
@@ -324,6 +375,57 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
         }
     }
 
+    // resize the secondary target rendered for the terminal when the window is also shown.
+    pub fn resize_tui(&mut self, size: (u32, u32)) {
+        if size.0 == 0 || size.1 == 0 || size == self.tui_size {
+            return;
+        }
+        info!("tui target update from {:?} to {:?}", self.tui_size, size);
+        self.tui_size = size;
+        self.tui_depth_texture = texture::Texture::create_depth_texture(&self.device, size, "tui_depth_texture");
+    }
+
+    pub fn update_projection(&mut self, width: u32, height: u32) {
+        self.camera.update_projection(width, height);
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.size.width, self.size.height)
+    }
+
+    pub fn camera(&self) -> &T {
+        &self.camera
+    }
+
+    /// replace the displayed model, the old one is kept if the new one fails to load.
+    pub fn load_model(&mut self, path: &str) -> anyhow::Result<()> {
+        let obj_model = pollster::block_on(resources::load_model(
+            path,
+            &self.device,
+            &self.queue,
+            &self.texture_bind_group_layout,
+        ))?;
+        self.obj_model = obj_model;
+        self.picker = load_picker(path);
+        Ok(())
+    }
+
+    pub fn picker(&self) -> Option<&PickScene> {
+        self.picker.as_ref()
+    }
+
+    /// what is under `ndc` of a target rendered at `size`, with the instances placed by `models`.
+    pub fn pick(&self, ndc: (f32, f32), size: (u32, u32), models: &ModelController) -> Option<Hit> where T: Clone {
+        let instances: Vec<cgmath::Matrix4<f32>> = models.instances().iter()
+            .map(|i| cgmath::Matrix4::from(i.to_raw().model))
+            .collect();
+        self.picker.as_ref()?.pick(&pick::target_ray(&self.camera, ndc, size), &instances)
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.obj_model.meshes.iter().map(|m| m.num_elements as usize / 3).sum()
+    }
+
     pub fn input(&mut self, event: &DeviceEvent) -> bool {
         match event {
             DeviceEvent::Key(
@@ -355,7 +457,7 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
     }
 
     pub fn update(&mut self, dt: Duration) {
-        self.camera_controller.update_camera(&mut self.camera, dt);
+        let dt = self.camera_controller.update_camera(&mut self.camera, dt);
         self.camera_uniform.update_view_proj(&self.camera);
         self.queue.write_buffer(
             &self.camera_buffer,
@@ -364,11 +466,7 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
         );
 
         let data = self.camera_controller.model_ctrl.update_model(dt);
-        self.queue.write_buffer(
-            &self.instance_buffer,
-            0,
-            bytemuck::cast_slice(&data)
-        );
+        self.write_instances(&data);
 
         let old_position: cgmath::Vector3<_> = self.light_uniform.position.into();
         self.light_uniform.position =
@@ -379,8 +477,23 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
 
     }
 
+    // the controller driving the frame may place a different number of instances than the
+    // buffer was made for, then the buffer is made again.
+    fn write_instances(&mut self, data: &[InstanceRaw]) {
+        if data.len() == self.instance_count {
+            self.queue.write_buffer(&self.instance_buffer, 0, bytemuck::cast_slice(data));
+            return;
+        }
+        self.instance_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Instance Buffer"),
+            contents: bytemuck::cast_slice(data),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+        self.instance_count = data.len();
+    }
+
     pub fn update_outside(&mut self, controller:&mut CameraController,dt: Duration) {
-        controller.update_camera(&mut self.camera, dt);
+        let dt = controller.update_camera(&mut self.camera, dt);
         self.camera_uniform.update_view_proj(&self.camera);
         self.queue.write_buffer(
             &self.camera_buffer,
@@ -389,11 +502,7 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
         );
 
         let data = controller.model_ctrl.update_model(dt);
-        self.queue.write_buffer(
-            &self.instance_buffer,
-            0,
-            bytemuck::cast_slice(&data)
-        );
+        self.write_instances(&data);
 
         let old_position: cgmath::Vector3<_> = self.light_uniform.position.into();
 
@@ -420,9 +529,9 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
                 label: Some("Render Encoder"),
             });
 
-        let (texture_desc, texture) = self.encode_a_new_render_texutre(&mut encoder, (self.size.width, self.size.height), &self.depth_texture);
-        let u32_size = std::mem::size_of::<u32>() as u32;
-        let output_buffer = self.create_output_buffer((self.size.width, self.size.height),u32_size);
+        let (texture_desc, texture) = self.encode_a_new_render_texutre(&mut encoder, (self.size.width, self.size.height),
+                                                                       &self.depth_texture, &self.camera_bind_group);
+        let output_buffer = self.create_output_buffer((self.size.width, self.size.height));
 
         encoder.copy_texture_to_buffer(
             wgpu::ImageCopyTexture {
@@ -435,16 +544,24 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
                 buffer: &output_buffer,
                 layout: wgpu::ImageDataLayout {
                     offset: 0,
-                    bytes_per_row: NonZeroU32::new(u32_size * self.size.width),
+                    bytes_per_row: NonZeroU32::new(padded_bytes_per_row(self.size.width)),
                     rows_per_image: NonZeroU32::new(self.size.height),
                 },
             },
             texture_desc.size,
         );
 
-        let tui_output_buffer = self.create_output_buffer(self.tui_size, u32_size);
+        let tui_output_buffer = self.create_output_buffer(self.tui_size);
         if tui_with_window {
-            let (tui_desc, tui_texture) = self.encode_a_new_render_texutre(&mut encoder, (self.tui_size.0, self.tui_size.1), &self.tui_depth_texture);
+            // same camera, projected for the aspect of the terminal instead of the window.
+            self.camera.update_projection(self.tui_size.0, self.tui_size.1);
+            let mut tui_uniform = CameraUniform::new();
+            tui_uniform.update_view_proj(&self.camera);
+            self.camera.update_projection(self.size.width, self.size.height);
+            self.queue.write_buffer(&self.tui_camera_buffer, 0, bytemuck::cast_slice(&[tui_uniform]));
+
+            let (tui_desc, tui_texture) = self.encode_a_new_render_texutre(&mut encoder, (self.tui_size.0, self.tui_size.1),
+                                                                           &self.tui_depth_texture, &self.tui_camera_bind_group);
 
 
             encoder.copy_texture_to_buffer(
@@ -458,7 +575,7 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
                     buffer: &tui_output_buffer,
                     layout: wgpu::ImageDataLayout {
                         offset: 0,
-                        bytes_per_row: NonZeroU32::new(u32_size * self.tui_size.0),
+                        bytes_per_row: NonZeroU32::new(padded_bytes_per_row(self.tui_size.0)),
                         rows_per_image: NonZeroU32::new(self.tui_size.1),
                     },
                 },
@@ -491,18 +608,18 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
             pollster::block_on(rx.receive());
 
             let data = buffer_slice.get_mapped_range();
-            ret_buf = data.iter().map(|x| *x).collect();
+            ret_buf = unpad_rows(&data, (self.size.width, self.size.height));
             if tui_with_window {
                 let data = tui_slice.unwrap().get_mapped_range();
-                tui_buf = Some(data.iter().map(|x| *x).collect());
+                tui_buf = Some(unpad_rows(&data, self.tui_size));
             }
         }
         output_buffer.unmap();
         (ret_buf, tui_buf)
     }
 
-    fn create_output_buffer(&self, size: (u32, u32), u32_size: u32) -> wgpu::Buffer {
-        let output_buffer_size = (u32_size * size.0 * size.1) as wgpu::BufferAddress;
+    fn create_output_buffer(&self, size: (u32, u32)) -> wgpu::Buffer {
+        let output_buffer_size = (padded_bytes_per_row(size.0) * size.1) as wgpu::BufferAddress;
         let output_buffer_desc = wgpu::BufferDescriptor {
             size: output_buffer_size,
             usage: wgpu::BufferUsages::COPY_DST
@@ -515,7 +632,8 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
         output_buffer
     }
 
-    fn encode_a_new_render_texutre(&self, encoder: &mut wgpu::CommandEncoder, w_h: (u32, u32), depth_texture: &texture::Texture) -> (wgpu::TextureDescriptor, wgpu::Texture) {
+    fn encode_a_new_render_texutre(&self, encoder: &mut wgpu::CommandEncoder, w_h: (u32, u32), depth_texture: &texture::Texture,
+                                   camera_bind_group: &wgpu::BindGroup) -> (wgpu::TextureDescriptor, wgpu::Texture) {
         let texture_desc = wgpu::TextureDescriptor {
             size: wgpu::Extent3d {
                 width: w_h.0,
@@ -540,11 +658,12 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
                     view: &view,
                     resolve_target: None,
                     ops: wgpu::Operations {
+                        // alpha 0 marks the pixels no model was drawn at, the terminal leaves them empty.
                         load: wgpu::LoadOp::Clear(wgpu::Color {
                             r: 0.0,
                             g: 0.0,
                             b: 0.0,
-                            a: 1.0,
+                            a: 0.0,
                         }),
                         store: true,
                     },
@@ -562,13 +681,13 @@ impl<T> State<T> where T: camera_trait::CameraTrait {
             render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
             use crate::wgpu::model::DrawLight;
             render_pass.set_pipeline(&self.light_render_pipeline);
-            render_pass.draw_light_model(&self.light_model, &self.camera_bind_group, &self.light_bind_group);
+            render_pass.draw_light_model(&self.light_model, camera_bind_group, &self.light_bind_group);
 
             render_pass.set_pipeline(&self.render_pipeline);
             render_pass.draw_model_instanced(
                 &self.obj_model,
-                0..self.instances.len() as u32,
-                &self.camera_bind_group,
+                0..self.instance_count as u32,
+                camera_bind_group,
                 &self.light_bind_group
             );
         }
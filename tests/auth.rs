@@ -0,0 +1,61 @@
+use dognut::department::net::auth::{Access, Tokens};
+use dognut::department::net::http::Request;
+use dognut::department::net::session;
+use dognut::pb::avpacket::Codec;
+
+fn tokens(view: &str, control: &str) -> Tokens {
+    Tokens { view: view.to_string(), control: control.to_string() }
+}
+
+#[test]
+fn tokens_grant_view_and_control() {
+    let both = tokens("look", "drive");
+    assert_eq!(both.access("drive"), Some(Access::Control));
+    assert_eq!(both.access("look"), Some(Access::View));
+    assert_eq!(both.access(""), None);
+    assert_eq!(both.access("driv"), None);
+    assert!(both.required());
+
+    // without a view token anybody watches, only the control token drives.
+    let control_only = tokens("", "drive");
+    assert_eq!(control_only.access(""), Some(Access::View));
+    assert_eq!(control_only.access("drive"), Some(Access::Control));
+    assert!(!control_only.required());
+
+    assert_eq!(tokens("look", "").access("look"), Some(Access::Control));
+    assert_eq!(Tokens::default().access("anything"), Some(Access::Control));
+}
+
+#[test]
+fn the_handshake_checks_the_token() {
+    let header = session::video_header(Codec::Jpeg, (320, 200));
+    let both = tokens("look", "drive");
+    let check = |token: &str| session::check_hello(&session::hello_packet(&[Codec::Jpeg], (80, 24), token), &header, &both);
+    assert_eq!(check("drive").unwrap().1, Access::Control);
+    assert_eq!(check("look").unwrap().1, Access::View);
+    assert!(check("").unwrap_err().contains("needs a token"));
+    assert!(check("guess").unwrap_err().contains("wrong token"));
+
+    let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    let (mut client, mut server) = tokio::io::duplex(4096);
+    let (granted, accepted) = rt.block_on(async {
+        let server = tokio::spawn(async move { session::accept(&mut server, &header, &both).await });
+        let granted = session::connect(&mut client, &[Codec::Jpeg], (80, 24), "look").await;
+        (granted.unwrap().1, server.await.unwrap().unwrap().1)
+    });
+    assert_eq!((granted, accepted), (Access::View, Access::View));
+}
+
+#[test]
+fn http_requests_carry_tokens() {
+    let request = Request::parse("GET /stream.mjpg?fps=5&token=a%2Fb+c HTTP/1.1\r\nHost: x").unwrap();
+    assert_eq!(request.route(), "/stream.mjpg");
+    assert_eq!(request.query("token").as_deref(), Some("a/b c"));
+    assert_eq!(request.query("missing"), None);
+    assert_eq!(request.bearer(), None);
+
+    let request = Request::parse("GET /snapshot.jpg HTTP/1.1\r\nAuthorization: Bearer  s3cret ").unwrap();
+    assert_eq!(request.bearer(), Some("s3cret"));
+    let request = Request::parse("GET /snapshot.jpg HTTP/1.1\r\nAuthorization: Basic abc").unwrap();
+    assert_eq!(request.bearer(), None);
+}
@@ -0,0 +1,55 @@
+use dognut::department::preview::cell_encoder::{self, Cell, CellMode};
+
+const RED: [u8; 3] = [255, 0, 0];
+const BLUE: [u8; 3] = [0, 0, 255];
+const WHITE: [u8; 3] = [255, 255, 255];
+const BLACK: [u8; 3] = [0, 0, 0];
+
+// encode a single cell of row-major pixels, None is a pixel nothing was drawn at.
+fn encode_one(mode: CellMode, pixels: &[Option<[u8; 3]>]) -> Cell {
+    let (w, h) = mode.cell_pixels();
+    assert_eq!(pixels.len() as u32, w * h);
+    let display: Vec<u8> = pixels.iter().map(|p| p.unwrap_or(BLACK)).flat_map(|c| [c[0], c[1], c[2], 255]).collect();
+    let coverage: Vec<bool> = pixels.iter().map(|p| p.is_some()).collect();
+    let (cols, rows, cells) = cell_encoder::encode(mode, &display, &coverage, w, h);
+    assert_eq!((cols, rows), (1, 1));
+    cells[0]
+}
+
+#[test]
+fn half_block_keeps_both_colors() {
+    assert_eq!(encode_one(CellMode::HalfBlock, &[Some(RED), Some(BLUE)]),
+               Cell { ch: '▀', fg: Some(RED), bg: Some(BLUE) });
+    assert_eq!(encode_one(CellMode::HalfBlock, &[Some(RED), None]),
+               Cell { ch: '▀', fg: Some(RED), bg: None });
+    assert_eq!(encode_one(CellMode::HalfBlock, &[None, Some(BLUE)]),
+               Cell { ch: '▄', fg: Some(BLUE), bg: None });
+    assert_eq!(encode_one(CellMode::HalfBlock, &[None, None]), Cell::EMPTY);
+}
+
+#[test]
+fn quadrant_splits_bright_and_dark_pixels() {
+    // pixels are top left, top right, bottom left, bottom right.
+    assert_eq!(encode_one(CellMode::Quadrant, &[Some(WHITE), Some(WHITE), Some(BLACK), Some(BLACK)]),
+               Cell { ch: '▀', fg: Some(WHITE), bg: Some(BLACK) });
+    assert_eq!(encode_one(CellMode::Quadrant, &[Some(BLACK), Some(WHITE), Some(WHITE), Some(BLACK)]),
+               Cell { ch: '▞', fg: Some(WHITE), bg: Some(BLACK) });
+    assert_eq!(encode_one(CellMode::Quadrant, &[Some(RED); 4]),
+               Cell { ch: '█', fg: Some(RED), bg: None });
+    // uncovered pixels show the terminal background.
+    assert_eq!(encode_one(CellMode::Quadrant, &[Some(RED), None, None, Some(BLUE)]),
+               Cell { ch: '▚', fg: Some([127, 0, 127]), bg: None });
+    assert_eq!(encode_one(CellMode::Quadrant, &[None; 4]), Cell::EMPTY);
+}
+
+#[test]
+fn braille_sets_a_dot_per_covered_pixel() {
+    // rows of two pixels, top to bottom.
+    assert_eq!(encode_one(CellMode::Braille, &[Some(RED); 8]),
+               Cell { ch: '⣿', fg: Some(RED), bg: None });
+    let left = [Some(BLUE), None, Some(BLUE), None, Some(BLUE), None, Some(BLUE), None];
+    assert_eq!(encode_one(CellMode::Braille, &left), Cell { ch: '⡇', fg: Some(BLUE), bg: None });
+    let corners = [Some(RED), None, None, None, None, None, None, Some(BLUE)];
+    assert_eq!(encode_one(CellMode::Braille, &corners), Cell { ch: '⢁', fg: Some([127, 0, 127]), bg: None });
+    assert_eq!(encode_one(CellMode::Braille, &[None; 8]), Cell::EMPTY);
+}
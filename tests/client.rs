@@ -0,0 +1,70 @@
+mod common;
+
+use std::sync::Arc;
+
+use protobuf::Message;
+
+use dognut::department::net::client::{decode_frame, read_packet, receive, rgba_len, write_packet, VideoFrame};
+use dognut::department::net::frame_queue::FrameQueue;
+use dognut::pb::avpacket::VideoPacket;
+
+use common::video_packet;
+
+#[test]
+fn packets_round_trip_through_the_framing() {
+    let packet = video_packet(vec![1, 2, 3, 4]);
+    let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let read = rt.block_on(async {
+        let mut wire = Vec::new();
+        write_packet(&mut wire, &packet).await.unwrap();
+        write_packet(&mut wire, &packet).await.unwrap();
+        let mut input = wire.as_slice();
+        read_packet(&mut input).await.unwrap();
+        let second = read_packet(&mut input).await.unwrap();
+        assert!(read_packet(&mut input).await.is_err());
+        second
+    });
+    assert_eq!(read, packet);
+}
+
+#[test]
+fn raw_rgba_and_jpeg_payloads_are_decoded() {
+    let rgba: Vec<u8> = (0..2 * 2 * 4).map(|i| i as u8).collect();
+    let frame = decode_frame(&video_packet(rgba.clone()), (2, 2)).unwrap();
+    assert_eq!(frame, VideoFrame { width: 2, height: 2, rgba });
+
+    let mut jpeg = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 90)
+        .encode(&[200u8; 8 * 4 * 4], 8, 4, image::ColorType::Rgba8)
+        .unwrap();
+    let mut video = VideoPacket::new();
+    video.data_len = jpeg.len() as u32;
+    video.data = jpeg;
+    let frame = decode_frame(&video_packet(video.write_to_bytes().unwrap()), (640, 480)).unwrap();
+    assert_eq!((frame.width, frame.height), (8, 4));
+    assert_eq!(frame.rgba.len(), 8 * 4 * 4);
+
+    assert!(decode_frame(&video_packet(vec![7; 10]), (2, 2)).is_err());
+
+    // 65536 x 16384 rgba is 4gb, which must not wrap around to an empty frame.
+    assert!(decode_frame(&video_packet(Vec::new()), (1 << 16, 1 << 14)).is_err());
+    assert_eq!(rgba_len((u32::MAX, u32::MAX)), None);
+}
+
+#[test]
+fn a_slow_viewer_gets_the_newest_frame() {
+    let frames = Arc::new(FrameQueue::new(1));
+    let (request_tx, _request_rx) = tokio::sync::mpsc::unbounded_channel();
+    let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    rt.block_on(async {
+        let mut wire = Vec::new();
+        for shade in 1..=3u8 {
+            write_packet(&mut wire, &video_packet(vec![shade; 2 * 2 * 4])).await.unwrap();
+        }
+        // nothing takes the frames while the stream is read.
+        assert!(receive(wire.as_slice(), (2, 2), frames.clone(), request_tx).await.is_err());
+    });
+    assert_eq!(frames.try_pop(), Some(VideoFrame { width: 2, height: 2, rgba: vec![3; 2 * 2 * 4] }));
+    assert_eq!(frames.try_pop(), None);
+    assert_eq!(frames.dropped(), 2);
+}
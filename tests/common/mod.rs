@@ -0,0 +1,11 @@
+// fixtures shared by the network tests.
+
+use dognut::pb::netpacket::{NetPacket, PacketKind};
+
+// a video packet carrying `data`, as the router sends the encoder output.
+pub fn video_packet(data: Vec<u8>) -> NetPacket {
+    let mut packet = NetPacket::new();
+    packet.kind = protobuf::EnumOrUnknown::from(PacketKind::VideoPacket);
+    packet.data = data;
+    packet
+}
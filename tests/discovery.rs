@@ -0,0 +1,32 @@
+use dognut::department::net::discovery;
+use dognut::department::net::session::PROTOCOL_VERSION;
+use dognut::pb::avpacket::Codec;
+use dognut::pb::netpacket::Announcement;
+
+#[test]
+fn announcements_survive_the_wire() {
+    let mut announcement = Announcement::new();
+    announcement.version = PROTOCOL_VERSION;
+    announcement.name = "studio".to_string();
+    announcement.port = 9528;
+    announcement.model = "plane.obj".to_string();
+    announcement.width = 640;
+    announcement.height = 480;
+    announcement.fps = 30;
+    announcement.codecs = vec![protobuf::EnumOrUnknown::from(Codec::Jpeg)];
+    announcement.clients = 2;
+
+    let datagram = discovery::announcement_datagram(&announcement);
+    assert_eq!(discovery::parse_announcement(&datagram), Ok(announcement));
+    assert!(!discovery::is_query(&datagram));
+}
+
+#[test]
+fn only_queries_are_answered() {
+    let query = discovery::query_datagram();
+    assert!(discovery::is_query(&query));
+    assert!(discovery::parse_announcement(&query).is_err());
+    // what older viewers sent.
+    assert!(!discovery::is_query(b"dognut"));
+    assert!(!discovery::is_query(&[]));
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use dognut::department::net::frame_queue::FrameQueue;
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap()
+}
+
+#[test]
+fn a_full_queue_drops_the_oldest_frame() {
+    let queue = FrameQueue::new(2);
+    assert!(queue.push(1));
+    assert!(queue.push(2));
+    assert!(!queue.push(3));
+    assert_eq!((queue.len(), queue.dropped()), (2, 1));
+
+    let rt = runtime();
+    assert_eq!(rt.block_on(queue.pop()), Some(2));
+    assert_eq!(rt.block_on(queue.pop()), Some(3));
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn pop_waits_for_a_push_and_ends_on_close() {
+    let queue = Arc::new(FrameQueue::new(4));
+    let popped = runtime().block_on(async {
+        let reader = {
+            let queue = queue.clone();
+            tokio::spawn(async move {
+                let mut popped = Vec::new();
+                while let Some(item) = queue.pop().await {
+                    popped.push(item);
+                }
+                popped
+            })
+        };
+        tokio::task::yield_now().await;
+        queue.push("frame");
+        tokio::task::yield_now().await;
+        queue.close();
+        reader.await.unwrap()
+    });
+    assert_eq!(popped, vec!["frame"]);
+    // a closed queue takes nothing anymore.
+    queue.push("late");
+    assert!(queue.is_empty());
+}
@@ -0,0 +1,47 @@
+use dognut::department::preview::cell_encoder::Cell;
+use dognut::department::preview::front_buffer::FrontBuffer;
+use dognut::department::preview::palette::ColorDepth;
+
+fn dot(c: [u8; 3]) -> Cell {
+    Cell { ch: '•', fg: Some(c), bg: None }
+}
+
+fn present(front: &mut FrontBuffer, cells: &[Cell]) -> String {
+    let mut out = Vec::new();
+    front.present(&mut out, 4, 2, cells, ColorDepth::TrueColor);
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn unchanged_frame_emits_no_cells() {
+    let mut front = FrontBuffer::new();
+    let mut cells = vec![Cell::EMPTY; 8];
+    cells[1] = dot([255, 0, 0]);
+    cells[2] = dot([255, 0, 0]);
+
+    let first = present(&mut front, &cells);
+    assert_eq!(first.matches('•').count(), 2);
+    // both cells are on one run, so the cursor only moves once.
+    assert_eq!(first.matches('H').count(), 1);
+
+    let second = present(&mut front, &cells);
+    assert!(!second.contains('•'));
+    assert!(!second.contains('H'));
+}
+
+#[test]
+fn emptied_cells_are_cleared_and_invalidate_redraws() {
+    let mut front = FrontBuffer::new();
+    let mut cells = vec![Cell::EMPTY; 8];
+    cells[5] = dot([0, 255, 0]);
+    present(&mut front, &cells);
+
+    cells[5] = Cell::EMPTY;
+    let cleared = present(&mut front, &cells);
+    assert!(cleared.contains("\x1b[2;2H "));
+
+    cells[0] = dot([0, 0, 255]);
+    present(&mut front, &cells);
+    front.invalidate();
+    assert_eq!(present(&mut front, &cells).matches('•').count(), 1);
+}
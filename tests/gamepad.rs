@@ -0,0 +1,46 @@
+use gilrs::{Axis, Button};
+
+use dognut::department::control::gamepad::{PadCommand, PadMapper};
+use dognut::department::control::keymap::Action;
+
+#[test]
+fn buttons_map_to_actions() {
+    let mut pad = PadMapper::new();
+    assert_eq!(pad.button(Button::DPadUp, true), Some(PadCommand::Action { action: Action::Forward, pressed: true, model: true }));
+    assert_eq!(pad.button(Button::North, true), Some(PadCommand::Action { action: Action::TogglePresentation, pressed: true, model: false }));
+    assert_eq!(pad.button(Button::West, false), Some(PadCommand::Action { action: Action::ResetModel, pressed: false, model: false }));
+    assert_eq!(pad.button(Button::Mode, true), None);
+}
+
+#[test]
+fn sticks_and_triggers_drive_the_camera() {
+    let mut pad = PadMapper::new();
+    // inside the deadzone nothing happens.
+    pad.axis(Axis::LeftStickY, 0.05);
+    assert!(pad.frame().is_empty());
+
+    pad.axis(Axis::LeftStickY, 1.0);
+    pad.axis(Axis::RightStickX, -0.5);
+    pad.button_value(Button::RightTrigger2, 0.75);
+    assert_eq!(pad.frame(), vec![
+        PadCommand::Move { forward: 1.0, right: 0.0 },
+        PadCommand::Look { x: -0.5, y: 0.0 },
+        PadCommand::Zoom(0.75),
+    ]);
+
+    // an unchanged stick is not sent again, look and zoom are.
+    assert_eq!(pad.frame().len(), 2);
+}
+
+#[test]
+fn reset_releases_everything() {
+    let mut pad = PadMapper::new();
+    pad.axis(Axis::LeftStickX, 1.0);
+    pad.button(Button::DPadLeft, true);
+    pad.frame();
+
+    let released = pad.reset();
+    assert!(released.contains(&PadCommand::Action { action: Action::Left, pressed: false, model: true }));
+    assert_eq!(pad.frame(), vec![PadCommand::Move { forward: 0.0, right: 0.0 }]);
+    assert!(pad.frame().is_empty());
+}
@@ -0,0 +1,232 @@
+//! Golden-image regression tests for the software renderer.
+//!
+//! Every bundled asset in `GOLDEN_ASSETS` is rendered through `RasterRunner`
+//! with a fixed camera and light, then compared with `tests/golden/<name>.png`.
+//! A pixel only counts as different when it is outside `PIXEL_TOLERANCE` on
+//! some channel *and* its perceptual (YIQ) distance is above
+//! `PERCEPTUAL_THRESHOLD`, so tiny rounding changes don't fail the suite.
+//!
+//! On failure the rendered frame and a diff image are written to
+//! `target/golden-diff/`. An asset without a reference is skipped with a
+//! message, nothing is compared for it. Run with `DOGNUT_BLESS=1` to
+//! (re)write the reference images after an intended rendering change or when
+//! adding an asset, then commit them under `tests/golden/`.
+
+use std::path::{Path, PathBuf};
+
+use image::{Rgba, RgbaImage};
+
+use dognut::department::model::object_loader::ObjectLoader;
+use dognut::department::model::triangle_resources::TriangleResources;
+use dognut::department::pipeline::rasterizer::RasterRunner;
+use dognut::department::pipeline::shader::LambertianShader;
+use dognut::department::preview::homo_transformation::HomoTransform;
+use dognut::department::preview::output_buffer::OutputBuffer;
+use dognut::department::preview::vector::Vector3;
+use dognut::department::types::msg::TransferMsg;
+use dognut::department::types::multi_sender::MultiSender;
+use dognut::department::view::camera::Camera;
+
+const GOLDEN_WIDTH: u32 = 256;
+const GOLDEN_HEIGHT: u32 = 192;
+
+/// max per channel difference that is still treated as identical.
+const PIXEL_TOLERANCE: u8 = 8;
+/// normalized YIQ distance (0..1) above which a pixel is visibly different.
+const PERCEPTUAL_THRESHOLD: f32 = 0.05;
+/// fraction of differing pixels a frame may have before the test fails.
+const MAX_DIFF_RATIO: f32 = 0.002;
+
+/// (reference name, obj path) of every asset bundled under res/.
+const GOLDEN_ASSETS: &[(&str, &str)] = &[
+    ("plane", "res/plane/plane.obj"),
+    ("cat", "res/cat.obj"),
+    ("cube", "res/cube/cube.obj"),
+    ("cube_flat", "res/cube.obj"),
+    ("nice_cube", "res/nice_cube/nice_cube.obj"),
+    ("light_ball", "res/nice_cube/light_ball.obj"),
+    ("link", "res/Link/link.obj"),
+    ("link_adult", "res/Link/link_adult.obj"),
+    ("new_link", "res/Link/new_link.obj"),
+    ("mario", "res/mario_mini/mario.obj"),
+    ("mario_mini", "res/mario_mini/mariomini.obj"),
+    ("strike_mario", "res/strike_mario/mario.obj"),
+    ("thumb", "res/thumbs-up/thumb.obj"),
+    ("diablo", "res/diablo/diablo3_pose.obj"),
+    ("creeper", "res/extra/Creeper/Creeper.obj"),
+    ("creeper_charged", "res/extra/Creeper/Creeper Charged.obj"),
+];
+
+fn crate_path(relative: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(relative)
+}
+
+fn should_bless() -> bool {
+    std::env::var("DOGNUT_BLESS").map_or(false, |v| v != "0")
+}
+
+/// scale and center the model so that every asset fills roughly the same part of the frame.
+fn fit_model_matrix(res: &TriangleResources) -> HomoTransform {
+    let positions = &res.model.mesh.positions;
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+    for p in positions.chunks_exact(3) {
+        for i in 0..3 {
+            min[i] = min[i].min(p[i]);
+            max[i] = max[i].max(p[i]);
+        }
+    }
+
+    let center = ((min[0] + max[0]) / 2., (min[1] + max[1]) / 2., (min[2] + max[2]) / 2.);
+    let half_extent = (0..3).map(|i| (max[i] - min[i]) / 2.).fold(f32::EPSILON, f32::max);
+    let s = 1.5 / half_extent;
+
+    HomoTransform::translation((-center.0, -center.1, -center.2))
+        * HomoTransform::scale((s, s, s))
+        * HomoTransform::rotation_matrix(&Vector3::from_xyz(0., 1., 0.), 0.6)
+}
+
+fn render_asset(obj_path: &str) -> RgbaImage {
+    let (net_sender, _net_receiver) = crossbeam_channel::unbounded::<TransferMsg>();
+    let (enc_sender, _enc_receiver) = crossbeam_channel::unbounded::<TransferMsg>();
+    let (win_sender, _win_receiver) = crossbeam_channel::unbounded::<TransferMsg>();
+    let ms = MultiSender::new(net_sender, enc_sender, win_sender);
+
+    let camera = Camera::new(45., GOLDEN_WIDTH as f32 / GOLDEN_HEIGHT as f32,
+                             -5., -50., Vector3::from_xyz(0., 0., 10.),
+                             Vector3::from_xyz(0., 0., -1.),
+                             Vector3::from_xyz(0., -1., 0.));
+    let shader = LambertianShader::new(Vector3::from_xyz(0., 1., 0.),
+                                       0.8, 1., &camera);
+    let mut raster = RasterRunner::new(ms, camera, Box::new(shader), false);
+
+    let res = ObjectLoader::load_triangle_resources(crate_path(obj_path).to_str().unwrap());
+    raster.set_model(fit_model_matrix(&res));
+
+    let mut out = OutputBuffer::new(GOLDEN_WIDTH, GOLDEN_HEIGHT, false);
+    raster.render_frame(&res, &mut out);
+
+    RgbaImage::from_raw(GOLDEN_WIDTH, GOLDEN_HEIGHT, out.display).unwrap()
+}
+
+/// perceptual distance between two pixels in YIQ space, normalized to 0..1.
+fn yiq_distance(a: &Rgba<u8>, b: &Rgba<u8>) -> f32 {
+    let yiq = |p: &Rgba<u8>| {
+        let (r, g, b) = (p[0] as f32, p[1] as f32, p[2] as f32);
+        (
+            0.298_895_3 * r + 0.586_622_5 * g + 0.114_482_2 * b,
+            0.595_977_9 * r - 0.274_176_4 * g - 0.321_801_5 * b,
+            0.211_470_2 * r - 0.522_617_2 * g + 0.311_146_9 * b,
+        )
+    };
+    let (ya, ia, qa) = yiq(a);
+    let (yb, ib, qb) = yiq(b);
+    let (dy, di, dq) = (ya - yb, ia - ib, qa - qb);
+
+    // 35215 is the largest value the weighted sum can reach for 8 bit channels.
+    ((0.5053 * dy * dy + 0.299 * di * di + 0.1957 * dq * dq) / 35215.).sqrt()
+}
+
+fn pixel_differs(a: &Rgba<u8>, b: &Rgba<u8>) -> bool {
+    let over_tolerance = a.0.iter().zip(b.0.iter()).any(|(x, y)| x.abs_diff(*y) > PIXEL_TOLERANCE);
+    over_tolerance && yiq_distance(a, b) > PERCEPTUAL_THRESHOLD
+}
+
+/// returns the number of differing pixels and an image highlighting them in red.
+fn diff_images(expected: &RgbaImage, actual: &RgbaImage) -> (usize, RgbaImage) {
+    let mut diff = RgbaImage::new(expected.width(), expected.height());
+    let mut count = 0;
+    for (x, y, e) in expected.enumerate_pixels() {
+        let a = actual.get_pixel(x, y);
+        if pixel_differs(e, a) {
+            count += 1;
+            diff.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+        } else {
+            // faded copy of the reference so the differences are easy to locate.
+            let l = (e[0] as u32 + e[1] as u32 + e[2] as u32) / 3;
+            let l = (255 - (255 - l) / 4) as u8;
+            diff.put_pixel(x, y, Rgba([l, l, l, 255]));
+        }
+    }
+    (count, diff)
+}
+
+fn check_golden(name: &str, actual: &RgbaImage) -> Result<(), String> {
+    let reference_path = crate_path(&format!("tests/golden/{}.png", name));
+
+    if should_bless() {
+        std::fs::create_dir_all(reference_path.parent().unwrap()).unwrap();
+        actual.save(&reference_path).unwrap();
+        eprintln!("golden reference written to {}", reference_path.display());
+        return Ok(());
+    }
+    if !reference_path.exists() {
+        eprintln!("{}: skipped, no reference at {}, write it with DOGNUT_BLESS=1", name, reference_path.display());
+        return Ok(());
+    }
+
+    let expected = image::open(&reference_path)
+        .map_err(|e| format!("{}: could not open reference: {}", name, e))?
+        .to_rgba8();
+
+    let out_dir = crate_path("target/golden-diff");
+    std::fs::create_dir_all(&out_dir).unwrap();
+
+    if expected.dimensions() != actual.dimensions() {
+        actual.save(out_dir.join(format!("{}.actual.png", name))).unwrap();
+        return Err(format!("{}: size {:?} differs from reference {:?}", name, actual.dimensions(), expected.dimensions()));
+    }
+
+    let (count, diff) = diff_images(&expected, actual);
+    let ratio = count as f32 / (expected.width() * expected.height()) as f32;
+    if ratio > MAX_DIFF_RATIO {
+        actual.save(out_dir.join(format!("{}.actual.png", name))).unwrap();
+        diff.save(out_dir.join(format!("{}.diff.png", name))).unwrap();
+        return Err(format!("{}: {} pixels ({:.3}%) differ, see {}", name, count, ratio * 100., out_dir.display()));
+    }
+
+    Ok(())
+}
+
+fn collect_objs(dir: &Path, out: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_objs(&path, out);
+        } else if path.extension().map_or(false, |e| e == "obj") {
+            out.push(path);
+        }
+    }
+}
+
+#[test]
+fn golden_images() {
+    // every bundled asset is listed, a new one needs an entry and a blessed reference.
+    let mut bundled = Vec::new();
+    collect_objs(&crate_path("res"), &mut bundled);
+    let listed: Vec<PathBuf> = GOLDEN_ASSETS.iter().map(|(_, path)| crate_path(path)).collect();
+    let unlisted: Vec<String> = bundled.iter().filter(|p| !listed.contains(p)).map(|p| p.display().to_string()).collect();
+    assert!(unlisted.is_empty(), "assets without a golden test:\n{}", unlisted.join("\n"));
+
+    let failures: Vec<String> = GOLDEN_ASSETS.iter()
+        .filter_map(|(name, path)| check_golden(name, &render_asset(path)).err())
+        .collect();
+
+    assert!(failures.is_empty(), "golden image mismatch:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn identical_images_have_no_diff() {
+    let img = RgbaImage::from_pixel(4, 4, Rgba([10, 20, 30, 255]));
+    assert_eq!(diff_images(&img, &img).0, 0);
+}
+
+#[test]
+fn small_color_noise_is_tolerated() {
+    let expected = RgbaImage::from_pixel(4, 4, Rgba([100, 100, 100, 255]));
+    let actual = RgbaImage::from_pixel(4, 4, Rgba([104, 97, 100, 255]));
+    assert_eq!(diff_images(&expected, &actual).0, 0);
+
+    let changed = RgbaImage::from_pixel(4, 4, Rgba([200, 40, 40, 255]));
+    assert_eq!(diff_images(&expected, &changed).0, 16);
+}
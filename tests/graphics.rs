@@ -0,0 +1,46 @@
+use dognut::department::preview::graphics::{self, GraphicsProtocol};
+
+#[test]
+fn detects_protocol_from_terminal_reply() {
+    assert_eq!(graphics::detect_from_reply("\x1b_Gi=31;OK\x1b\\\x1b[?62;22c"), GraphicsProtocol::Kitty);
+    assert_eq!(graphics::detect_from_reply("\x1b[?62;4;6;22c"), GraphicsProtocol::Sixel);
+    assert_eq!(graphics::detect_from_reply("\x1b[?64;1;2;6;22c"), GraphicsProtocol::Cells);
+    assert_eq!(graphics::detect_from_env(Some("xterm-kitty"), None, false), Some(GraphicsProtocol::Kitty));
+    assert_eq!(graphics::detect_from_env(Some("foot-extra"), None, false), Some(GraphicsProtocol::Sixel));
+    assert_eq!(graphics::detect_from_env(Some("xterm-256color"), None, false), None);
+}
+
+#[test]
+fn cell_size_comes_from_the_window_or_the_reply() {
+    assert_eq!(graphics::cell_size_from_window(80, 24, 800, 480), Some((10, 20)));
+    // terminals that don't know their pixels report 0.
+    assert_eq!(graphics::cell_size_from_window(80, 24, 0, 0), None);
+    assert_eq!(graphics::cell_size_from_reply("\x1b[6;18;9t\x1b[?62;22c"), Some((9, 18)));
+    assert_eq!(graphics::cell_size_from_reply("\x1b[?62;22c"), None);
+}
+
+#[test]
+fn sixel_frame_is_framed_and_banded() {
+    let (w, h) = (5u32, 8u32);
+    let display: Vec<u8> = (0..w * h).flat_map(|i| if i % 2 == 0 { [255, 0, 0, 255] } else { [0, 0, 0, 255] }).collect();
+    let sixel = String::from_utf8(graphics::encode_sixel(&display, w, h)).unwrap();
+
+    assert!(sixel.starts_with("\x1bP0;0;0q\"1;1;5;8"));
+    assert!(sixel.ends_with("\x1b\\"));
+    // 8 rows make two bands of six pixels.
+    assert_eq!(sixel.matches('-').count(), 2);
+    assert!(sixel.contains("#196;2;100;0;0"));
+}
+
+#[test]
+fn kitty_frame_is_chunked() {
+    let (w, h) = (64u32, 64u32);
+    let display = vec![128u8; (w * h * 4) as usize];
+    let kitty = String::from_utf8(graphics::encode_kitty(&display, w, h)).unwrap();
+
+    // 64 * 64 * 3 bytes are 16384 base64 chars, four chunks.
+    assert!(kitty.starts_with("\x1b_Ga=T,f=24,s=64,v=64,"));
+    assert_eq!(kitty.matches("\x1b_G").count(), 4);
+    assert_eq!(kitty.matches("m=1;").count(), 3);
+    assert!(kitty.contains("\x1b_Gm=0;"));
+}
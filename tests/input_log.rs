@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use dognut::department::control::camera_controller::CameraController;
+use dognut::department::control::input_log::{self, parse, parse_mode, Frame, InputEvent, InputLog, InputReplay};
+use dognut::department::control::keymap::Action;
+use dognut::department::model::scene;
+use dognut::department::preview::vector::Vector3;
+use dognut::department::view::camera::Camera;
+
+#[test]
+fn frames_round_trip_exactly() {
+    let frames = vec![
+        Frame { index: 0, dt: Duration::from_nanos(33_333_333), events: vec![] },
+        Frame {
+            index: 1,
+            dt: Duration::from_nanos(16_666_667),
+            events: vec![
+                InputEvent::Action { action: Action::Forward, pressed: true, model: false },
+                InputEvent::Action { action: Action::Left, pressed: false, model: true },
+                InputEvent::Look { dx: 0.1, dy: -3.75 },
+                InputEvent::Scroll(-100.0),
+                InputEvent::Movement { forward: 1.0 / 3.0, right: -0.2 },
+                InputEvent::Rotate { dx: 16.0, dy: f32::MIN_POSITIVE },
+                InputEvent::Select(2),
+            ],
+        },
+    ];
+    let text: String = frames.iter().map(Frame::to_lines).collect();
+    assert_eq!(parse(&text).unwrap(), frames);
+}
+
+#[test]
+fn bad_lines_are_reported() {
+    assert!(parse("look 1 2\n").unwrap_err().contains("line 1"));
+    assert!(parse("frame 0 100\naction jump 1 0\n").unwrap_err().contains("line 2"));
+    assert!(parse("# comment only\n").unwrap().is_empty());
+}
+
+#[test]
+fn the_header_keeps_the_controller_mode() {
+    assert_eq!(parse_mode(&format!("{}\n", input_log::header(true))), Ok(true));
+    assert_eq!(parse_mode(&format!("{}\n", input_log::header(false))), Ok(false));
+    // logs from before the mode was written come from the window.
+    assert_eq!(parse_mode("# dognut input log v1\nframe 0 100\n"), Ok(false));
+    assert!(parse_mode("# dognut input log v1 gamepad\n").unwrap_err().contains("line 1"));
+}
+
+#[test]
+fn a_terminal_log_replays_with_the_terminal_controller() {
+    // terminals repeat a held key as presses, two of them in one update move twice as far.
+    let text = format!("{}\nframe 0 100000000\naction forward 1 1\naction forward 1 1\nframe 1 100000000\n",
+                       input_log::header(true));
+    let replay = InputReplay::parse(&text).unwrap();
+    assert!(replay.tui());
+
+    let mut camera = Camera::new(45., 1., -5., -50., Vector3::from_xyz(0., 0., 10.),
+                                 Vector3::from_xyz(0., 0., -1.), Vector3::from_xyz(0., -1., 0.));
+    let mut controller = CameraController::new(2.0, 0.2, replay.tui());
+    controller.model_ctrl.set_instances(scene::grid(1));
+    controller.set_input_log(Some(InputLog::Replay(replay)));
+    let start = controller.model_ctrl.instances()[0].position.z;
+
+    let mut moved = Vec::new();
+    while controller.is_replaying() {
+        let dt = controller.update_camera(&mut camera, Duration::ZERO);
+        controller.model_ctrl.update_model(dt);
+        moved.push(controller.model_ctrl.instances()[0].position.z - start);
+    }
+    // the presses are used up by the first update, the window controller would keep moving.
+    assert!((moved[0] - 0.4).abs() < 1e-5, "moved {:?}", moved);
+    assert!((moved[1] - 0.4).abs() < 1e-5, "moved {:?}", moved);
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use std::sync::Arc;
+
+use dognut::department::net::client::{receive, write_packet};
+use dognut::department::net::frame_queue::FrameQueue;
+use dognut::department::net::session;
+use dognut::department::types::control::{self, ControlMsg};
+use dognut::pb::netpacket::PacketKind;
+
+use common::video_packet;
+
+#[test]
+fn waiting_keyframe_requests_are_merged() {
+    let requests = control::encoder_control();
+    while requests.try_recv().is_ok() {}
+
+    assert!(control::request_keyframe());
+    assert!(!control::request_keyframe());
+    assert_eq!(requests.try_recv(), Ok(ControlMsg::GenIDR));
+    assert!(requests.try_recv().is_err());
+    assert!(control::request_keyframe());
+}
+
+#[test]
+fn a_client_asks_once_per_run_of_bad_frames() {
+    let bad = video_packet(vec![1, 2, 3]);
+    let good = video_packet(vec![0; 2 * 2 * 4]);
+    let frames = Arc::new(FrameQueue::new(4));
+    let (request_tx, mut request_rx) = tokio::sync::mpsc::unbounded_channel();
+    let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    rt.block_on(async {
+        let mut wire = Vec::new();
+        for packet in [&bad, &bad, &good, &bad] {
+            write_packet(&mut wire, packet).await.unwrap();
+        }
+        // the stream ends after the last packet.
+        assert!(receive(wire.as_slice(), (2, 2), frames.clone(), request_tx).await.is_err());
+    });
+    assert_eq!(frames.len(), 1);
+
+    let mut reasons = Vec::new();
+    while let Ok(request) = request_rx.try_recv() {
+        assert_eq!(request.kind.enum_value(), Ok(PacketKind::KeyframeRequest));
+        reasons.push(session::keyframe_reason(&request).unwrap());
+    }
+    assert_eq!(reasons, vec!["decode error", "decode error"]);
+}
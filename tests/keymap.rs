@@ -0,0 +1,66 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use winit::event::VirtualKeyCode;
+
+use dognut::department::control::camera_controller::CameraController;
+use dognut::department::control::keymap::{Action, Keymap};
+
+#[test]
+fn default_bindings_match_in_terminal_and_window() {
+    let keymap = Keymap::default();
+    let pairs = [
+        (KeyCode::Char('w'), VirtualKeyCode::W, Action::Forward),
+        (KeyCode::Up, VirtualKeyCode::Up, Action::Forward),
+        (KeyCode::Char('x'), VirtualKeyCode::X, Action::Up),
+        (KeyCode::Char(' '), VirtualKeyCode::Space, Action::Up),
+        (KeyCode::Char('q'), VirtualKeyCode::Q, Action::Quit),
+        (KeyCode::Esc, VirtualKeyCode::Escape, Action::Quit),
+        (KeyCode::Char('k'), VirtualKeyCode::K, Action::RequestKeyframe),
+    ];
+    for (tui, win, action) in pairs {
+        assert_eq!(keymap.tui_action(&tui), Some(action), "{:?}", tui);
+        assert_eq!(keymap.winit_action(win), Some(action), "{:?}", win);
+    }
+    assert_eq!(keymap.winit_action(VirtualKeyCode::LShift), Some(Action::Down));
+    assert_eq!(keymap.tui_action(&KeyCode::Char('W')), Some(Action::Forward));
+}
+
+#[test]
+fn config_replaces_only_listed_actions() {
+    let keymap = Keymap::parse("# custom\nforward = i, F1\n\nquit = ctrl_q_is_not_a_key # comment\n").unwrap();
+
+    assert_eq!(keymap.tui_action(&KeyCode::Char('i')), Some(Action::Forward));
+    assert_eq!(keymap.tui_action(&KeyCode::F(1)), Some(Action::Forward));
+    assert_eq!(keymap.tui_action(&KeyCode::Char('w')), None);
+    assert_eq!(keymap.tui_action(&KeyCode::Char('q')), None);
+    assert_eq!(keymap.tui_action(&KeyCode::Char('s')), Some(Action::Backward));
+
+    assert!(Keymap::parse("jump = j").is_err());
+    assert!(Keymap::parse("forward w").is_err());
+}
+
+#[test]
+fn printed_keymap_parses_back() {
+    let keymap = Keymap::default();
+    let reparsed = Keymap::parse(&keymap.to_config()).unwrap();
+    for action in Action::ALL {
+        assert_eq!(keymap.keys(action), reparsed.keys(action));
+    }
+}
+
+#[test]
+fn json_keymap_names_actions() {
+    let bindings = json::parse(&Keymap::default().to_json()).unwrap();
+    assert_eq!(bindings["w"], "forward");
+    assert_eq!(bindings["space"], "up");
+    assert!(bindings["j"].is_null());
+}
+
+#[test]
+fn controller_uses_the_keymap_it_was_given() {
+    let f1 = KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE);
+    let mut controller = CameraController::new(2.0, 0.2, true);
+    assert!(controller.process_tui_keyboard(&f1));
+    controller.set_keymap(Keymap::parse("quit = F1").unwrap());
+    assert!(!controller.process_tui_keyboard(&f1));
+    assert_eq!(controller.keymap().tui_action(&KeyCode::F(1)), Some(Action::Quit));
+}
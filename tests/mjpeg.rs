@@ -0,0 +1,57 @@
+mod common;
+
+use dognut::department::net::mjpeg::{self, BOUNDARY};
+use dognut::department::net::transport::PacketWriter;
+use dognut::pb::avpacket::VideoPacket;
+use dognut::pb::netpacket::PacketKind;
+use protobuf::Message;
+
+use common::video_packet;
+
+const JPEG: [u8; 6] = [0xff, 0xd8, 0xff, 0xe0, 0xff, 0xd9];
+
+#[test]
+fn jpegs_are_found_bare_or_wrapped() {
+    assert_eq!(mjpeg::jpeg_of(&video_packet(JPEG.to_vec())), Some(JPEG.to_vec()));
+
+    let mut video = VideoPacket::new();
+    video.data = JPEG.to_vec();
+    assert_eq!(mjpeg::jpeg_of(&video_packet(video.write_to_bytes().unwrap())), Some(JPEG.to_vec()));
+
+    // raw rgba frames and other packets have no jpeg.
+    assert_eq!(mjpeg::jpeg_of(&video_packet(vec![0; 16])), None);
+    let mut hello = video_packet(JPEG.to_vec());
+    hello.kind = protobuf::EnumOrUnknown::from(PacketKind::Hello);
+    assert_eq!(mjpeg::jpeg_of(&hello), None);
+}
+
+#[test]
+fn mjpeg_writer_sends_one_part_per_jpeg() {
+    let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let wire = rt.block_on(async {
+        let mut out = PacketWriter::Mjpeg(Vec::new());
+        out.write(&video_packet(JPEG.to_vec())).await.unwrap();
+        out.write(&video_packet(vec![0; 16])).await.unwrap();
+        out.write(&video_packet(JPEG.to_vec())).await.unwrap();
+        match out {
+            PacketWriter::Mjpeg(wire) => wire,
+            _ => unreachable!(),
+        }
+    });
+
+    let mut part = format!("--{}\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n", BOUNDARY, JPEG.len()).into_bytes();
+    part.extend_from_slice(&JPEG);
+    part.extend_from_slice(b"\r\n");
+    assert_eq!(wire, [part.clone(), part].concat());
+}
+
+#[test]
+fn stream_head_names_the_boundary() {
+    let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let mut head = Vec::new();
+    rt.block_on(mjpeg::write_stream_head(&mut head)).unwrap();
+    let head = String::from_utf8(head).unwrap();
+    assert!(head.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(head.contains(&format!("Content-Type: multipart/x-mixed-replace; boundary={}\r\n", BOUNDARY)));
+    assert!(head.ends_with("\r\n\r\n"));
+}
@@ -0,0 +1,25 @@
+use std::path::Path;
+
+use dognut::department::tui::model_browser;
+
+#[test]
+fn scan_finds_bundled_models() {
+    let res = Path::new(env!("CARGO_MANIFEST_DIR")).join("res");
+    let models = model_browser::scan(&res);
+
+    assert!(models.iter().any(|p| p.ends_with("plane/plane.obj")));
+    assert!(models.iter().all(|p| p.extension().unwrap().eq_ignore_ascii_case("obj")));
+    assert!(models.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn inspect_reports_triangles_and_textures() {
+    let cube = Path::new(env!("CARGO_MANIFEST_DIR")).join("res/cube/cube.obj");
+    let info = model_browser::inspect(&cube).unwrap();
+
+    assert!(info.meshes > 0);
+    assert!(info.triangles > 0);
+    assert!(info.has_all_textures());
+
+    assert!(model_browser::inspect(Path::new("does/not/exist.obj")).is_err());
+}
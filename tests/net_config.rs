@@ -0,0 +1,55 @@
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use dognut::department::net::config::NetConfig;
+use dognut::department::net::pacer::Pacer;
+use dognut::util::Args;
+
+#[test]
+fn config_files_override_some_keys() {
+    let config = NetConfig::parse("# stay on this machine\nbind = 127.0.0.1\nport = 0\n\nmax_clients = 4 # a few\nclient_bandwidth = 512\n").unwrap();
+    assert_eq!(config.bind, "127.0.0.1");
+    assert_eq!(config.port, 0);
+    assert_eq!(config.udp_port, NetConfig::default().udp_port);
+    assert_eq!(config.max_clients, 4);
+    assert_eq!(config.client_bandwidth, 512 * 1024);
+    // the system picks the port, there is nothing else to try.
+    assert_eq!(config.ports().collect::<Vec<_>>(), vec![0]);
+    assert!(!config.is_full(3));
+    assert!(config.is_full(4));
+
+    assert_eq!(NetConfig::parse(&NetConfig::default().to_config()), Ok(NetConfig::default()));
+    assert!(NetConfig::parse("port = lots").unwrap_err().contains("line 1"));
+    assert!(NetConfig::parse("host = 1.2.3.4").unwrap_err().contains("unknown key"));
+}
+
+#[test]
+fn the_command_line_wins_over_the_file() {
+    let path = std::env::temp_dir().join(format!("dognut-net-{}.conf", std::process::id()));
+    std::fs::write(&path, "bind = 127.0.0.1\nport = 7000\nport_range = 3\n").unwrap();
+    let args = Args::try_parse_from(["dognut", "--net-config", path.to_str().unwrap(), "--port", "8000", "--client-bandwidth", "64"]).unwrap();
+    let config = NetConfig::from_args(&args).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(config.bind, "127.0.0.1");
+    assert_eq!(config.ports().collect::<Vec<_>>(), vec![8000, 8001, 8002]);
+    assert_eq!(config.client_bandwidth, 64 * 1024);
+    assert_eq!(config.max_clients, 0);
+
+    let args = Args::try_parse_from(["dognut", "--port-range", "0"]).unwrap();
+    assert!(NetConfig::from_args(&args).is_err());
+}
+
+#[test]
+fn pacer_spaces_writes_out_to_the_rate() {
+    let start = Instant::now();
+    let mut pacer = Pacer::new(1000);
+    assert_eq!(pacer.delay(500, start), Duration::ZERO);
+    assert_eq!(pacer.delay(500, start), Duration::from_millis(500));
+    // a client that was idle doesn't get to burst past the rate later.
+    assert_eq!(pacer.delay(100, start + Duration::from_secs(5)), Duration::ZERO);
+    assert_eq!(pacer.delay(100, start + Duration::from_secs(5)), Duration::from_millis(100));
+
+    let mut unlimited = Pacer::new(0);
+    assert_eq!(unlimited.delay(usize::MAX, start), Duration::ZERO);
+}
@@ -0,0 +1,9 @@
+use dognut::department::preview::output_buffer::OutputBuffer;
+
+#[test]
+fn gpu_frame_coverage_comes_from_alpha() {
+    let mut out_buf = OutputBuffer::new(3, 1, true);
+    // a black pixel of a model, an empty pixel, and a colored one.
+    out_buf.load_frame(&[0, 0, 0, 255, 0, 0, 0, 0, 40, 80, 120, 255]);
+    assert_eq!(out_buf.coverage, vec![true, false, true]);
+}
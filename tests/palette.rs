@@ -0,0 +1,36 @@
+use dognut::department::preview::palette::{self, ColorDepth, Dither};
+
+#[test]
+fn detects_color_depth_from_env() {
+    assert_eq!(palette::detect_from(Some("truecolor"), Some("xterm")), ColorDepth::TrueColor);
+    assert_eq!(palette::detect_from(Some("24bit"), None), ColorDepth::TrueColor);
+    assert_eq!(palette::detect_from(None, Some("screen-256color")), ColorDepth::Ansi256);
+    assert_eq!(palette::detect_from(None, Some("xterm-direct")), ColorDepth::TrueColor);
+    assert_eq!(palette::detect_from(None, Some("linux")), ColorDepth::Ansi16);
+    assert_eq!(palette::detect_from(None, None), ColorDepth::Ansi16);
+}
+
+#[test]
+fn palette_colors_map_to_themselves() {
+    for index in 16..=255u8 {
+        let c = palette::palette_256(index);
+        assert_eq!(palette::palette_256(palette::nearest_256(&c)), c, "index {}", index);
+    }
+}
+
+#[test]
+fn dithering_snaps_to_palette_and_skips_empty_pixels() {
+    let (w, h) = (8u32, 8u32);
+    let mut display: Vec<u8> = (0..w * h).flat_map(|i| [(i * 4) as u8, 120, 200, 255]).collect();
+    let mut coverage = vec![true; (w * h) as usize];
+    coverage[0] = false;
+    let untouched = display[0..4].to_vec();
+
+    palette::dither(ColorDepth::Ansi16, Dither::FloydSteinberg, &mut display, &coverage, w, h);
+
+    assert_eq!(&display[0..4], untouched.as_slice());
+    for p in display.chunks_exact(4).skip(1) {
+        let c = [p[0], p[1], p[2]];
+        assert_eq!(palette::quantize(ColorDepth::Ansi16, &c), c);
+    }
+}
@@ -0,0 +1,83 @@
+use cgmath::{Deg, InnerSpace, Matrix4, Point3, Vector3};
+
+use dognut::department::pick::{self, intersect_triangle, PickMesh, PickScene, Ray};
+use dognut::department::view::camera_trait::CameraTrait;
+use dognut::wgpu::camera::{Camera, Projection};
+
+// a flat grid of n by n quads in the z = 0 plane, two triangles each.
+fn grid_mesh(n: u32) -> PickMesh {
+    let mut positions = Vec::new();
+    for y in 0..=n {
+        for x in 0..=n {
+            positions.extend_from_slice(&[x as f32, y as f32, 0.]);
+        }
+    }
+    let mut indices = Vec::new();
+    for y in 0..n {
+        for x in 0..n {
+            let i = y * (n + 1) + x;
+            indices.extend_from_slice(&[i, i + 1, i + n + 1, i + 1, i + n + 2, i + n + 1]);
+        }
+    }
+    PickMesh::new("grid".into(), &positions, &indices)
+}
+
+fn down_at(x: f32, y: f32) -> Ray {
+    Ray { origin: Point3::new(x, y, 5.), direction: Vector3::new(0., 0., -1.) }
+}
+
+#[test]
+fn rays_hit_and_miss_triangles() {
+    let (a, b, c) = (Point3::new(0., 0., 0.), Point3::new(1., 0., 0.), Point3::new(0., 1., 0.));
+    assert_eq!(intersect_triangle(&down_at(0.25, 0.25), a, b, c), Some(5.));
+    assert_eq!(intersect_triangle(&down_at(0.75, 0.75), a, b, c), None);
+
+    let away = Ray { origin: Point3::new(0.25, 0.25, 5.), direction: Vector3::new(0., 0., 1.) };
+    assert_eq!(intersect_triangle(&away, a, b, c), None);
+}
+
+#[test]
+fn bvh_finds_the_same_triangle_as_brute_force() {
+    let mesh = grid_mesh(8);
+    assert_eq!(mesh.triangle_count(), 128);
+    for (x, y) in [(0.2, 0.3), (3.7, 1.1), (7.9, 7.9), (4.5, 6.25)] {
+        let ray = down_at(x, y);
+        let (triangle, distance) = mesh.intersect(&ray).unwrap();
+        // lower left triangle of the quad under the ray, or the upper right one.
+        let quad = (y as usize * 8 + x as usize) * 2;
+        let upper = x.fract() + y.fract() > 1.;
+        assert_eq!(triangle, quad + upper as usize);
+        assert_eq!(distance, 5.);
+    }
+    assert_eq!(mesh.intersect(&down_at(9., 1.)), None);
+}
+
+#[test]
+fn the_nearer_instance_is_picked() {
+    let scene = PickScene { meshes: vec![grid_mesh(2)] };
+    let instances = [
+        Matrix4::from_translation(Vector3::new(0., 0., -3.)),
+        Matrix4::from_translation(Vector3::new(0., 0., 1.)),
+    ];
+    let hit = scene.pick(&down_at(1.5, 0.5), &instances).unwrap();
+    assert_eq!(hit.instance, 1);
+    assert_eq!(hit.mesh, 0);
+    assert_eq!(hit.distance, 4.);
+    assert_eq!(hit.point, Point3::new(1.5, 0.5, 1.));
+
+    assert_eq!(pick::ndc((1, 1), (2, 2)), (0.5, -0.5));
+}
+
+#[test]
+fn target_ray_uses_the_target_aspect() {
+    let camera_at = |w, h| Camera::new(Point3::new(0., 0., 5.), Deg(-90f32), Deg(0f32), Projection::new(w, h, Deg(45f32), 0.1, 100.));
+    // the window is twice as wide as it is tall, the terminal target is square.
+    let window = camera_at(800, 400);
+    let ray = pick::target_ray(&window, (1., 0.), (100, 100));
+    let expected = camera_at(100, 100).screen_ray((1., 0.));
+    assert_eq!(ray.origin, expected.origin);
+    assert!((ray.direction - expected.direction).magnitude() < 1e-5);
+    assert!((ray.direction - window.screen_ray((1., 0.)).direction).magnitude() > 0.1);
+    // the camera still projects for the window.
+    assert_eq!(window.to_view_proj(), camera_at(800, 400).to_view_proj());
+}
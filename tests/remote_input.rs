@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use dognut::department::control::camera_controller::{CameraController, SCROLL_LINE};
+use dognut::department::control::input_log::InputEvent;
+use dognut::department::control::keymap::Action;
+use dognut::department::model::scene;
+use dognut::department::net::remote_input::{self, ControlPolicy, InputGate, RemoteControl};
+use dognut::department::types::msg::TransferMsg;
+
+#[test]
+fn the_policy_decides_who_controls() {
+    let mut gate = InputGate::new(ControlPolicy::ViewOnly);
+    gate.join(1);
+    assert!(!gate.may_control(1));
+
+    let mut gate = InputGate::new(ControlPolicy::Single);
+    gate.join(1);
+    gate.join(2);
+    assert!(gate.may_control(1));
+    assert!(!gate.may_control(2));
+    // the next viewer takes over when the first one leaves.
+    gate.leave(1);
+    assert!(gate.may_control(2));
+    assert!(!gate.may_control(1));
+
+    let mut gate = InputGate::new(ControlPolicy::Shared);
+    gate.join(1);
+    gate.join(2);
+    assert!(gate.may_control(1) && gate.may_control(2));
+    assert!(!gate.may_control(3));
+}
+
+#[test]
+fn packets_turn_into_controller_input() {
+    let key = remote_input::key_packet(Action::Forward, true, true);
+    assert_eq!(remote_input::to_input_event(&key).unwrap(),
+               Some(InputEvent::Action { action: Action::Forward, pressed: true, model: true }));
+    let mouse = remote_input::mouse_packet(3., -1.5, true);
+    assert_eq!(remote_input::to_input_event(&mouse).unwrap(), Some(InputEvent::Rotate { dx: 3., dy: -1.5 }));
+    let scroll = remote_input::scroll_packet(2.);
+    assert_eq!(remote_input::to_input_event(&scroll).unwrap(), Some(InputEvent::Scroll(-2. * SCROLL_LINE)));
+
+    // quitting only concerns the viewer.
+    let quit = remote_input::key_packet(Action::Quit, true, false);
+    assert_eq!(remote_input::to_input_event(&quit).unwrap(), None);
+    let keyframe = remote_input::key_packet(Action::RequestKeyframe, true, false);
+    assert_eq!(remote_input::to_input_event(&keyframe).unwrap(), None);
+}
+
+#[test]
+fn a_remote_press_lasts_one_update() {
+    let (tx, rx) = crossbeam_channel::unbounded();
+    let mut remote = RemoteControl::new(rx);
+    let mut controller = CameraController::new(2.0, 0.2, false);
+    controller.model_ctrl.set_instances(scene::grid(2));
+
+    tx.send(TransferMsg::RemoteInput(InputEvent::Action { action: Action::NextInstance, pressed: true, model: false }))
+        .unwrap();
+    tx.send(TransferMsg::RemoteInput(InputEvent::Action { action: Action::Forward, pressed: true, model: true }))
+        .unwrap();
+    remote.update(&mut controller);
+    assert_eq!(controller.model_ctrl.selected(), 1);
+    let start = controller.model_ctrl.instances()[1].position.z;
+    controller.model_ctrl.update_model(Duration::from_secs(1));
+    let moved = controller.model_ctrl.instances()[1].position.z;
+    assert!(moved > start);
+
+    // the queued release stops the model without another message.
+    remote.update(&mut controller);
+    controller.model_ctrl.update_model(Duration::from_secs(1));
+    assert_eq!(controller.model_ctrl.instances()[1].position.z, moved);
+    assert_eq!(controller.model_ctrl.selected(), 1);
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use dognut::department::control::ModelController;
+use dognut::department::control::keymap::Action;
+use dognut::department::model::scene::{self, ORIGIN, SPACE_BETWEEN};
+
+#[test]
+fn grid_is_centered_and_starts_at_the_origin() {
+    assert_eq!(scene::grid(1)[0].position, ORIGIN);
+
+    let grid = scene::grid(4);
+    assert_eq!(grid.len(), 4);
+    assert_eq!(grid[0].position.x, -SPACE_BETWEEN / 2.);
+    assert_eq!(grid[1].position.x, SPACE_BETWEEN / 2.);
+    assert_eq!(grid[2].position.z, ORIGIN.z - SPACE_BETWEEN);
+}
+
+#[test]
+fn scene_files_are_parsed() {
+    let instances = scene::parse("# two of them\n0 0 1\n2 0 -3 90\n").unwrap();
+    assert_eq!(instances.len(), 2);
+    assert_eq!(instances[1].position.x, 2.);
+    assert!(scene::parse("1 2\n").is_err());
+    assert!(scene::parse("").is_err());
+}
+
+#[test]
+fn only_the_selected_instance_moves() {
+    let mut models = ModelController::new(2.0, true);
+    models.set_instances(scene::grid(3));
+    models.process_action(Action::NextInstance, true);
+    models.process_action(Action::NextInstance, true);
+    models.process_action(Action::NextInstance, true);
+    models.process_action(Action::PreviousInstance, true);
+    assert_eq!(models.selected(), 2);
+
+    models.process_action(Action::Up, true);
+    models.update_model(Duration::from_secs(1));
+    let instances = models.instances();
+    assert_eq!(instances[0].position.y, 0.);
+    assert_eq!(instances[1].position.y, 0.);
+    assert_eq!(instances[2].position.y, 2.);
+}
@@ -0,0 +1,99 @@
+use protobuf::Message;
+
+use dognut::department::net::auth::{Access, Tokens};
+use dognut::department::net::client::{read_packet, write_packet};
+use dognut::department::net::session::{self, PROTOCOL_VERSION};
+use dognut::department::net::transport::{PacketReader, PacketWriter};
+use dognut::pb::avpacket::Codec;
+use dognut::pb::netpacket::{Hello, PacketKind};
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap()
+}
+
+#[test]
+fn a_compatible_client_gets_the_header() {
+    let header = session::video_header(Codec::Jpeg, (320, 200));
+    let (mut client, mut server) = tokio::io::duplex(4096);
+    let ((hello, access), (welcome, granted)) = runtime().block_on(async {
+        let server = tokio::spawn(async move { session::accept(&mut server, &header, &Tokens::default()).await });
+        let welcome = session::connect(&mut client, &[Codec::Raw, Codec::Jpeg], (80, 24), "").await;
+        (server.await.unwrap().unwrap(), welcome.unwrap())
+    });
+    // without tokens everybody may control, as far as the control policy lets them.
+    assert_eq!((access, granted), (Access::Control, Access::Control));
+    assert_eq!(hello.version, PROTOCOL_VERSION);
+    assert_eq!((hello.columns, hello.rows), (80, 24));
+    assert_eq!(welcome.codec.enum_value(), Ok(Codec::Jpeg));
+    assert_eq!((welcome.width, welcome.height), (320, 200));
+}
+
+#[test]
+fn a_client_without_the_codec_is_turned_away() {
+    let header = session::video_header(Codec::H264, (320, 200));
+    let (mut client, mut server) = tokio::io::duplex(4096);
+    let (refused, error) = runtime().block_on(async {
+        let server = tokio::spawn(async move { session::accept(&mut server, &header, &Tokens::default()).await });
+        let refused = session::connect(&mut client, &[Codec::Jpeg], (80, 24), "").await;
+        (refused, server.await.unwrap())
+    });
+    assert_eq!(refused.unwrap_err().kind(), std::io::ErrorKind::ConnectionRefused);
+    assert!(error.unwrap_err().to_string().contains("H264"));
+
+    // a client of another protocol version gets an error packet too.
+    let mut hello = session::hello_packet(&[Codec::Jpeg], (0, 0), "");
+    let mut future = Hello::parse_from_bytes(&hello.data).unwrap();
+    future.version = PROTOCOL_VERSION + 1;
+    hello.data = future.write_to_bytes().unwrap();
+    let header = session::video_header(Codec::Jpeg, (320, 200));
+    assert!(session::check_hello(&hello, &header, &Tokens::default()).unwrap_err().contains("version"));
+    let (mut client, mut server) = tokio::io::duplex(4096);
+    let answer = runtime().block_on(async {
+        write_packet(&mut client, &hello).await.unwrap();
+        assert!(session::accept(&mut server, &header, &Tokens::default()).await.is_err());
+        read_packet(&mut client).await.unwrap()
+    });
+    assert_eq!(answer.kind.enum_value(), Ok(PacketKind::Error));
+}
+
+#[test]
+fn a_good_hello_is_not_answered_yet() {
+    let header = session::video_header(Codec::Jpeg, (320, 200));
+    let (mut client, server) = tokio::io::duplex(4096);
+    let (accepted, answer) = runtime().block_on(async {
+        write_packet(&mut client, &session::hello_packet(&[Codec::Jpeg], (80, 24), "")).await.unwrap();
+        let (read, write) = tokio::io::split(server);
+        let (mut reader, mut writer) = (PacketReader::Framed(read), PacketWriter::Framed(write));
+        let accepted = session::read_hello(&mut reader, &mut writer, &header, &Tokens::default()).await;
+        // the server may still turn the client away, here it just hangs up.
+        drop((reader, writer));
+        (accepted, read_packet(&mut client).await)
+    });
+    assert_eq!(accepted.unwrap().1, Access::Control);
+    assert_eq!(answer.unwrap_err().kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn a_huge_hello_is_refused_before_it_is_read() {
+    let header = session::video_header(Codec::Jpeg, (320, 200));
+    let (mut client, mut server) = tokio::io::duplex(4096);
+    let refused = runtime().block_on(async {
+        // only the length arrives, the server must not wait for the rest.
+        tokio::io::AsyncWriteExt::write_u32(&mut client, session::MAX_HELLO_SIZE as u32 + 1).await.unwrap();
+        session::accept(&mut server, &header, &Tokens::default()).await
+    });
+    assert_eq!(refused.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn a_stream_too_big_to_decode_is_refused() {
+    let header = session::video_header(Codec::Raw, (u32::MAX, u32::MAX));
+    let (mut client, mut server) = tokio::io::duplex(4096);
+    let refused = runtime().block_on(async {
+        let server = tokio::spawn(async move { session::accept(&mut server, &header, &Tokens::default()).await });
+        let refused = session::connect(&mut client, &[Codec::Raw], (80, 24), "").await;
+        server.await.unwrap().unwrap();
+        refused
+    });
+    assert!(refused.unwrap_err().to_string().contains("too big"));
+}
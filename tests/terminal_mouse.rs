@@ -0,0 +1,30 @@
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+use dognut::department::control::camera_controller::CameraController;
+use dognut::department::control::terminal_mouse::{MouseAction, TerminalMouse};
+
+fn event(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+    MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE }
+}
+
+#[test]
+fn press_and_release_is_a_click() {
+    let mut mouse = TerminalMouse::new();
+    let mut controller = CameraController::new(2.0, 0.2, true);
+    assert_eq!(mouse.handle(&event(MouseEventKind::Down(MouseButton::Left), 4, 2), &mut controller), MouseAction::None);
+    assert_eq!(mouse.handle(&event(MouseEventKind::Up(MouseButton::Left), 4, 2), &mut controller),
+               MouseAction::Click { column: 4, row: 2 });
+}
+
+#[test]
+fn drag_is_not_a_click() {
+    let mut mouse = TerminalMouse::new();
+    let mut controller = CameraController::new(2.0, 0.2, true);
+    mouse.handle(&event(MouseEventKind::Down(MouseButton::Left), 4, 2), &mut controller);
+    mouse.handle(&event(MouseEventKind::Drag(MouseButton::Left), 6, 2), &mut controller);
+    assert_eq!(mouse.handle(&event(MouseEventKind::Up(MouseButton::Left), 6, 2), &mut controller), MouseAction::None);
+
+    // a right click only turns the model.
+    mouse.handle(&event(MouseEventKind::Down(MouseButton::Right), 1, 1), &mut controller);
+    assert_eq!(mouse.handle(&event(MouseEventKind::Up(MouseButton::Right), 1, 1), &mut controller), MouseAction::None);
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "tls")]
+
+use dognut::department::net::auth::{Access, Tokens};
+use dognut::department::net::session;
+use dognut::department::net::tls::{self, Acceptor};
+use dognut::pb::avpacket::Codec;
+use tokio::net::{TcpListener, TcpStream};
+
+// a self signed certificate for localhost and 127.0.0.1, made with
+// openssl req -x509 -newkey ec -pkeyopt ec_paramgen_curve:prime256v1 -nodes -days 36500 -subj /CN=localhost \
+//   -addext subjectAltName=DNS:localhost,IP:127.0.0.1 -addext basicConstraints=critical,CA:FALSE \
+//   -keyout localhost.key -out localhost.crt
+const CERT: &str = "tests/data/localhost.crt";
+const KEY: &str = "tests/data/localhost.key";
+
+#[test]
+fn a_session_runs_over_tls_with_a_self_signed_certificate() {
+    let acceptor = Acceptor::load(CERT, KEY).unwrap();
+    let header = session::video_header(Codec::Jpeg, (320, 200));
+    let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    let (welcome, accepted) = rt.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = acceptor.accept(stream).await.unwrap();
+            session::accept(&mut stream, &header, &Tokens::default()).await.map(|(_, access)| access)
+        });
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut stream = tls::connect(stream, "localhost", CERT).await.unwrap();
+        let welcome = session::connect(&mut stream, &[Codec::Jpeg], (80, 24), "").await.unwrap();
+        (welcome, server.await.unwrap().unwrap())
+    });
+    assert_eq!((welcome.0.width, welcome.0.height), (320, 200));
+    assert_eq!((welcome.1, accepted), (Access::Control, Access::Control));
+}
+
+#[test]
+fn an_untrusted_name_is_refused() {
+    let acceptor = Acceptor::load(CERT, KEY).unwrap();
+    let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    let refused = rt.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let _ = acceptor.accept(stream).await;
+        });
+        let stream = TcpStream::connect(addr).await.unwrap();
+        tls::connect(stream, "example.com", CERT).await.err()
+    });
+    assert!(refused.is_some());
+    assert!(Acceptor::load(KEY, KEY).is_err());
+}
@@ -0,0 +1,76 @@
+use tokio::sync::Mutex;
+
+use dognut::department::common::base64;
+use dognut::department::net::http::{self, Request};
+use dognut::department::net::websocket::{self, OP_BINARY, OP_CLOSE, OP_CONTINUATION, OP_PING, OP_PONG};
+use dognut::pb::netpacket::{NetPacket, PacketKind};
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread().build().unwrap()
+}
+
+#[test]
+fn the_accept_key_matches_the_rfc() {
+    assert_eq!(websocket::accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+    assert_eq!(base64::encode(b"f"), "Zg==");
+    assert_eq!(base64::encode(b"fo"), "Zm8=");
+    assert_eq!(base64::encode(b"foo"), "Zm9v");
+}
+
+#[test]
+fn upgrade_requests_are_recognized() {
+    let request = Request::parse("GET /ws HTTP/1.1\r\nHost: dognut:9527\r\nUpgrade: websocket\r\n\
+                                  Connection: keep-alive, Upgrade\r\nSec-WebSocket-Key: abc").unwrap();
+    assert_eq!(request.route(), "/ws");
+    assert_eq!(request.header("sec-websocket-key"), Some("abc"));
+    assert!(websocket::is_upgrade(&request));
+
+    let page = Request::parse("GET /?debug HTTP/1.1\r\nHost: dognut").unwrap();
+    assert_eq!(page.route(), "/");
+    assert!(!websocket::is_upgrade(&page));
+    assert!(Request::parse("hello").is_err());
+
+    // a raw client starts with the length of its hello.
+    assert!(http::looks_like_http(b'G'));
+    assert!(!http::looks_like_http(0));
+}
+
+#[test]
+fn masked_fragments_make_one_packet() {
+    let mut packet = NetPacket::new();
+    packet.kind = protobuf::EnumOrUnknown::from(PacketKind::KeyframeRequest);
+    packet.data = vec![7; 300];
+    let serialized = protobuf::Message::write_to_bytes(&packet).unwrap();
+
+    let read = runtime().block_on(async {
+        let mut wire = Vec::new();
+        let mask = Some([1, 2, 3, 4]);
+        let (head, tail) = serialized.split_at(100);
+        websocket::write_frame(&mut wire, OP_PING, b"hi", mask).await.unwrap();
+        // only the last fragment has the fin bit, write_frame always sets it.
+        let start = wire.len();
+        websocket::write_frame(&mut wire, OP_BINARY, head, mask).await.unwrap();
+        wire[start] &= 0x7f;
+        websocket::write_frame(&mut wire, OP_CONTINUATION, tail, mask).await.unwrap();
+        // a normal closure, status 1000.
+        websocket::write_frame(&mut wire, OP_CLOSE, &[0x03, 0xe8], mask).await.unwrap();
+
+        let mut input = wire.as_slice();
+        let out = Mutex::new(Vec::new());
+        let read = websocket::read_packet(&mut input, &out).await.unwrap();
+        assert!(websocket::read_packet(&mut input, &out).await.is_err());
+        (read, out.into_inner())
+    });
+    assert_eq!(read.0, packet);
+    // the ping is answered with its payload, the close is echoed with its status.
+    assert_eq!(read.1, vec![0x80 | OP_PONG, 2, b'h', b'i', 0x80 | OP_CLOSE, 2, 0x03, 0xe8]);
+
+    // the server sends unmasked frames.
+    let echoed = runtime().block_on(async {
+        let mut wire = Vec::new();
+        websocket::write_packet(&mut wire, &packet).await.unwrap();
+        assert_eq!(wire[1], 126);
+        websocket::read_packet(&mut wire.as_slice(), &Mutex::new(Vec::new())).await.unwrap()
+    });
+    assert_eq!(echoed, packet);
+}